@@ -5,18 +5,102 @@ use crate::{
         health::HealthCheck,
         volume::Volume,
     },
+    container::duration_secs,
     error::DockerError,
+    image::tar_path_as,
 };
-use bollard::container::{Config, CreateContainerOptions, StartContainerOptions};
+use bollard::container::{
+    Config, CreateContainerOptions, RemoveContainerOptions, StartContainerOptions,
+    StopContainerOptions,
+};
+use bollard::errors::Error as BollardError;
 use bollard::network::CreateNetworkOptions;
 use bollard::service::{HealthConfig, HostConfig, Mount, PortBinding};
 use futures_util::StreamExt;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tar;
-use tempfile;
+use std::time::Duration;
 use uuid::Uuid;
-use walkdir;
+
+/// Docker's default grace period between `SIGTERM` and `SIGKILL`
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Options for [`DockerBuilder::compose_down`]
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::builder::compose::ComposeDownOptions;
+/// use std::time::Duration;
+///
+/// let options = ComposeDownOptions::new()
+///     .grace_period(Duration::from_secs(5))
+///     .service_grace_period("db", Duration::from_secs(30))
+///     .timeout(Duration::from_secs(120));
+/// assert_eq!(options.grace_period_for("db"), Duration::from_secs(30));
+/// assert_eq!(options.grace_period_for("web"), Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComposeDownOptions {
+    grace_period: Duration,
+    service_grace_periods: HashMap<String, Duration>,
+    timeout: Option<Duration>,
+}
+
+impl Default for ComposeDownOptions {
+    fn default() -> Self {
+        Self {
+            grace_period: DEFAULT_GRACE_PERIOD,
+            service_grace_periods: HashMap::new(),
+            timeout: None,
+        }
+    }
+}
+
+impl ComposeDownOptions {
+    /// Create options with Docker's default 10 second grace period and no overall timeout
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time each service is given to exit after `SIGTERM` before it is killed
+    ///
+    /// Docker only supports whole seconds, so this is rounded up.
+    #[must_use]
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Override the grace period for a single service
+    #[must_use]
+    pub fn service_grace_period(
+        mut self,
+        service: impl Into<String>,
+        grace_period: Duration,
+    ) -> Self {
+        self.service_grace_periods
+            .insert(service.into(), grace_period);
+        self
+    }
+
+    /// Set a limit on how long the entire teardown may take
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The grace period that will be used for `service`
+    #[must_use]
+    pub fn grace_period_for(&self, service: &str) -> Duration {
+        self.service_grace_periods
+            .get(service)
+            .copied()
+            .unwrap_or(self.grace_period)
+    }
+}
 
 impl DockerBuilder {
     /// Deploys a Docker Compose configuration with a custom base directory
@@ -142,7 +226,7 @@ impl DockerBuilder {
             if let Volume::Named(_) = volume_type {
                 self.client
                     .create_volume(bollard::volume::CreateVolumeOptions {
-                        name: volume_name.to_string(),
+                        name: volume_name.clone(),
                         ..Default::default()
                     })
                     .await
@@ -170,6 +254,119 @@ impl DockerBuilder {
         Ok(container_ids)
     }
 
+    /// Stops and removes the services deployed by [`DockerBuilder::deploy_compose`]
+    ///
+    /// Services are torn down in reverse dependency order, mirroring `docker compose down`. Each
+    /// service is stopped (and waited on) before any of its dependencies are touched, so a
+    /// database will never disappear out from under a service still using it.
+    ///
+    /// Services missing from `container_ids`, or whose containers no longer exist, are skipped.
+    ///
+    /// NOTE: The network and volumes created by the deployment are left in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Docker Compose configuration that was deployed
+    /// * `container_ids` - The service name to container ID mapping returned by the deployment
+    /// * `options` - Grace periods and an optional timeout for the teardown
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::builder::compose::ComposeDownOptions;
+    /// use docktopus::parser::ComposeParser;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::new().await?;
+    /// let mut config = ComposeParser::new().parse_from_path("docker-compose.yml")?;
+    /// let container_ids = builder.deploy_compose(&mut config).await?;
+    ///
+    /// let options = ComposeDownOptions::new()
+    ///     .service_grace_period("db", Duration::from_secs(30))
+    ///     .timeout(Duration::from_secs(120));
+    /// builder
+    ///     .compose_down(&config, &container_ids, &options)
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `DockerError` if:
+    /// - The configuration has circular dependencies
+    /// - Stopping or removing a container fails
+    /// - The teardown takes longer than the configured timeout
+    pub async fn compose_down(
+        &self,
+        config: &ComposeConfig,
+        container_ids: &HashMap<String, String>,
+        options: &ComposeDownOptions,
+    ) -> Result<(), DockerError> {
+        let mut order = config.resolve_service_order()?;
+        order.reverse();
+
+        let teardown = async {
+            for service_name in &order {
+                let Some(container_id) = container_ids.get(service_name) else {
+                    continue;
+                };
+
+                self.stop_service(container_id, options.grace_period_for(service_name))
+                    .await?;
+            }
+
+            Ok(())
+        };
+
+        match options.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, teardown)
+                .await
+                .map_err(|_| DockerError::TeardownTimeout(timeout))?,
+            None => teardown.await,
+        }
+    }
+
+    /// Stops a single compose service and removes its container
+    ///
+    /// A container that is already stopped or already gone is not an error.
+    async fn stop_service(
+        &self,
+        container_id: &str,
+        grace_period: Duration,
+    ) -> Result<(), DockerError> {
+        let stop = StopContainerOptions {
+            t: duration_secs(grace_period),
+        };
+
+        // The daemon only responds once the container has exited. Bollard treats the
+        // `304 Not Modified` for an already stopped container as a success.
+        match self.client.stop_container(container_id, Some(stop)).await {
+            Ok(()) => {}
+            Err(BollardError::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                log::debug!("Container {container_id} is already gone");
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        match self
+            .client
+            .remove_container(container_id, None::<RemoveContainerOptions>)
+            .await
+        {
+            Ok(())
+            | Err(BollardError::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Creates a Docker [`HealthConfig`] from a [`HealthCheck`] configuration
     ///
     /// This method converts our internal [`HealthCheck`] configuration into the format
@@ -247,39 +444,8 @@ impl DockerBuilder {
                 )));
             }
 
-            // Create a temporary directory for the build context
-            let temp_dir = tempfile::tempdir()?;
-            let temp_dockerfile = temp_dir.path().join("Dockerfile");
-
-            // Copy the Dockerfile to temp directory
-            tokio::fs::copy(&dockerfile_path, &temp_dockerfile).await?;
-
-            // Create tar archive with the Dockerfile and context
-            let tar_path = temp_dir.path().join("context.tar");
-            let tar_file = std::fs::File::create(&tar_path)?;
-            let mut tar_builder = tar::Builder::new(tar_file);
-
-            // Add Dockerfile to tar
-            tar_builder.append_path_with_name(&temp_dockerfile, "Dockerfile")?;
-
-            // Add context directory to tar
-            for entry in walkdir::WalkDir::new(&context_path)
-                .follow_links(true)
-                .into_iter()
-                .filter_map(Result::ok)
-            {
-                let path = entry.path();
-                if path.is_file() {
-                    let relative_path = path
-                        .strip_prefix(&context_path)
-                        .map_err(|e| DockerError::ValidationError(e.to_string()))?;
-                    tar_builder.append_path_with_name(path, relative_path)?;
-                }
-            }
-            tar_builder.finish()?;
-
-            // Read the tar file
-            let context = tokio::fs::read(&tar_path).await?;
+            // The Dockerfile is archived with the rest of the context, at its relative path
+            let context = tar_path_as(&context_path, ".")?;
 
             // Build the image using Bollard API
             let build_opts = bollard::image::BuildImageOptions {
@@ -448,7 +614,7 @@ fn make_bind_paths_absolute(
     Ok(())
 }
 
-fn normalize_path(base: &Path, path: &str) -> PathBuf {
+pub(crate) fn normalize_path(base: &Path, path: &str) -> PathBuf {
     let path = PathBuf::from(path);
     if path.is_absolute() {
         return path;
@@ -509,3 +675,91 @@ fn create_host_config(service: &Service, network_name: &str) -> HostConfig {
 
     host_config
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_daemon::{FakeDaemon, Response};
+
+    /// A daemon that knows nothing of the `gone` container
+    fn daemon() -> FakeDaemon {
+        FakeDaemon::with_handler(|line| {
+            Some(if line.contains("/containers/gone/") {
+                Response::status(404, r#"{"message":"No such container: gone"}"#)
+            } else {
+                Response::status(204, "")
+            })
+        })
+    }
+
+    fn service(depends_on: &[&str]) -> Service {
+        Service {
+            image: Some(String::from("alpine")),
+            depends_on: Some(depends_on.iter().map(ToString::to_string).collect()),
+            ..Service::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compose_down_order() {
+        let daemon = daemon();
+        let builder = DockerBuilder {
+            client: daemon.client(),
+            host: String::new(),
+        };
+
+        let config = ComposeConfig {
+            version: String::from("3"),
+            services: HashMap::from([
+                (String::from("db"), service(&[])),
+                (String::from("api"), service(&["db"])),
+                (String::from("web"), service(&["api"])),
+                (String::from("worker"), service(&["db"])),
+            ]),
+            volumes: HashMap::new(),
+        };
+        let container_ids = HashMap::from([
+            (String::from("db"), String::from("db")),
+            (String::from("api"), String::from("api")),
+            (String::from("web"), String::from("web")),
+            (String::from("worker"), String::from("gone")),
+        ]);
+
+        let options = ComposeDownOptions::new()
+            .grace_period(Duration::from_millis(2500))
+            .service_grace_period("db", Duration::from_secs(30));
+        builder
+            .compose_down(&config, &container_ids, &options)
+            .await
+            .unwrap();
+
+        let requests = daemon.requests();
+        let position = |needle: &str| {
+            requests
+                .iter()
+                .position(|r| r.contains(needle))
+                .unwrap_or_else(|| panic!("no request for {needle}: {requests:?}"))
+        };
+
+        // Every dependent is stopped and removed before its dependencies are stopped
+        let stop_db = position("/containers/db/stop");
+        assert!(position("DELETE /containers/api") < stop_db);
+        assert!(position("/containers/gone/stop") < stop_db);
+        assert!(position("DELETE /containers/web") < position("/containers/api/stop"));
+        assert!(
+            requests
+                .iter()
+                .any(|r| r.starts_with("DELETE /containers/db"))
+        );
+
+        // The missing container is skipped rather than removed
+        assert!(
+            !requests
+                .iter()
+                .any(|r| r.starts_with("DELETE /containers/gone"))
+        );
+
+        assert!(requests[stop_db].contains("t=30"), "{}", requests[stop_db]);
+        assert!(requests[position("/containers/web/stop")].contains("t=3 "));
+    }
+}
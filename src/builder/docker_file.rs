@@ -1,5 +1,6 @@
 use crate::config::docker_file::DockerfileConfig;
 use crate::error::DockerError;
+use crate::image::tar_path_as;
 use bollard::container::{Config, CreateContainerOptions, StartContainerOptions};
 use bollard::image::BuildImageOptions;
 use bollard::service::HostConfig;
@@ -85,15 +86,8 @@ impl DockerBuilder {
         // Write the Dockerfile content from our config
         tokio::fs::write(&dockerfile_path, config.to_string()).await?;
 
-        // Create tar archive with the Dockerfile
-        let tar_path = temp_dir.path().join("context.tar");
-        let tar_file = std::fs::File::create(&tar_path)?;
-        let mut tar_builder = tar::Builder::new(tar_file);
-        tar_builder.append_path_with_name(&dockerfile_path, "Dockerfile")?;
-        tar_builder.finish()?;
-
-        // Read the tar file
-        let context = tokio::fs::read(&tar_path).await?;
+        // The Dockerfile is the only thing in the build context
+        let context = tar_path_as(&dockerfile_path, "Dockerfile")?;
 
         // Build the image
         let build_opts = BuildImageOptions {
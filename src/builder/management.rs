@@ -1,14 +1,169 @@
 use crate::DockerBuilder;
 use crate::error::DockerError;
-use bollard::container::LogsOptions;
+use bollard::auth::DockerCredentials;
+use bollard::container::{LogsOptions, PruneContainersOptions};
 use bollard::exec::{CreateExecOptions, StartExecOptions};
-use bollard::network::CreateNetworkOptions;
-use bollard::volume::{CreateVolumeOptions, ListVolumesOptions};
-use futures_util::{StreamExt, TryStreamExt};
+use bollard::image::PruneImagesOptions;
+use bollard::models::CreateImageInfo;
+use bollard::network::{CreateNetworkOptions, PruneNetworksOptions};
+use bollard::volume::{CreateVolumeOptions, ListVolumesOptions, PruneVolumesOptions};
+use futures_util::{Stream, StreamExt, TryStreamExt};
 use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::sleep;
 
+/// A progress event from [`DockerBuilder::pull_image_with_progress`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullProgress {
+    /// The layer this event is for, if any
+    pub id: Option<String>,
+    /// The status text (e.g. `Downloading`, `Pull complete`)
+    pub status: String,
+    /// How far along the current layer operation is, from `0.0` to `100.0`, if known
+    pub percentage: Option<f64>,
+}
+
+impl PullProgress {
+    /// Whether this is the final event of a pull
+    ///
+    /// The daemon ends every successful pull with a `Status: ...` message, either
+    /// `Downloaded newer image` or `Image is up to date`.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.id.is_none() && self.status.starts_with("Status:")
+    }
+}
+
+impl From<CreateImageInfo> for PullProgress {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(info: CreateImageInfo) -> Self {
+        let percentage =
+            info.progress_detail
+                .and_then(|detail| match (detail.current, detail.total) {
+                    (Some(current), Some(total)) if total > 0 => {
+                        Some((current as f64 / total as f64 * 100.0).clamp(0.0, 100.0))
+                    }
+                    _ => None,
+                });
+
+        Self {
+            id: info.id,
+            status: info.status.unwrap_or_default(),
+            percentage,
+        }
+    }
+}
+
+/// What [`DockerBuilder::prune`] should remove
+///
+/// Nothing is pruned by default, each kind of resource has to be opted into.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::builder::PruneTargets;
+///
+/// // Only clean up after our own CI jobs
+/// let targets = PruneTargets::new()
+///     .containers(true)
+///     .images(true)
+///     .label("com.myorg.ci");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneTargets {
+    containers: bool,
+    images: bool,
+    networks: bool,
+    volumes: bool,
+    labels: Vec<String>,
+}
+
+impl PruneTargets {
+    /// Prune nothing
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prune stopped containers, dangling images, unused networks, and unused volumes
+    #[must_use]
+    pub fn all() -> Self {
+        Self::new()
+            .containers(true)
+            .images(true)
+            .networks(true)
+            .volumes(true)
+    }
+
+    /// Remove stopped containers (equivalent to `docker container prune`)
+    #[must_use]
+    pub fn containers(mut self, containers: bool) -> Self {
+        self.containers = containers;
+        self
+    }
+
+    /// Remove dangling images (equivalent to `docker image prune`)
+    #[must_use]
+    pub fn images(mut self, images: bool) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// Remove networks not used by any container (equivalent to `docker network prune`)
+    #[must_use]
+    pub fn networks(mut self, networks: bool) -> Self {
+        self.networks = networks;
+        self
+    }
+
+    /// Remove anonymous volumes not used by any container (equivalent to `docker volume prune`)
+    #[must_use]
+    pub fn volumes(mut self, volumes: bool) -> Self {
+        self.volumes = volumes;
+        self
+    }
+
+    /// Only prune resources with a label, either `key`, `key=value`, or negated with `key!=...`
+    ///
+    /// This can be called multiple times, in which case all labels must match.
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    fn filters(&self) -> HashMap<String, Vec<String>> {
+        let mut filters = HashMap::new();
+        for label in &self.labels {
+            // The daemon expects negated labels under their own filter
+            let (filter, label) = match label.split_once("!=") {
+                Some((key, value)) => ("label!", format!("{key}={value}")),
+                None => ("label", label.clone()),
+            };
+            filters
+                .entry(filter.to_string())
+                .or_insert_with(Vec::new)
+                .push(label);
+        }
+        filters
+    }
+}
+
+/// What was removed by [`DockerBuilder::prune`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// IDs of the removed containers
+    pub containers_deleted: Vec<String>,
+    /// IDs of the removed images
+    pub images_deleted: Vec<String>,
+    /// Names of the removed networks
+    pub networks_deleted: Vec<String>,
+    /// Names of the removed volumes
+    pub volumes_deleted: Vec<String>,
+    /// The total disk space freed, in bytes
+    pub space_reclaimed: u64,
+}
+
 impl DockerBuilder {
     /// Creates a network with extra creation settings
     ///
@@ -158,6 +313,56 @@ impl DockerBuilder {
         Ok(())
     }
 
+    /// Pulls a Docker image, streaming its progress
+    ///
+    /// Unlike [`DockerBuilder::pull_image`], this reports each progress event as it arrives and
+    /// supports pulling from private registries with `credentials`. The pull is complete when the
+    /// stream ends, the last event will satisfy [`PullProgress::is_complete`].
+    ///
+    /// # Errors
+    ///
+    /// Each item will be a `DockerError::BollardError` if the pull fails (e.g. the image doesn't
+    /// exist, or the credentials are rejected)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::new().await?;
+    ///
+    /// let mut progress = builder.pull_image_with_progress("ubuntu:latest", None);
+    /// while let Some(event) = progress.next().await {
+    ///     let event = event?;
+    ///     match (event.id, event.percentage) {
+    ///         (Some(layer), Some(percentage)) => {
+    ///             println!("{layer}: {} {percentage:.1}%", event.status)
+    ///         }
+    ///         _ => println!("{}", event.status),
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn pull_image_with_progress(
+        &self,
+        image: &str,
+        credentials: Option<DockerCredentials>,
+    ) -> impl Stream<Item = Result<PullProgress, DockerError>> + use<> {
+        self.client
+            .create_image(
+                Some(bollard::image::CreateImageOptions {
+                    from_image: image.to_string(),
+                    ..Default::default()
+                }),
+                None,
+                credentials,
+            )
+            .map_ok(PullProgress::from)
+            .map_err(DockerError::BollardError)
+    }
+
     /// Lists all Docker networks
     ///
     /// This method retrieves a list of all Docker networks present on the system.
@@ -465,4 +670,119 @@ impl DockerBuilder {
             _ => Ok(String::new()),
         }
     }
+
+    /// Remove unused resources to reclaim disk space
+    ///
+    /// Containers are pruned first, so any images, networks, and volumes only they were using
+    /// can be pruned in the same call.
+    ///
+    /// # Errors
+    ///
+    /// Will return a `DockerError::BollardError` if any of the prunes fail. Resources pruned
+    /// before the failure stay removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::builder::PruneTargets;
+    ///
+    /// # async fn example() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::new().await?;
+    /// let report = builder.prune(PruneTargets::all()).await?;
+    /// println!("Reclaimed {} bytes", report.space_reclaimed);
+    /// # Ok(()) }
+    /// ```
+    pub async fn prune(&self, targets: PruneTargets) -> Result<PruneReport, DockerError> {
+        // Negative sizes have been seen from some storage drivers
+        let bytes = |space: Option<i64>| space.and_then(|space| u64::try_from(space).ok());
+
+        let filters = targets.filters();
+        let mut report = PruneReport::default();
+
+        if targets.containers {
+            let pruned = self
+                .client()
+                .prune_containers(Some(PruneContainersOptions {
+                    filters: filters.clone(),
+                }))
+                .await?;
+            report.containers_deleted = pruned.containers_deleted.unwrap_or_default();
+            report.space_reclaimed += bytes(pruned.space_reclaimed).unwrap_or_default();
+        }
+
+        if targets.images {
+            let pruned = self
+                .client()
+                .prune_images(Some(PruneImagesOptions {
+                    filters: filters.clone(),
+                }))
+                .await?;
+            report.images_deleted = pruned
+                .images_deleted
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|image| image.deleted)
+                .collect();
+            report.space_reclaimed += bytes(pruned.space_reclaimed).unwrap_or_default();
+        }
+
+        if targets.networks {
+            let pruned = self
+                .client()
+                .prune_networks(Some(PruneNetworksOptions {
+                    filters: filters.clone(),
+                }))
+                .await?;
+            report.networks_deleted = pruned.networks_deleted.unwrap_or_default();
+        }
+
+        if targets.volumes {
+            let pruned = self
+                .client()
+                .prune_volumes(Some(PruneVolumesOptions { filters }))
+                .await?;
+            report.volumes_deleted = pruned.volumes_deleted.unwrap_or_default();
+            report.space_reclaimed += bytes(pruned.space_reclaimed).unwrap_or_default();
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::ProgressDetail;
+
+    #[test]
+    fn test_pull_progress_percentage() {
+        let progress = PullProgress::from(CreateImageInfo {
+            id: Some(String::from("a1b2c3")),
+            status: Some(String::from("Downloading")),
+            progress_detail: Some(ProgressDetail {
+                current: Some(256),
+                total: Some(1024),
+            }),
+            ..Default::default()
+        });
+        assert_eq!(progress.id.as_deref(), Some("a1b2c3"));
+        assert_eq!(progress.status, "Downloading");
+        assert_eq!(progress.percentage, Some(25.0));
+        assert!(!progress.is_complete());
+
+        // Some events have an empty detail, or an unknown total
+        let progress = PullProgress::from(CreateImageInfo {
+            status: Some(String::from(
+                "Status: Downloaded newer image for alpine:latest",
+            )),
+            progress_detail: Some(ProgressDetail {
+                current: None,
+                total: Some(0),
+            }),
+            ..Default::default()
+        });
+        assert_eq!(progress.percentage, None);
+        assert!(progress.is_complete());
+    }
 }
@@ -1,18 +1,48 @@
+use crate::DockerError;
 use bollard::{API_DEFAULT_VERSION, Docker};
 use std::ops::Deref;
+#[cfg(feature = "tls")]
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub mod compose;
 pub mod docker_file;
 pub mod management;
 
+pub use management::{PruneReport, PruneTargets, PullProgress};
+
+/// Bollard's default request timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The Docker daemon's version, see [`DockerBuilder::version`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// The Docker Engine version, e.g. `27.3.1`
+    pub version: String,
+    /// The newest API version the daemon supports, e.g. `1.47`
+    pub api_version: String,
+    /// The oldest API version the daemon supports, if reported
+    pub min_api_version: Option<String>,
+    /// The daemon's operating system, e.g. `linux`
+    pub os: String,
+    /// The daemon's architecture, e.g. `amd64`
+    pub arch: String,
+    /// The host's kernel version, if reported
+    pub kernel_version: Option<String>,
+}
+
 pub struct DockerBuilder {
     client: Arc<Docker>,
+    host: String,
 }
 
 impl DockerBuilder {
     /// Create a new `DockerBuilder`
     ///
+    /// Requests use bollard's default timeout of 120 seconds. See [`DockerBuilder::with_timeout`]
+    /// to change it.
+    ///
     /// # Errors
     ///
     /// This will attempt to connect to and ping the docker server. If either fails, this will return
@@ -26,6 +56,75 @@ impl DockerBuilder {
 
         Ok(Self {
             client: Arc::new(client),
+            host: local_host(),
+        })
+    }
+
+    /// Create a new `DockerBuilder`, retrying while the daemon isn't ready
+    ///
+    /// This is useful in CI, where the daemon may still be starting up alongside the tests. The
+    /// daemon is pinged up to `attempts` times, waiting `backoff` after the first failure and
+    /// doubling the wait after each one. Each wait is randomly jittered by up to 50% either way,
+    /// so parallel test suites don't retry in lockstep.
+    ///
+    /// # Errors
+    ///
+    /// The daemon still can't be reached after `attempts` tries, see
+    /// [`DockerError::ConnectionFailed`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::DockerError> {
+    /// // Waits up to ~6 seconds in total (0.2 + 0.4 + 0.8 + 1.6 + 3.2)
+    /// let builder = DockerBuilder::new_with_retry(6, Duration::from_millis(200)).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn new_with_retry(attempts: u32, backoff: Duration) -> Result<Self, DockerError> {
+        Self::connect_with_retry(&local_host(), attempts, backoff).await
+    }
+
+    async fn connect_with_retry(
+        addr: &str,
+        attempts: u32,
+        backoff: Duration,
+    ) -> Result<Self, DockerError> {
+        let attempts = attempts.max(1);
+        let mut delay = backoff;
+        let mut last_error = None;
+        for attempt in 1..=attempts {
+            let connected = match Docker::connect_with_local(addr, 20, API_DEFAULT_VERSION) {
+                Ok(client) => client.ping().await.map(|_| client),
+                Err(e) => Err(e),
+            };
+            match connected {
+                Ok(client) => {
+                    return Ok(Self {
+                        client: Arc::new(client),
+                        host: addr.to_string(),
+                    });
+                }
+                Err(e) => {
+                    log::debug!("Docker daemon not ready (attempt {attempt}/{attempts}): {e}");
+                    last_error = Some(e);
+                }
+            }
+
+            if attempt < attempts {
+                tokio::time::sleep(jitter(delay)).await;
+                delay = delay.saturating_mul(2);
+            }
+        }
+
+        let reason = last_error.map(|e| e.to_string()).unwrap_or_default();
+        log::error!("Failed to connect to docker server after {attempts} attempts: {reason}");
+        Err(DockerError::ConnectionFailed {
+            host: addr.to_string(),
+            reason: format!("gave up after {attempts} attempts, last error: {reason}"),
         })
     }
 
@@ -44,6 +143,169 @@ impl DockerBuilder {
 
         Ok(Self {
             client: Arc::new(client),
+            host: addr.to_string(),
+        })
+    }
+
+    /// Create a new `DockerBuilder` connected to a remote daemon over plain HTTP
+    ///
+    /// `host` may be given as `tcp://host:port`, `http://host:port` or `host:port`.
+    ///
+    /// # Errors
+    ///
+    /// This will attempt to connect to and ping the docker server. If either fails, this will return
+    /// an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::with_http("tcp://10.0.0.5:2375").await?;
+    /// println!("Connected to {}", builder.host());
+    /// # Ok(()) }
+    /// ```
+    pub async fn with_http(host: &str) -> Result<Self, DockerError> {
+        let client =
+            Docker::connect_with_http(host, DEFAULT_TIMEOUT.as_secs(), API_DEFAULT_VERSION)?;
+        Self::connect(client, host, DEFAULT_TIMEOUT).await
+    }
+
+    /// Create a new `DockerBuilder` connected to a remote daemon over TLS
+    ///
+    /// `host` may be given as `tcp://host:port`, `https://host:port` or `host:port`. The client
+    /// certificate, key and CA are the PEM files usually found in `$DOCKER_CERT_PATH`
+    /// (`cert.pem`, `key.pem` and `ca.pem`).
+    ///
+    /// NOTE: This requires the `tls` feature.
+    ///
+    /// # Errors
+    ///
+    /// * [`DockerError::ValidationError`] if any of the certificate files don't exist
+    /// * [`DockerError::BollardError`] if connecting to or pinging the daemon fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::with_tls(
+    ///     "tcp://docker.example.com:2376",
+    ///     "/etc/docker/certs/cert.pem",
+    ///     "/etc/docker/certs/key.pem",
+    ///     "/etc/docker/certs/ca.pem",
+    /// )
+    /// .await?;
+    /// println!("Connected to {}", builder.host());
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub async fn with_tls(
+        host: &str,
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+        ca_path: impl AsRef<Path>,
+    ) -> Result<Self, DockerError> {
+        let (cert_path, key_path, ca_path) =
+            (cert_path.as_ref(), key_path.as_ref(), ca_path.as_ref());
+        for (kind, path) in [
+            ("certificate", cert_path),
+            ("key", key_path),
+            ("CA certificate", ca_path),
+        ] {
+            if !path.is_file() {
+                return Err(DockerError::ValidationError(format!(
+                    "TLS {kind} file not found: {}",
+                    path.display()
+                )));
+            }
+        }
+
+        let client = Docker::connect_with_ssl(
+            host,
+            key_path,
+            cert_path,
+            ca_path,
+            DEFAULT_TIMEOUT.as_secs(),
+            API_DEFAULT_VERSION,
+        )?;
+        Self::connect(client, host, DEFAULT_TIMEOUT).await
+    }
+
+    /// Create a new `DockerBuilder` with a custom request timeout
+    ///
+    /// The timeout applies to every request made through the client, and separately bounds the
+    /// initial connection to the daemon, so an unreachable daemon fails fast rather than hanging.
+    /// The default, as used by [`DockerBuilder::new`], is 120 seconds.
+    ///
+    /// # Errors
+    ///
+    /// * [`DockerError::ConnectionTimeout`] if the daemon doesn't respond to a ping within `timeout`
+    /// * [`DockerError::BollardError`] if connecting to or pinging the daemon otherwise fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::{DockerBuilder, DockerError};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DockerError> {
+    /// let builder = match DockerBuilder::with_timeout(Duration::from_secs(5)).await {
+    ///     Err(DockerError::ConnectionTimeout(_)) => {
+    ///         // Retry later
+    ///         return Ok(());
+    ///     }
+    ///     res => res?,
+    /// };
+    /// # Ok(()) }
+    /// ```
+    pub async fn with_timeout(timeout: Duration) -> Result<Self, DockerError> {
+        let client = Docker::connect_with_local_defaults()?;
+        Self::connect(client, &local_host(), timeout).await
+    }
+
+    /// Create a new `DockerBuilder` with a custom address and request timeout
+    ///
+    /// See [`DockerBuilder::with_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// See [`DockerBuilder::with_timeout`].
+    pub async fn with_address_and_timeout(
+        addr: &str,
+        timeout: Duration,
+    ) -> Result<Self, DockerError> {
+        // Round up, so a sub-second timeout isn't treated as none at all
+        let secs = timeout
+            .as_secs()
+            .saturating_add(u64::from(timeout.subsec_nanos() > 0));
+        let client = Docker::connect_with_local(addr, secs, API_DEFAULT_VERSION)?;
+        Self::connect(client, addr, timeout).await
+    }
+
+    async fn connect(client: Docker, host: &str, timeout: Duration) -> Result<Self, DockerError> {
+        let client = client.with_timeout(timeout);
+        match tokio::time::timeout(timeout, client.ping()).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) if !matches!(e, bollard::errors::Error::RequestTimeoutError) => {
+                log::error!("Failed to ping docker server: {}", e);
+                return Err(e.into());
+            }
+            // Either bollard or our own timeout may fire first
+            Ok(Err(_)) | Err(_) => {
+                log::error!("Timed out after {:?} connecting to docker server", timeout);
+                return Err(DockerError::ConnectionTimeout(timeout));
+            }
+        }
+
+        Ok(Self {
+            client: Arc::new(client),
+            host: host.to_string(),
         })
     }
 
@@ -51,6 +313,123 @@ impl DockerBuilder {
     pub fn client(&self) -> Arc<Docker> {
         self.client.clone()
     }
+
+    /// The address of the daemon this builder is connected to
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Check that the daemon is still responsive
+    ///
+    /// # Errors
+    ///
+    /// * The daemon can't be reached, see [`DockerError::ConnectionFailed`]
+    /// * The daemon responds with an error
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::new().await?;
+    ///
+    /// // Before a long deployment, make sure the daemon hasn't gone away
+    /// builder.ping().await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn ping(&self) -> Result<(), DockerError> {
+        self.client
+            .ping()
+            .await
+            .map_err(|e| self.connection_error(e))?;
+        Ok(())
+    }
+
+    /// Get the daemon's version and platform
+    ///
+    /// # Errors
+    ///
+    /// * The daemon can't be reached, see [`DockerError::ConnectionFailed`]
+    /// * The daemon responds with an error
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::DockerError> {
+    /// let builder = DockerBuilder::new().await?;
+    ///
+    /// let version = builder.version().await?;
+    /// println!(
+    ///     "Docker {} (API {}) on {}/{}",
+    ///     version.version, version.api_version, version.os, version.arch
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub async fn version(&self) -> Result<VersionInfo, DockerError> {
+        let version = self
+            .client
+            .version()
+            .await
+            .map_err(|e| self.connection_error(e))?;
+
+        Ok(VersionInfo {
+            version: version.version.unwrap_or_default(),
+            api_version: version.api_version.unwrap_or_default(),
+            min_api_version: version.min_api_version,
+            os: version.os.unwrap_or_default(),
+            arch: version.arch.unwrap_or_default(),
+            kernel_version: version.kernel_version,
+        })
+    }
+
+    /// Report failures to reach the daemon as [`DockerError::ConnectionFailed`]
+    fn connection_error(&self, e: bollard::errors::Error) -> DockerError {
+        use bollard::errors::Error;
+
+        match e {
+            Error::IOError { .. }
+            | Error::HyperResponseError { .. }
+            | Error::HyperLegacyError { .. }
+            | Error::SocketNotFoundError(_)
+            | Error::RequestTimeoutError => DockerError::ConnectionFailed {
+                host: self.host.clone(),
+                reason: e.to_string(),
+            },
+            e => e.into(),
+        }
+    }
+}
+
+/// Randomly scale `delay` by 50-150%
+pub(crate) fn jitter(delay: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    // `RandomState` is randomly seeded, which is plenty for spreading out retries
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    #[allow(clippy::cast_precision_loss)]
+    let factor = 0.5 + (random as f64 / u64::MAX as f64);
+    delay.mul_f64(factor)
+}
+
+/// The address bollard resolves for a local connection
+fn local_host() -> String {
+    #[cfg(unix)]
+    let default = "unix:///var/run/docker.sock";
+    #[cfg(windows)]
+    let default = "npipe:////./pipe/docker_engine";
+
+    std::env::var("DOCKER_HOST")
+        .ok()
+        .filter(|host| cfg!(windows) || host.starts_with("unix://"))
+        .unwrap_or_else(|| default.to_string())
 }
 
 impl Deref for DockerBuilder {
@@ -60,3 +439,160 @@ impl Deref for DockerBuilder {
         &self.client
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_connection_timeout() {
+        use std::os::unix::net::UnixListener;
+        use std::time::Instant;
+
+        // A socket that accepts connections but never responds
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("docker.sock");
+        let _listener = UnixListener::bind(&socket).unwrap();
+
+        let timeout = Duration::from_millis(500);
+        let start = Instant::now();
+        let result =
+            DockerBuilder::with_address_and_timeout(socket.to_str().unwrap(), timeout).await;
+
+        assert!(
+            matches!(result, Err(DockerError::ConnectionTimeout(t)) if t == timeout),
+            "{:?}",
+            result.err()
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_connect_with_retry() {
+        use std::time::Instant;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("docker.sock");
+
+        // The daemon only comes up after a few attempts
+        let path = socket.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            let listener = UnixListener::bind(path).unwrap();
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK")
+                    .await;
+            }
+        });
+
+        let addr = socket.to_str().unwrap();
+        let builder = DockerBuilder::connect_with_retry(addr, 10, Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert_eq!(builder.host(), addr);
+
+        // Nothing ever listens here
+        let missing = dir.path().join("missing.sock");
+        let start = Instant::now();
+        let Err(err) = DockerBuilder::connect_with_retry(
+            missing.to_str().unwrap(),
+            3,
+            Duration::from_millis(20),
+        )
+        .await
+        else {
+            panic!("expected the connection to fail");
+        };
+        assert!(
+            matches!(&err, DockerError::ConnectionFailed { reason, .. } if reason.contains("3 attempts")),
+            "{err:?}"
+        );
+        // Two jittered waits, of 20ms and 40ms
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_jitter() {
+        let delay = Duration::from_millis(100);
+        for _ in 0..100 {
+            let jittered = jitter(delay);
+            assert!(jittered >= Duration::from_millis(50), "{jittered:?}");
+            assert!(jittered <= Duration::from_millis(150), "{jittered:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ping_unreachable() {
+        // Grab a free port, then close it
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let host = format!("http://{addr}");
+        let builder = DockerBuilder {
+            client: Arc::new(Docker::connect_with_http(&host, 1, API_DEFAULT_VERSION).unwrap()),
+            host: host.clone(),
+        };
+
+        let err = builder.ping().await.unwrap_err();
+        assert!(
+            matches!(&err, DockerError::ConnectionFailed { host: h, .. } if *h == host),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains(&host), "{err}");
+        assert!(matches!(
+            builder.version().await,
+            Err(DockerError::ConnectionFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_version() {
+        use crate::fake_daemon::FakeDaemon;
+
+        const VERSION: &str = r#"{"Version":"27.3.1","ApiVersion":"1.47","MinAPIVersion":"1.24","Os":"linux","Arch":"amd64","KernelVersion":"6.8.0"}"#;
+
+        let daemon = FakeDaemon::serve(VERSION);
+        let builder = DockerBuilder {
+            client: daemon.client_with_timeout(1),
+            host: daemon.url(),
+        };
+        assert_eq!(
+            builder.version().await.unwrap(),
+            VersionInfo {
+                version: String::from("27.3.1"),
+                api_version: String::from("1.47"),
+                min_api_version: Some(String::from("1.24")),
+                os: String::from("linux"),
+                arch: String::from("amd64"),
+                kernel_version: Some(String::from("6.8.0")),
+            }
+        );
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn test_tls_missing_cert() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("key.pem");
+        let ca = dir.path().join("ca.pem");
+        std::fs::write(&key, "").unwrap();
+        std::fs::write(&ca, "").unwrap();
+
+        let cert = dir.path().join("cert.pem");
+        let result = DockerBuilder::with_tls("tcp://127.0.0.1:2376", &cert, &key, &ca).await;
+
+        let Err(DockerError::ValidationError(msg)) = result else {
+            panic!("expected a validation error");
+        };
+        assert!(msg.contains("certificate"), "{msg}");
+        assert!(msg.contains(&cert.display().to_string()), "{msg}");
+    }
+}
@@ -0,0 +1,385 @@
+//! Utilities for turning Docker Compose files into [`Container`]s
+
+use crate::builder::compose::normalize_path;
+use crate::config::compose::Service;
+use crate::config::volume::Volume;
+use crate::container::Container;
+use crate::error::DockerError;
+use crate::stack::Stack;
+use bollard::Docker;
+use bollard::models::{RestartPolicy, RestartPolicyNameEnum};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The service keys understood by [`Compose`], everything else is reported as a warning
+const SUPPORTED_KEYS: &[&str] = &[
+    "image",
+    "command",
+    "environment",
+    "ports",
+    "volumes",
+    "restart",
+    "depends_on",
+];
+
+/// A subset of a Docker Compose file, as [`Container`] builders
+///
+/// Only the `image`, `command`, `environment`, `ports`, `volumes`, `restart`, and `depends_on`
+/// service keys are supported. Any other keys are ignored, and listed in
+/// [`Compose::warnings`]. For full Compose deployments (builds, networks, healthchecks, etc.),
+/// see [`DockerBuilder::deploy_compose`](crate::DockerBuilder::deploy_compose).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use docktopus::DockerBuilder;
+/// use docktopus::compose::Compose;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let compose = Compose::from_yaml(&std::fs::read_to_string("docker-compose.yml")?)?;
+/// for warning in compose.warnings() {
+///     eprintln!("{warning}");
+/// }
+///
+/// let connection = DockerBuilder::new().await?;
+/// let mut stack = compose.stack(connection.client())?;
+/// stack.up().await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Compose {
+    /// Services in the order they appear in the file
+    services: Vec<(String, Service)>,
+    warnings: Vec<String>,
+}
+
+impl Compose {
+    /// Parse a Compose file
+    ///
+    /// # Errors
+    ///
+    /// * The YAML is malformed, or has no `services` mapping
+    /// * A supported key has an invalid value
+    pub fn from_yaml(yaml: &str) -> Result<Self, DockerError> {
+        let mut compose = Self::default();
+
+        let Value::Mapping(root) = serde_yaml::from_str::<Value>(yaml)? else {
+            return Err(DockerError::ValidationError(String::from(
+                "Compose file must be a mapping",
+            )));
+        };
+        for key in root.keys().filter_map(Value::as_str) {
+            if !matches!(key, "version" | "services") {
+                compose
+                    .warnings
+                    .push(format!("`{key}` is not supported, ignoring it"));
+            }
+        }
+
+        let Some(Value::Mapping(services)) = root.get("services") else {
+            return Err(DockerError::ValidationError(String::from(
+                "Compose file has no `services` mapping",
+            )));
+        };
+        for (name, service) in services {
+            let Some(name) = name.as_str() else {
+                return Err(DockerError::ValidationError(format!(
+                    "Invalid service name: {name:?}"
+                )));
+            };
+            let service = compose.parse_service(name, service)?;
+            compose.services.push((name.to_string(), service));
+        }
+
+        Ok(compose)
+    }
+
+    fn parse_service(&mut self, name: &str, service: &Value) -> Result<Service, DockerError> {
+        let mut supported = Mapping::new();
+        for (key, value) in service.as_mapping().into_iter().flatten() {
+            let Some(key) = key.as_str().filter(|key| SUPPORTED_KEYS.contains(key)) else {
+                self.warnings.push(format!(
+                    "service `{name}`: `{}` is not supported, ignoring it",
+                    key.as_str().unwrap_or_default()
+                ));
+                continue;
+            };
+
+            // The long form maps each dependency to its startup condition
+            let value = match (key, value) {
+                ("depends_on", Value::Mapping(dependencies)) => {
+                    self.warnings.push(format!(
+                        "service `{name}`: `depends_on` conditions are not supported, only the \
+                         startup order is kept"
+                    ));
+                    Value::Sequence(dependencies.keys().cloned().collect())
+                }
+                _ => value.clone(),
+            };
+            supported.insert(Value::from(key), value);
+        }
+
+        let service: Service = serde_yaml::from_value(Value::Mapping(supported))
+            .map_err(|e| DockerError::ValidationError(format!("Invalid service `{name}`: {e}")))?;
+        if service.image.is_none() {
+            return Err(DockerError::ValidationError(format!(
+                "service `{name}` has no `image`"
+            )));
+        }
+        if let Some(restart) = &service.restart {
+            parse_restart_policy(restart).map_err(|e| {
+                DockerError::ValidationError(format!("Invalid service `{name}`: {e}"))
+            })?;
+        }
+        // Driver settings belong in the top-level `volumes`, there's nothing here to mount
+        for (i, volume) in service.volumes.iter().flatten().enumerate() {
+            if matches!(volume, Volume::Config { .. }) {
+                self.warnings.push(format!(
+                    "service `{name}`: `volumes` entry {i} is a volume definition rather than a \
+                     mount, ignoring it"
+                ));
+            }
+        }
+
+        Ok(service)
+    }
+
+    /// Any parts of the file that were ignored
+    #[must_use]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// The names of the services, in the order they appear in the file
+    pub fn service_names(&self) -> impl Iterator<Item = &str> {
+        self.services.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Create a [`Container`] builder for each service, keyed by service name
+    ///
+    /// Relative bind mount sources are resolved against the current directory.
+    ///
+    /// NOTE: The containers aren't named, use [`Container::with_name`] to name them.
+    ///
+    /// # Errors
+    ///
+    /// * A port can't be parsed, see [`Container::port`]
+    /// * The current directory can't be determined
+    pub fn containers(
+        &self,
+        client: Arc<Docker>,
+    ) -> Result<HashMap<String, Container>, DockerError> {
+        let base_dir = std::env::current_dir()?;
+
+        self.services
+            .iter()
+            .map(move |(name, service)| {
+                let container =
+                    service_container(client.clone(), service, &base_dir).map_err(|e| {
+                        DockerError::ValidationError(format!("Invalid service `{name}`: {e}"))
+                    })?;
+                Ok((name.clone(), container))
+            })
+            .collect()
+    }
+
+    /// Create a [`Stack`] of the services, honoring `depends_on`
+    ///
+    /// # Errors
+    ///
+    /// See [`Compose::containers`]
+    pub fn stack(&self, client: Arc<Docker>) -> Result<Stack, DockerError> {
+        let mut containers = self.containers(client)?;
+
+        let mut stack = Stack::new();
+        for (name, service) in &self.services {
+            if let Some(container) = containers.remove(name) {
+                stack = stack.container(name.clone(), container);
+            }
+            for dependency in service.depends_on.iter().flatten() {
+                stack = stack.depends_on(name.clone(), dependency.clone());
+            }
+        }
+
+        Ok(stack)
+    }
+}
+
+fn service_container(
+    client: Arc<Docker>,
+    service: &Service,
+    base_dir: &std::path::Path,
+) -> Result<Container, crate::container::Error> {
+    let mut container = Container::new(client, service.image.clone().unwrap_or_default());
+
+    if let Some(command) = &service.command {
+        container = container.cmd(command);
+    }
+    if let Some(environment) = &service.environment {
+        container = container.env(environment.into_iter().map(|(k, v)| format!("{k}={v}")));
+    }
+    for port in service.ports.iter().flatten() {
+        container = container.port(port)?;
+    }
+    if let Some(volumes) = &service.volumes {
+        container = container.binds(volumes.iter().filter_map(|volume| match volume {
+            Volume::Named(volume) => Some(volume.clone()),
+            Volume::Bind {
+                source,
+                target,
+                read_only,
+            } => {
+                let source = normalize_path(base_dir, source);
+                let mode = if *read_only { ":ro" } else { "" };
+                Some(format!("{}:{target}{mode}", source.display()))
+            }
+            Volume::Config { .. } => None,
+        }));
+    }
+    if let Some(restart) = &service.restart {
+        container = container.restart_policy(parse_restart_policy(restart)?);
+    }
+
+    Ok(container)
+}
+
+/// Parse a Compose `restart` value, e.g. `unless-stopped` or `on-failure:3`
+fn parse_restart_policy(restart: &str) -> Result<RestartPolicy, crate::container::Error> {
+    let invalid =
+        || crate::container::Error::InvalidConfig(format!("invalid restart policy `{restart}`"));
+
+    let (name, maximum_retry_count) = match restart.split_once(':') {
+        Some(("on-failure", retries)) => (
+            RestartPolicyNameEnum::ON_FAILURE,
+            Some(retries.parse().map_err(|_| invalid())?),
+        ),
+        Some(_) => return Err(invalid()),
+        None => match restart {
+            "no" => (RestartPolicyNameEnum::NO, None),
+            "always" => (RestartPolicyNameEnum::ALWAYS, None),
+            "unless-stopped" => (RestartPolicyNameEnum::UNLESS_STOPPED, None),
+            "on-failure" => (RestartPolicyNameEnum::ON_FAILURE, None),
+            _ => return Err(invalid()),
+        },
+    };
+
+    Ok(RestartPolicy {
+        name: Some(name),
+        maximum_retry_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::API_DEFAULT_VERSION;
+
+    const COMPOSE: &str = r#"
+version: "3.8"
+services:
+  web:
+    image: nginx:latest
+    command: ["nginx", "-g", "daemon off;"]
+    ports:
+      - "8080:80"
+    volumes:
+      - /srv/html:/usr/share/nginx/html:ro
+    restart: unless-stopped
+    depends_on:
+      db:
+        condition: service_healthy
+    healthcheck:
+      test: ["CMD", "curl", "-f", "http://localhost"]
+  db:
+    image: postgres:16
+    environment:
+      POSTGRES_PASSWORD: secret
+    volumes:
+      - pgdata:/var/lib/postgresql/data
+    restart: on-failure:3
+networks:
+  default: {}
+"#;
+
+    fn client() -> Arc<Docker> {
+        Arc::new(
+            Docker::connect_with_http("http://127.0.0.1:2375", 4, API_DEFAULT_VERSION).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let compose = Compose::from_yaml(COMPOSE).unwrap();
+        assert_eq!(compose.service_names().collect::<Vec<_>>(), ["web", "db"]);
+        assert_eq!(compose.warnings().len(), 3, "{:?}", compose.warnings());
+        assert!(compose.warnings()[0].contains("`networks`"));
+
+        let containers = compose.containers(client()).unwrap();
+
+        let web = containers["web"].container_config();
+        assert_eq!(web.image.as_deref(), Some("nginx:latest"));
+        assert_eq!(
+            web.cmd.unwrap(),
+            ["nginx", "-g", "daemon off;"].map(String::from)
+        );
+        let host_config = web.host_config.unwrap();
+        let bindings = host_config.port_bindings.unwrap();
+        assert_eq!(
+            bindings["80/tcp"].as_ref().unwrap()[0].host_port.as_deref(),
+            Some("8080")
+        );
+        assert_eq!(
+            host_config.binds.unwrap(),
+            ["/srv/html:/usr/share/nginx/html:ro"]
+        );
+        assert_eq!(
+            host_config.restart_policy.unwrap().name,
+            Some(RestartPolicyNameEnum::UNLESS_STOPPED)
+        );
+
+        let db = containers["db"].container_config();
+        assert_eq!(db.env.unwrap(), ["POSTGRES_PASSWORD=secret"]);
+        let host_config = db.host_config.unwrap();
+        assert_eq!(
+            host_config.binds.unwrap(),
+            ["pgdata:/var/lib/postgresql/data"]
+        );
+        assert_eq!(
+            host_config.restart_policy,
+            Some(RestartPolicy {
+                name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                maximum_retry_count: Some(3),
+            })
+        );
+
+        let stack = compose.stack(client()).unwrap();
+        assert_eq!(stack.startup_order().unwrap(), ["db", "web"]);
+    }
+
+    #[test]
+    fn test_volume_definition_warning() {
+        let compose = Compose::from_yaml(
+            "services:\n  web:\n    image: nginx\n    volumes:\n      - data:/data\n      - driver: local\n",
+        )
+        .unwrap();
+        assert_eq!(compose.warnings().len(), 1, "{:?}", compose.warnings());
+        assert!(compose.warnings()[0].contains("entry 1"));
+
+        let containers = compose.containers(client()).unwrap();
+        let host_config = containers["web"].container_config().host_config.unwrap();
+        assert_eq!(host_config.binds.unwrap(), ["data:/data"]);
+    }
+
+    #[test]
+    fn test_from_yaml_invalid() {
+        let err = Compose::from_yaml("services:\n  web:\n    command: echo\n").unwrap_err();
+        assert!(err.to_string().contains("no `image`"), "{err}");
+
+        let err =
+            Compose::from_yaml("services:\n  web:\n    image: nginx\n    restart: sometimes\n")
+                .unwrap_err();
+        assert!(err.to_string().contains("restart policy"), "{err}");
+    }
+}
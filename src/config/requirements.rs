@@ -1,6 +1,11 @@
 use crate::error::DockerError;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "deploy")]
 use std::path::Path;
+#[cfg(feature = "deploy")]
+use std::time::Duration;
 
 #[cfg(feature = "deploy")]
 use bollard::service::HostConfig;
@@ -9,15 +14,28 @@ use sysinfo::Disks;
 #[cfg(feature = "deploy")]
 use sysinfo::System;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SystemRequirements {
     pub min_memory_gb: u64,
+    /// Memory that must be free (not just installed) when checked, see [`SystemRequirements::check`]
+    #[serde(default)]
+    pub min_free_memory_gb: Option<u64>,
     pub min_disk_gb: u64,
     pub min_bandwidth_mbps: u64,
-    pub required_ports: Vec<u16>,
+    /// A URL to download to measure bandwidth against `min_bandwidth_mbps`
+    ///
+    /// Bandwidth is only checked if this is set, so offline environments aren't affected. The
+    /// payload should be large enough (a few MB) for the transfer time to be meaningful.
+    #[serde(default)]
+    pub bandwidth_probe_url: Option<String>,
+    pub required_ports: Vec<PortRequirement>,
+    /// The address to check `required_ports` against, defaults to all interfaces (IPv4 and IPv6)
+    #[serde(default)]
+    pub port_bind_address: Option<IpAddr>,
     pub data_directory: String,
     // Resource limit fields
-    pub cpu_limit: Option<f64>,             // Number of CPUs
+    #[serde(default, deserialize_with = "deserialize_cpu_limit")]
+    pub cpu_limit: Option<f64>, // Number of CPUs, e.g. 1.5 or "500m"
     pub memory_limit: Option<String>,       // e.g., "1G", "512M"
     pub memory_swap: Option<String>,        // Total memory including swap
     pub memory_reservation: Option<String>, // Soft limit
@@ -29,62 +47,156 @@ pub struct SystemRequirements {
 impl SystemRequirements {
     /// Check if this host meets the system requirements
     ///
+    /// Every requirement is checked, so all of the problems can be fixed at once.
+    ///
     /// # Errors
     ///
-    /// Will return an error indicating the resources that are missing
+    /// Will return [`DockerError::RequirementsNotMet`], listing every requirement that isn't met
+    /// as its own error (e.g. [`DockerError::PortInUse`] or [`DockerError::InsufficientDisk`]).
+    /// A bandwidth probe that fails or times out is listed as a [`DockerError::ValidationError`].
+    /// An invalid [`SystemRequirements::memory_limit`], or a
+    /// [`SystemRequirements::data_directory`] that doesn't exist, is returned immediately as a
+    /// [`DockerError::ValidationError`].
     pub fn check(&self) -> Result<(), DockerError> {
         let mut sys = System::new_all();
         sys.refresh_all();
-
-        // Check memory
-        let total_memory = sys.total_memory() / 1024 / 1024 / 1024; // Convert to GB
-        if total_memory < self.min_memory_gb {
-            return Err(DockerError::ValidationError(format!(
-                "Insufficient memory: {} GB available, {} GB required",
-                total_memory, self.min_memory_gb
-            )));
-        }
-
-        // Check memory limits if specified
-        if let Some(limit) = &self.memory_limit {
-            let limit_bytes = parse_memory_string(limit)?;
-            let total_bytes = total_memory * 1024 * 1024 * 1024;
-            if limit_bytes > total_bytes {
-                return Err(DockerError::ValidationError(format!(
-                    "Memory limit {} exceeds available memory {}GB",
-                    limit, total_memory
-                )));
-            }
-        }
+        let mut violations = self.memory_violations(sys.total_memory(), sys.available_memory())?;
 
         // Check disk space
-        let data_path = Path::new(&self.data_directory);
+        let data_path = Path::new(&self.data_directory)
+            .canonicalize()
+            .map_err(|e| {
+                DockerError::ValidationError(format!(
+                    "Data directory {} is not accessible: {}",
+                    self.data_directory, e
+                ))
+            })?;
 
         let disks = Disks::new_with_refreshed_list();
-        if let Some(disk) = disks
-            .iter()
-            .find(|disk| data_path.starts_with(disk.mount_point().to_string_lossy().as_ref()))
+        let mount_point =
+            find_mount_point(&data_path, disks.iter().map(sysinfo::Disk::mount_point));
+        if let Some(disk) =
+            mount_point.and_then(|mount| disks.iter().find(|disk| disk.mount_point() == mount))
         {
             let available_gb = disk.available_space() / 1024 / 1024 / 1024;
             if available_gb < self.min_disk_gb {
-                return Err(DockerError::ValidationError(format!(
-                    "Insufficient disk space: {} GB available, {} GB required",
-                    available_gb, self.min_disk_gb
-                )));
+                violations.push(DockerError::InsufficientDisk {
+                    available_gb,
+                    required_gb: self.min_disk_gb,
+                });
             }
         }
 
         // Check if ports are available
         for port in &self.required_ports {
-            if !is_port_available(*port) {
-                return Err(DockerError::ValidationError(format!(
-                    "Port {} is already in use",
-                    port
+            if !is_port_available(self.port_bind_address, *port) {
+                violations.push(DockerError::PortInUse {
+                    port: port.port,
+                    protocol: port.protocol,
+                });
+            }
+        }
+
+        // Check bandwidth, if a probe is configured
+        if let Some(url) = self.bandwidth_probe_url.clone() {
+            if self.min_bandwidth_mbps > 0 {
+                // On its own thread, so this works whether or not it's called from a runtime
+                let measured = std::thread::spawn(move || {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .map_err(|e| {
+                            DockerError::ValidationError(format!("Bandwidth probe failed: {}", e))
+                        })?
+                        .block_on(measure_bandwidth(&url, BANDWIDTH_PROBE_TIMEOUT))
+                })
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+
+                match measured {
+                    #[allow(clippy::cast_precision_loss)]
+                    Ok(mbps) if mbps < self.min_bandwidth_mbps as f64 => {
+                        violations.push(DockerError::InsufficientBandwidth {
+                            measured_mbps: mbps,
+                            required_mbps: self.min_bandwidth_mbps,
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => violations.push(e),
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(DockerError::RequirementsNotMet(violations))
+        }
+    }
+
+    /// Check if this host meets the system requirements, without blocking the async runtime
+    ///
+    /// [`SystemRequirements::check`] probes memory, disks, and ports synchronously, which can
+    /// stall other tasks. This runs it on tokio's blocking thread pool instead.
+    ///
+    /// # Errors
+    ///
+    /// See [`SystemRequirements::check`]
+    pub async fn check_async(&self) -> Result<(), DockerError> {
+        let requirements = self.clone();
+        match tokio::task::spawn_blocking(move || requirements.check()).await {
+            Ok(res) => res,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => Err(DockerError::ValidationError(format!(
+                "Requirements check did not complete: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Check the memory requirements against the host's total and available memory, in bytes
+    ///
+    /// `min_memory_gb` sizes the host, while `min_free_memory_gb` catches a host that is large
+    /// enough, but already too busy to run the container.
+    fn memory_violations(
+        &self,
+        total_bytes: u64,
+        available_bytes: u64,
+    ) -> Result<Vec<DockerError>, DockerError> {
+        let mut violations = Vec::new();
+
+        // Check memory
+        let total_memory = total_bytes / 1024 / 1024 / 1024; // Convert to GB
+        if total_memory < self.min_memory_gb {
+            violations.push(DockerError::InsufficientMemory {
+                available_gb: total_memory,
+                required_gb: self.min_memory_gb,
+            });
+        }
+
+        if let Some(min_free) = self.min_free_memory_gb {
+            let free_memory = available_bytes / 1024 / 1024 / 1024;
+            if free_memory < min_free {
+                violations.push(DockerError::InsufficientFreeMemory {
+                    free_gb: free_memory,
+                    required_gb: min_free,
+                });
+            }
+        }
+
+        // Check memory limits if specified
+        if let Some(limit) = &self.memory_limit {
+            let limit_bytes = parse_memory_string(limit)?;
+            let total_bytes = total_memory * 1024 * 1024 * 1024;
+            if limit_bytes > total_bytes {
+                violations.push(DockerError::InvalidResourceLimit(format!(
+                    "Memory limit {} exceeds available memory {}GB",
+                    limit, total_memory
                 )));
             }
         }
 
-        Ok(())
+        Ok(violations)
     }
 
     #[must_use]
@@ -113,37 +225,300 @@ impl SystemRequirements {
     }
 }
 
-fn is_port_available(port: u16) -> bool {
-    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+/// How long the bandwidth probe may take in total, so a stalled server can't hang the check
+#[cfg(feature = "deploy")]
+const BANDWIDTH_PROBE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the bandwidth probe may take to connect
+#[cfg(feature = "deploy")]
+const BANDWIDTH_PROBE_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Download `url` within `timeout`, returning the measured throughput in megabits per second
+#[cfg(feature = "deploy")]
+async fn measure_bandwidth(url: &str, timeout: Duration) -> Result<f64, DockerError> {
+    let probe_error =
+        |e: reqwest::Error| DockerError::ValidationError(format!("Bandwidth probe failed: {}", e));
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(BANDWIDTH_PROBE_CONNECT_TIMEOUT.min(timeout))
+        .timeout(timeout)
+        .build()
+        .map_err(probe_error)?;
+
+    let start = std::time::Instant::now();
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(probe_error)?;
+
+    let mut bytes = 0_u64;
+    while let Some(chunk) = response.chunk().await.map_err(probe_error)? {
+        bytes += chunk.len() as u64;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    #[allow(clippy::cast_precision_loss)]
+    let megabits = (bytes * 8) as f64 / 1_000_000.0;
+    Ok(megabits / elapsed.max(f64::EPSILON))
+}
+
+/// Find the mount point containing `path`, preferring the most specific (e.g. `/data` over `/`)
+#[cfg(feature = "deploy")]
+fn find_mount_point<'a>(path: &Path, mounts: impl Iterator<Item = &'a Path>) -> Option<&'a Path> {
+    mounts
+        .filter(|mount| path.starts_with(mount))
+        .max_by_key(|mount| mount.components().count())
+}
+
+/// The transport protocol of a [`PortRequirement`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+impl std::fmt::Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Protocol::Tcp => write!(f, "tcp"),
+            Protocol::Udp => write!(f, "udp"),
+        }
+    }
+}
+
+/// A port that must be free on the host, see [`SystemRequirements::required_ports`]
+///
+/// This deserializes from either a bare port number (TCP), or a `{ port, protocol }` map:
+///
+/// ```yaml
+/// required_ports:
+///   - 8080
+///   - port: 53
+///     protocol: udp
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PortRequirement {
+    pub port: u16,
+    pub protocol: Protocol,
+}
+
+impl PortRequirement {
+    /// A TCP port
+    #[must_use]
+    pub fn tcp(port: u16) -> Self {
+        Self {
+            port,
+            protocol: Protocol::Tcp,
+        }
+    }
+
+    /// A UDP port
+    #[must_use]
+    pub fn udp(port: u16) -> Self {
+        Self {
+            port,
+            protocol: Protocol::Udp,
+        }
+    }
+}
+
+impl From<u16> for PortRequirement {
+    fn from(port: u16) -> Self {
+        Self::tcp(port)
+    }
 }
 
-/// Helper function to parse memory strings like "1G", "512M" into bytes
+impl std::fmt::Display for PortRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.port, self.protocol)
+    }
+}
+
+impl<'de> Deserialize<'de> for PortRequirement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum PortInput {
+            Port(u16),
+            Full {
+                port: u16,
+                #[serde(default)]
+                protocol: Protocol,
+            },
+        }
+
+        match PortInput::deserialize(deserializer)? {
+            PortInput::Port(port) => Ok(Self::tcp(port)),
+            PortInput::Full { port, protocol } => Ok(Self { port, protocol }),
+        }
+    }
+}
+
+/// Check that `port` can be bound on `address`, or on all interfaces (like Docker publishes ports)
+fn is_port_available(address: Option<IpAddr>, port: PortRequirement) -> bool {
+    let bind = |address: IpAddr| match port.protocol {
+        Protocol::Tcp => std::net::TcpListener::bind((address, port.port)).map(drop),
+        Protocol::Udp => std::net::UdpSocket::bind((address, port.port)).map(drop),
+    };
+
+    if let Some(address) = address {
+        return bind(address).is_ok();
+    }
+
+    // The host may not support IPv6, so only a port conflict counts
+    [
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    ]
+    .into_iter()
+    .all(|address| match bind(address) {
+        Ok(()) => true,
+        Err(e) => e.kind() != std::io::ErrorKind::AddrInUse,
+    })
+}
+
+/// Helper function to parse memory strings like "1G", "512MB", "2GiB" or "1.5G" into bytes
+///
+/// Units are case-insensitive, and come in three families:
+///
+/// * `K`, `M`, `G`, `T` are binary (powers of 1024), as in Docker's `--memory`
+/// * `KB`, `MB`, `GB`, `TB` are decimal (powers of 1000)
+/// * `KiB`, `MiB`, `GiB`, `TiB` (or `Ki`, `Mi`, ...) are binary
+///
+/// A bare number, or one with a `B` suffix, is treated as bytes. Fractional values are rounded to
+/// the nearest byte.
 ///
 /// # Errors
 ///
 /// The input is not a valid memory string
 pub fn parse_memory_string(memory: &str) -> Result<u64, DockerError> {
-    let len = memory.len();
-    let (num, unit) = memory.split_at(len - 1);
-    let base = num.parse::<u64>().map_err(|_| {
-        DockerError::InvalidResourceLimit(format!("Invalid memory value: {}", memory))
-    })?;
+    let unit_start = memory
+        .rfind(|c: char| !c.is_ascii_alphabetic())
+        .map_or(0, |i| i + 1);
+    let (num, unit) = memory.split_at(unit_start);
+    let invalid_value =
+        || DockerError::InvalidResourceLimit(format!("Invalid memory value: {}", memory));
+
+    let base = parse_plain_decimal(num).ok_or_else(invalid_value)?;
 
-    match unit.to_uppercase().as_str() {
-        "K" => Ok(base * 1024),
-        "M" => Ok(base * 1024 * 1024),
-        "G" => Ok(base * 1024 * 1024 * 1024),
-        _ => Err(DockerError::InvalidResourceLimit(format!(
-            "Invalid memory unit: {}",
-            unit
-        ))),
+    let multiplier: u64 = match unit.to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "ki" | "kib" => 1 << 10,
+        "m" | "mi" | "mib" => 1 << 20,
+        "g" | "gi" | "gib" => 1 << 30,
+        "t" | "ti" | "tib" => 1 << 40,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        _ => {
+            return Err(DockerError::InvalidResourceLimit(format!(
+                "Invalid memory unit: {}",
+                unit
+            )));
+        }
+    };
+
+    // Whole numbers are handled exactly, as large values can't be represented as an `f64`
+    if let Ok(base) = num.parse::<u64>() {
+        return base.checked_mul(multiplier).ok_or_else(invalid_value);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let bytes = (base * multiplier as f64).round();
+    #[allow(clippy::cast_precision_loss)]
+    if !bytes.is_finite() || bytes >= u64::MAX as f64 {
+        return Err(invalid_value());
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(bytes as u64)
+}
+
+/// Helper function to parse CPU strings like "2", "1.5" or "500m" (millicpus) into a CPU count
+///
+/// # Errors
+///
+/// The input is not a valid CPU string
+pub fn parse_cpu_string(cpus: &str) -> Result<f64, DockerError> {
+    let invalid_value =
+        || DockerError::InvalidResourceLimit(format!("Invalid CPU value: {}", cpus));
+
+    let (num, divisor) = match cpus.strip_suffix('m') {
+        Some(millis) => (millis, 1000.0),
+        None => (cpus, 1.0),
+    };
+
+    parse_plain_decimal(num)
+        .map(|value| value / divisor)
+        .ok_or_else(invalid_value)
+}
+
+/// Parse a plain decimal like "2", "1.5" or ".5"
+///
+/// `f64`'s parser would also accept forms like "1e3", "inf" and "-1", which aren't valid here.
+fn parse_plain_decimal(num: &str) -> Option<f64> {
+    let is_decimal = num.chars().any(|c| c.is_ascii_digit())
+        && num.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && num.matches('.').count() <= 1;
+    if !is_decimal {
+        return None;
+    }
+
+    num.parse().ok()
+}
+
+/// Accept a CPU limit as either a number (`1.5`) or a string (`"1.5"`, `"500m"`)
+fn deserialize_cpu_limit<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CpuInput {
+        Number(f64),
+        String(String),
+    }
+
+    match Option::<CpuInput>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(CpuInput::Number(cpus)) => Ok(Some(cpus)),
+        Some(CpuInput::String(cpus)) => parse_cpu_string(&cpus)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::parse_memory_string;
+    use super::{
+        PortRequirement, Protocol, SystemRequirements, is_port_available, parse_cpu_string,
+        parse_memory_string,
+    };
+    #[cfg(feature = "deploy")]
+    use super::{find_mount_point, measure_bandwidth};
     use crate::error::DockerError;
+    use core::net::{IpAddr, Ipv4Addr};
+    #[cfg(feature = "deploy")]
+    use std::path::Path;
+    #[cfg(feature = "deploy")]
+    use std::time::Duration;
+
+    /// Requirements any host meets, for tests to override
+    #[cfg(feature = "deploy")]
+    fn requirements() -> SystemRequirements {
+        SystemRequirements {
+            data_directory: String::from("/"),
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn test_memory_string_parsing() {
@@ -152,12 +527,84 @@ mod tests {
         assert!(parse_memory_string("invalid").is_err());
     }
 
+    #[test]
+    fn test_docker_memory_string_forms() {
+        assert_eq!(parse_memory_string("512m").unwrap(), 512 * 1024 * 1024);
+        assert_eq!(parse_memory_string("1g").unwrap(), 1024 * 1024 * 1024);
+        assert_eq!(parse_memory_string("1gb").unwrap(), 1_000_000_000);
+        assert_eq!(parse_memory_string("64KB").unwrap(), 64_000);
+        assert_eq!(parse_memory_string("0b").unwrap(), 0);
+        assert_eq!(parse_memory_string("2048").unwrap(), 2048);
+        assert!(parse_memory_string("").is_err());
+        assert!(parse_memory_string("1X").is_err());
+        assert!(parse_memory_string("1gbb").is_err());
+    }
+
+    #[test]
+    fn test_memory_unit_families() {
+        // Single letters are binary
+        assert_eq!(parse_memory_string("2K").unwrap(), 2 << 10);
+        assert_eq!(parse_memory_string("2M").unwrap(), 2 << 20);
+        assert_eq!(parse_memory_string("2G").unwrap(), 2 << 30);
+        assert_eq!(parse_memory_string("2T").unwrap(), 2 << 40);
+
+        // `xB` is decimal
+        assert_eq!(parse_memory_string("512KB").unwrap(), 512_000);
+        assert_eq!(parse_memory_string("512MB").unwrap(), 512_000_000);
+        assert_eq!(parse_memory_string("2GB").unwrap(), 2_000_000_000);
+        assert_eq!(parse_memory_string("2TB").unwrap(), 2_000_000_000_000);
+
+        // `xiB` is binary
+        assert_eq!(parse_memory_string("512KiB").unwrap(), 512 << 10);
+        assert_eq!(parse_memory_string("512MiB").unwrap(), 512 << 20);
+        assert_eq!(parse_memory_string("2GiB").unwrap(), 2 << 30);
+        assert_eq!(parse_memory_string("2TiB").unwrap(), 2 << 40);
+        assert_eq!(parse_memory_string("2Gi").unwrap(), 2 << 30);
+
+        // Case doesn't blur the distinction
+        assert_eq!(parse_memory_string("2gib").unwrap(), 2 << 30);
+        assert_eq!(parse_memory_string("2gb").unwrap(), 2_000_000_000);
+        assert_eq!(parse_memory_string("10b").unwrap(), 10);
+
+        // Ambiguous or mangled suffixes
+        for input in ["1KiBB", "1iB", "1BK", "1GBi", "1KM", "1PB"] {
+            assert!(
+                matches!(
+                    parse_memory_string(input),
+                    Err(DockerError::InvalidResourceLimit(msg)) if msg.contains("unit")
+                ),
+                "{input} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fractional_memory_strings() {
+        assert_eq!(parse_memory_string("1.5G").unwrap(), 1_610_612_736);
+        assert_eq!(parse_memory_string("12.5G").unwrap(), 13_421_772_800);
+        assert_eq!(parse_memory_string("0.5M").unwrap(), 512 * 1024);
+        assert_eq!(parse_memory_string("2.5K").unwrap(), 2560);
+        assert_eq!(parse_memory_string("2.5kib").unwrap(), 2560);
+        assert_eq!(parse_memory_string("2.5kb").unwrap(), 2500);
+        assert_eq!(parse_memory_string(".5m").unwrap(), 512 * 1024);
+        // Rounded to the nearest byte
+        assert_eq!(parse_memory_string("0.001K").unwrap(), 1);
+        assert_eq!(parse_memory_string("1.4B").unwrap(), 1);
+        assert_eq!(parse_memory_string("1.5").unwrap(), 2);
+
+        assert!(parse_memory_string(".").is_err());
+        assert!(parse_memory_string("1.2.3G").is_err());
+        assert!(parse_memory_string("-1G").is_err());
+        assert!(parse_memory_string("1e3").is_err());
+        assert!(parse_memory_string("1.5X").is_err());
+    }
+
     #[test]
     fn test_invalid_resource_limits() {
         let memory_tests = vec![
             ("1X", "Invalid memory unit: X"),
             ("abc", "Invalid memory value: abc"),
-            ("12.5G", "Invalid memory value: 12.5G"),
+            ("1.5.5G", "Invalid memory value: 1.5.5G"),
         ];
 
         for (input, expected_error) in memory_tests {
@@ -168,4 +615,420 @@ mod tests {
             ));
         }
     }
+
+    #[test]
+    fn test_cpu_string_parsing() {
+        assert!((parse_cpu_string("2").unwrap() - 2.0).abs() < f64::EPSILON);
+        assert!((parse_cpu_string("1.5").unwrap() - 1.5).abs() < f64::EPSILON);
+        assert!((parse_cpu_string("500m").unwrap() - 0.5).abs() < f64::EPSILON);
+        assert!((parse_cpu_string("2500m").unwrap() - 2.5).abs() < f64::EPSILON);
+
+        for input in ["bad", "", "m", "-1", "1e3", "1.5.5", "500M", "2 "] {
+            assert!(
+                matches!(
+                    parse_cpu_string(input),
+                    Err(DockerError::InvalidResourceLimit(msg)) if msg.contains("Invalid CPU value")
+                ),
+                "{input} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cpu_limit_deserialization() {
+        let base = "min_memory_gb: 1\nmin_disk_gb: 1\nmin_bandwidth_mbps: 1\nrequired_ports: []\ndata_directory: /data\n";
+        let parse = |extra: &str| {
+            serde_yaml::from_str::<SystemRequirements>(&format!("{base}{extra}"))
+                .map(|requirements| requirements.cpu_limit)
+        };
+
+        assert_eq!(parse("").unwrap(), None);
+        assert_eq!(parse("cpu_limit: null").unwrap(), None);
+        assert_eq!(parse("cpu_limit: 2").unwrap(), Some(2.0));
+        assert_eq!(parse("cpu_limit: 1.5").unwrap(), Some(1.5));
+        assert_eq!(parse("cpu_limit: '1.5'").unwrap(), Some(1.5));
+        assert_eq!(parse("cpu_limit: 500m").unwrap(), Some(0.5));
+        assert!(parse("cpu_limit: bad").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_check_reports_all_failures() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let requirements = SystemRequirements {
+            min_memory_gb: u64::MAX,
+            required_ports: vec![PortRequirement::tcp(port)],
+            ..requirements()
+        };
+
+        let Err(DockerError::RequirementsNotMet(violations)) = requirements.check() else {
+            panic!("expected unmet requirements");
+        };
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert!(
+            matches!(
+                violations[0],
+                DockerError::InsufficientMemory {
+                    required_gb: u64::MAX,
+                    ..
+                }
+            ),
+            "{violations:?}"
+        );
+        assert!(
+            matches!(
+                violations[1],
+                DockerError::PortInUse { port: p, protocol: Protocol::Tcp } if p == port
+            ),
+            "{violations:?}"
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            format!("Port {port}/tcp is already in use")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_port_check_all_interfaces() {
+        // Bound on all interfaces, not just loopback
+        let listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(!is_port_available(None, PortRequirement::tcp(port)));
+        assert!(!is_port_available(
+            Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            PortRequirement::tcp(port)
+        ));
+
+        let requirements = SystemRequirements {
+            required_ports: vec![PortRequirement::tcp(port)],
+            ..requirements()
+        };
+        let Err(DockerError::RequirementsNotMet(violations)) = requirements.check() else {
+            panic!("expected port {port} to be reported as in use");
+        };
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [DockerError::PortInUse { port: p, protocol: Protocol::Tcp }] if *p == port
+            ),
+            "{violations:?}"
+        );
+
+        drop(listener);
+        assert!(is_port_available(None, PortRequirement::tcp(port)));
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_free_memory_check() {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        let mut requirements = SystemRequirements {
+            min_memory_gb: 8,
+            min_free_memory_gb: Some(4),
+            ..requirements()
+        };
+
+        // Plenty installed, but mostly in use
+        let violations = requirements.memory_violations(16 * GB, GB).unwrap();
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [DockerError::InsufficientFreeMemory {
+                    free_gb: 1,
+                    required_gb: 4
+                }]
+            ),
+            "{violations:?}"
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "Insufficient free memory: 1 GB free, 4 GB required"
+        );
+        assert!(
+            requirements
+                .memory_violations(16 * GB, 4 * GB)
+                .unwrap()
+                .is_empty()
+        );
+
+        // The total check still applies on its own
+        requirements.min_free_memory_gb = None;
+        assert!(
+            requirements
+                .memory_violations(16 * GB, 0)
+                .unwrap()
+                .is_empty()
+        );
+        let violations = requirements.memory_violations(4 * GB, 4 * GB).unwrap();
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [DockerError::InsufficientMemory {
+                    available_gb: 4,
+                    required_gb: 8
+                }]
+            ),
+            "{violations:?}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_port_check_protocols() {
+        // A held UDP port doesn't affect TCP, and vice versa
+        let udp = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+        let udp_port = udp.local_addr().unwrap().port();
+        assert!(!is_port_available(None, PortRequirement::udp(udp_port)));
+
+        let tcp = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let tcp_port = tcp.local_addr().unwrap().port();
+        assert!(!is_port_available(None, PortRequirement::tcp(tcp_port)));
+
+        let requirements = SystemRequirements {
+            required_ports: vec![
+                PortRequirement::udp(udp_port),
+                PortRequirement::tcp(tcp_port),
+            ],
+            ..requirements()
+        };
+        let Err(DockerError::RequirementsNotMet(violations)) = requirements.check() else {
+            panic!("expected both ports to be reported as in use");
+        };
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [
+                    DockerError::PortInUse { port: udp, protocol: Protocol::Udp },
+                    DockerError::PortInUse { port: tcp, protocol: Protocol::Tcp },
+                ] if *udp == udp_port && *tcp == tcp_port
+            ),
+            "{violations:?}"
+        );
+
+        drop(udp);
+        drop(tcp);
+        assert!(is_port_available(None, PortRequirement::udp(udp_port)));
+        assert!(is_port_available(None, PortRequirement::tcp(tcp_port)));
+    }
+
+    #[test]
+    fn test_port_requirement_deserialization() {
+        let ports: Vec<PortRequirement> =
+            serde_yaml::from_str("[8080, {port: 53, protocol: udp}, {port: 443}]").unwrap();
+        assert_eq!(
+            ports,
+            vec![
+                PortRequirement::tcp(8080),
+                PortRequirement {
+                    port: 53,
+                    protocol: Protocol::Udp,
+                },
+                PortRequirement::tcp(443),
+            ]
+        );
+
+        assert!(
+            serde_yaml::from_str::<Vec<PortRequirement>>("[{port: 53, protocol: sctp}]").is_err()
+        );
+        assert!(serde_yaml::from_str::<Vec<PortRequirement>>("[70000]").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_find_mount_point() {
+        let mounts = [
+            Path::new("/"),
+            Path::new("/data"),
+            Path::new("/data/fast"),
+            Path::new("/boot"),
+        ];
+        let find = |path: &str| find_mount_point(Path::new(path), mounts.iter().copied());
+
+        assert_eq!(find("/data/fast/db"), Some(Path::new("/data/fast")));
+        assert_eq!(find("/data/slow"), Some(Path::new("/data")));
+        // Components are compared, not strings
+        assert_eq!(find("/database"), Some(Path::new("/")));
+        // Only root matches
+        assert_eq!(find("/srv/app"), Some(Path::new("/")));
+
+        assert_eq!(
+            find_mount_point(Path::new("/srv/app"), [Path::new("/data")].into_iter()),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_missing_data_directory() {
+        let requirements = SystemRequirements {
+            data_directory: String::from("/does/not/exist/docktopus"),
+            ..requirements()
+        };
+        assert!(
+            matches!(
+                requirements.check(),
+                Err(DockerError::ValidationError(msg)) if msg.contains("/does/not/exist/docktopus")
+            ),
+            "a missing data directory should be rejected"
+        );
+
+        // A directory that only lives under the root mount is still checked
+        let requirements = SystemRequirements {
+            data_directory: std::env::temp_dir().to_string_lossy().into_owned(),
+            min_disk_gb: u64::MAX,
+            ..requirements
+        };
+        let Err(DockerError::RequirementsNotMet(violations)) = requirements.check() else {
+            panic!("expected insufficient disk space");
+        };
+        assert!(
+            matches!(
+                violations[0],
+                DockerError::InsufficientDisk {
+                    required_gb: u64::MAX,
+                    ..
+                }
+            ),
+            "{violations:?}"
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "deploy")]
+    async fn test_check_async_yields() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requirements = SystemRequirements {
+            required_ports: vec![PortRequirement::tcp(port)],
+            ..requirements()
+        };
+
+        // On this single-threaded runtime, the ticker only runs while the check is awaited
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticker = tokio::spawn({
+            let ticks = ticks.clone();
+            async move {
+                loop {
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+
+        let res = requirements.check_async().await;
+        assert!(
+            ticks.load(Ordering::SeqCst) > 0,
+            "check_async blocked the runtime"
+        );
+        ticker.abort();
+
+        // Same result as the sync check
+        let Err(DockerError::RequirementsNotMet(violations)) = res else {
+            panic!("expected port {port} to be reported as in use");
+        };
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [DockerError::PortInUse { port: p, protocol: Protocol::Tcp }] if *p == port
+            ),
+            "{violations:?}"
+        );
+    }
+
+    /// Serve a `size` byte payload over HTTP, returning its URL
+    #[cfg(feature = "deploy")]
+    fn serve_payload(size: usize) -> String {
+        use crate::fake_daemon::{FakeDaemon, Response};
+
+        let daemon = FakeDaemon::with_handler(move |_| Some(Response::ok(vec![0; size])));
+        format!("{}/payload", daemon.url())
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "deploy")]
+    async fn test_measure_bandwidth() {
+        let url = serve_payload(4 * 1024 * 1024);
+        let mbps = measure_bandwidth(&url, Duration::from_secs(30))
+            .await
+            .unwrap();
+        // Loopback is fast, but not infinitely so
+        assert!(mbps.is_finite() && mbps > 1.0, "{mbps}");
+
+        let err = measure_bandwidth("http://127.0.0.1:1/payload", Duration::from_secs(30))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, DockerError::ValidationError(msg) if msg.contains("Bandwidth probe failed")),
+            "{err:?}"
+        );
+
+        // A server that accepts the connection but never answers times out
+        let stalled = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/payload", stalled.local_addr().unwrap());
+        let err = tokio::time::timeout(
+            Duration::from_secs(10),
+            measure_bandwidth(&url, Duration::from_millis(200)),
+        )
+        .await
+        .expect("the probe should time out on its own")
+        .unwrap_err();
+        assert!(
+            matches!(&err, DockerError::ValidationError(msg) if msg.contains("Bandwidth probe failed")),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn test_bandwidth_requirement() {
+        let mut requirements = SystemRequirements {
+            min_bandwidth_mbps: u64::MAX,
+            ..requirements()
+        };
+
+        // Opt-in only
+        assert!(requirements.check().is_ok());
+
+        requirements.bandwidth_probe_url = Some(serve_payload(1024 * 1024));
+        let Err(DockerError::RequirementsNotMet(violations)) = requirements.check() else {
+            panic!("expected insufficient bandwidth");
+        };
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [DockerError::InsufficientBandwidth {
+                    required_mbps: u64::MAX,
+                    ..
+                }]
+            ),
+            "{violations:?}"
+        );
+
+        requirements.min_bandwidth_mbps = 1;
+        assert!(requirements.check().is_ok());
+
+        // A failed probe is reported alongside the other violations
+        requirements.bandwidth_probe_url = Some(String::from("http://127.0.0.1:1/payload"));
+        requirements.min_memory_gb = u64::MAX;
+        let Err(DockerError::RequirementsNotMet(violations)) = requirements.check() else {
+            panic!("expected the failed probe to be reported");
+        };
+        assert!(
+            matches!(
+                violations.as_slice(),
+                [
+                    DockerError::InsufficientMemory { .. },
+                    DockerError::ValidationError(msg),
+                ] if msg.contains("Bandwidth probe failed")
+            ),
+            "{violations:?}"
+        );
+    }
 }
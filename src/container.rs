@@ -1,31 +1,174 @@
 //! Utilities for spinning up and managing Docker containers
 
+#[cfg(test)]
+mod tests;
+
+use crate::DockerError;
+use crate::builder::PullProgress;
+use crate::config::{Protocol, SystemRequirements, parse_memory_string};
 use bollard::Docker;
 use bollard::container::{
-    Config, CreateContainerOptions, InspectContainerOptions, ListContainersOptions,
-    StartContainerOptions, StopContainerOptions, WaitContainerOptions,
+    AttachContainerOptions, AttachContainerResults, Config, CreateContainerOptions,
+    DownloadFromContainerOptions, InspectContainerOptions, KillContainerOptions,
+    ListContainersOptions, LogOutput, LogsOptions, NetworkingConfig, RemoveContainerOptions,
+    RenameContainerOptions, ResizeContainerTtyOptions, RestartContainerOptions,
+    StartContainerOptions, StopContainerOptions, UpdateContainerOptions, UploadToContainerOptions,
+    WaitContainerOptions,
 };
+use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
+use bollard::image::{CommitContainerOptions, CreateImageOptions, RemoveImageOptions};
 use bollard::models::{
-    ContainerConfig, ContainerCreateResponse, ContainerInspectResponse, HostConfig,
-    MountPointTypeEnum, PortMap, RestartPolicy,
+    ContainerConfig, ContainerCreateResponse, ContainerInspectResponse, DeviceRequest,
+    EndpointIpamConfig, EndpointSettings, EventMessage, HealthConfig, HealthStatusEnum, HostConfig,
+    HostConfigLogConfig, MountPointTypeEnum, PortBinding, PortMap, ResourcesBlkioWeightDevice,
+    ResourcesUlimits, RestartPolicy, RestartPolicyNameEnum,
 };
+use bollard::network::{ConnectNetworkOptions, InspectNetworkOptions};
+use bollard::system::EventsOptions;
+use bytes::{Bytes, BytesMut};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::pin::Pin;
 use core::str::FromStr;
-use futures_util::{Stream, StreamExt};
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use ipnet::IpNet;
+use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use tokio_util::io::StreamReader;
+
+/// The chunk size used to report progress on archive uploads, and to read streamed archives
+///
+/// Archives smaller than this are sent in one piece, so progress reporting adds no overhead.
+const UPLOAD_PROGRESS_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Docker's default grace period before a stopping container is killed
+const DEFAULT_STOP_TIMEOUT_SECS: isize = 10;
+
+/// The mount options for writable paths added by [`Container::read_only_with_writable`]
+pub const DEFAULT_TMPFS_OPTIONS: &str = "rw,nosuid,nodev,size=64m";
 
+/// How often [`Container::wait_until_healthy`] checks the container's health
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Errors from [`Container`] operations
+///
+/// New variants may be added in minor releases, use [`Error::is_not_found`] and
+/// [`Error::as_bollard`] to classify errors without matching every variant.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Attempted to connect to a non-existent container")]
     ContainerNotFound,
-    #[error("Found an invalid status for the container: `{0}`")]
-    BadContainerStatus(String),
+    #[error("Image `{0}` not found, try `docker pull {0}`")]
+    ImageNotFound(String),
+    #[error("Cannot transition container from `{from:?}` to `{to:?}`")]
+    InvalidStateTransition {
+        from: ContainerStatus,
+        to: ContainerStatus,
+    },
+    #[error("Cannot unpause container, it is `{0:?}` rather than paused")]
+    NotPaused(ContainerStatus),
+    #[error("The name `{name}` is already in use by container `{existing}`")]
+    Conflict { name: String, existing: String },
+    #[error("Multiple containers are named `{name}`: {}", ids.join(", "))]
+    AmbiguousName { name: String, ids: Vec<String> },
+    #[error("Invalid container configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid resource limit: {0}")]
+    InvalidResourceLimit(String),
+    #[error(
+        "Timed out after {timeout:?} waiting for the container to become healthy (last check: {})",
+        last_failure.as_ref().map_or("none", |result| result.output.trim())
+    )]
+    HealthTimeout {
+        timeout: Duration,
+        last_failure: Option<HealthCheckResult>,
+    },
+    #[error(
+        "Expected {expected} log lines matching `{pattern}` within {timeout:?}, found {}",
+        found.len()
+    )]
+    LogTimeout {
+        pattern: String,
+        expected: usize,
+        timeout: Duration,
+        found: Vec<String>,
+    },
+    #[error("Timed out after {0:?} waiting for the container to exit")]
+    WaitTimeout(Duration),
+    #[error("The container wasn't ready within {0:?}, or exited first")]
+    ReadyTimeout(Duration),
+    #[error(
+        "Container exited with code {exit_code}, last log lines:\n{}",
+        tail.join("\n")
+    )]
+    NonZeroExit { exit_code: i64, tail: Vec<String> },
+    #[error("Failed to resolve `{host}` to an {family:?} address: {reason}")]
+    HostResolution {
+        host: String,
+        family: IpFamily,
+        reason: String,
+    },
+    #[error("`{}` exited with code {exit_code}, output:\n{output}", cmd.join(" "))]
+    ExecFailed {
+        cmd: Vec<String>,
+        exit_code: i64,
+        output: String,
+    },
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
     #[error("{0}")]
     Bollard(#[from] bollard::errors::Error),
+    #[error(transparent)]
+    Docker(Box<DockerError>),
+}
+
+impl From<DockerError> for Error {
+    fn from(err: DockerError) -> Self {
+        match err {
+            DockerError::BollardError(e) => Error::Bollard(e),
+            DockerError::FileError(e) => Error::Io(e),
+            DockerError::InvalidResourceLimit(msg) => Error::InvalidResourceLimit(msg),
+            DockerError::Container(e) => *e,
+            e => Error::Docker(Box::new(e)),
+        }
+    }
+}
+
+impl Error {
+    /// Whether the container or image doesn't exist
+    ///
+    /// This covers [`Error::ContainerNotFound`] and [`Error::ImageNotFound`], as well as `404`
+    /// responses from the daemon (e.g. the container was removed out-of-band).
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            Error::ContainerNotFound
+                | Error::ImageNotFound(_)
+                | Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404,
+                    ..
+                })
+        )
+    }
+
+    /// The underlying Docker client error, if this is one
+    #[must_use]
+    pub fn as_bollard(&self) -> Option<&bollard::errors::Error> {
+        match self {
+            Error::Bollard(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 /// The status of a Docker container
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ContainerStatus {
     /// Created, but never started
     Created,
@@ -41,8 +184,13 @@ pub enum ContainerStatus {
     Removing,
     /// Defunct, partially removed
     Dead,
+    /// A state this crate doesn't recognize, holding the raw string reported by the daemon
+    Unknown(String),
 }
 
+/// Parse a container status as reported by the daemon
+///
+/// This never fails, states introduced by newer daemons are kept as [`ContainerStatus::Unknown`].
 impl FromStr for ContainerStatus {
     type Err = Error;
 
@@ -55,516 +203,3565 @@ impl FromStr for ContainerStatus {
             "exited" => Ok(ContainerStatus::Exited),
             "removing" => Ok(ContainerStatus::Removing),
             "dead" => Ok(ContainerStatus::Dead),
-            _ => Err(Error::BadContainerStatus(s.to_string())),
+            _ => Ok(ContainerStatus::Unknown(s.to_string())),
         }
     }
 }
 
 impl ContainerStatus {
+    fn as_str(&self) -> &str {
+        match self {
+            ContainerStatus::Created => "created",
+            ContainerStatus::Running => "running",
+            ContainerStatus::Paused => "paused",
+            ContainerStatus::Restarting => "restarting",
+            ContainerStatus::Exited => "exited",
+            ContainerStatus::Removing => "removing",
+            ContainerStatus::Dead => "dead",
+            ContainerStatus::Unknown(status) => status,
+        }
+    }
+
     #[must_use]
-    pub fn is_active(self) -> bool {
+    pub fn is_active(&self) -> bool {
         matches!(self, ContainerStatus::Running)
     }
 
+    /// Whether the container can still be used, [`ContainerStatus::Unknown`] is assumed not to be
     #[must_use]
-    pub fn is_usable(self) -> bool {
-        !matches!(self, ContainerStatus::Removing | ContainerStatus::Dead)
+    pub fn is_usable(&self) -> bool {
+        !matches!(
+            self,
+            ContainerStatus::Removing | ContainerStatus::Dead | ContainerStatus::Unknown(_)
+        )
     }
 }
 
-/// A [Docker](https://en.wikipedia.org/wiki/Docker_(software)) container
-#[derive(Debug)]
-pub struct Container {
-    id: Option<String>,
-    name: Option<String>,
-    image: String,
-    client: Arc<Docker>,
-    options: ContainerOptions,
+/// The health of a Docker container, as reported by its healthcheck
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HealthStatus {
+    /// The healthcheck hasn't passed yet, and the container is still within its start period
+    Starting,
+    /// The most recent healthcheck passed
+    Healthy,
+    /// The healthcheck has failed too many times in a row
+    Unhealthy,
+    /// The container has no healthcheck
+    None,
 }
 
-#[derive(Debug, Default, Clone)]
-struct ContainerOptions {
-    name: Option<String>,
-    env: Option<Vec<String>>,
-    cmd: Option<Vec<String>>,
-    binds: Option<Vec<String>>,
-    extra_hosts: Option<Vec<String>>,
-    runtime: Option<String>,
-    port_bindings: Option<PortMap>,
-    restart_policy: Option<RestartPolicy>,
-    config_override: Option<Config<String>>,
-}
+impl HealthStatus {
+    fn from_inspect(inspect: &ContainerInspectResponse) -> Self {
+        let status = inspect
+            .state
+            .as_ref()
+            .and_then(|state| state.health.as_ref())
+            .and_then(|health| health.status);
 
-impl Container {
-    /// Create a new `Container`
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust");
-    ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
-    pub fn new<T>(client: Arc<Docker>, image: T) -> Self
-    where
-        T: Into<String>,
-    {
-        Self {
-            id: None,
-            name: None,
-            image: image.into(),
-            client,
-            options: ContainerOptions::default(),
+        match status {
+            Some(HealthStatusEnum::STARTING) => HealthStatus::Starting,
+            Some(HealthStatusEnum::HEALTHY) => HealthStatus::Healthy,
+            Some(HealthStatusEnum::UNHEALTHY) => HealthStatus::Unhealthy,
+            Some(HealthStatusEnum::NONE | HealthStatusEnum::EMPTY) | None => HealthStatus::None,
         }
     }
+}
 
-    /// Attempt to fetch an existing container by its ID
-    ///
-    /// # Errors
-    ///
-    /// * Docker inspect fails
-    /// * The container isn't found
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust");
-    ///
-    /// // We can now start our container and grab its id
-    /// container.start(false).await?;
-    ///
-    /// let id = container.id().unwrap();
-    ///
-    /// let container2 = Container::from_id(connection.client(), id).await?;
-    ///
-    /// assert_eq!(container.id(), container2.id());
-    /// # Ok(()) }
-    /// ```
-    pub async fn from_id<T>(client: Arc<Docker>, id: T) -> Result<Self, Error>
-    where
-        T: AsRef<str>,
-    {
-        let ContainerInspectResponse {
-            id: Some(id),
-            name,
-            config:
-                Some(ContainerConfig {
-                    env,
-                    cmd,
-                    image: Some(image),
-                    ..
-                }),
-            mounts,
-            host_config,
-            ..
-        } = client
-            .inspect_container(id.as_ref(), None::<InspectContainerOptions>)
-            .await?
-        else {
-            return Err(Error::ContainerNotFound);
-        };
-
-        let binds = mounts.map(|mounts| {
-            mounts
-                .into_iter()
-                .filter_map(|mount| {
-                    if !matches!(mount.typ, Some(MountPointTypeEnum::BIND)) {
-                        return None;
-                    }
-                    let source = mount.source?;
-                    let dest = mount.destination?;
-                    let mut bind = format!("{}:{}", source, dest);
-                    if let Some(mode) = mount.mode {
-                        bind.push(':');
-                        bind.push_str(&mode);
-                    }
-                    Some(bind)
-                })
-                .collect::<Vec<_>>()
-        });
-
-        let mut extra_hosts = None;
-        let mut runtime = None;
-        let mut restart_policy = None;
-        let mut port_bindings = None;
-        if let Some(hc) = host_config {
-            extra_hosts = hc.extra_hosts;
-            runtime = hc.runtime;
-            restart_policy = hc.restart_policy;
-            port_bindings = hc.port_bindings;
-        }
-
-        let options = ContainerOptions {
-            name: name.clone(),
-            env,
-            cmd,
-            binds,
-            extra_hosts,
-            runtime,
-            port_bindings,
-            restart_policy,
-            config_override: None,
-        };
+/// Filters for [`Container::list`]
+///
+/// By default, only running containers are listed, and no other filters are applied.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::container::{ContainerStatus, ListFilter};
+/// use regex::Regex;
+///
+/// // All exited containers in the `foo` stack, named like `foo-worker-N`
+/// let filter = ListFilter::new()
+///     .label("com.myorg.stack=foo")
+///     .name(Regex::new(r"^foo-worker-\d+$").unwrap())
+///     .status(ContainerStatus::Exited);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ListFilter {
+    labels: Vec<String>,
+    name: Option<Regex>,
+    status: Option<ContainerStatus>,
+    all: bool,
+}
 
-        Ok(Self {
-            name,
-            id: Some(id),
-            image,
-            client,
-            options,
-        })
+impl ListFilter {
+    /// Create an empty filter, matching all running containers
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    /// Set the environment variables for the container
-    ///
-    /// NOTE: This will override any existing variables.
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container =
-    ///     Container::new(connection.client(), "rustlang/rust").env(["FOO=BAR", "BAZ=QUX"]);
+    /// Only match containers with a label, either `key` or `key=value`
     ///
-    /// // We can now start our container, and the "FOO" and "BAZ" env vars will be set
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// This can be called multiple times, in which case all labels must match.
     #[must_use]
-    pub fn env(mut self, env: impl IntoIterator<Item = impl Into<String>>) -> Self {
-        self.options.env = Some(env.into_iter().map(Into::into).collect());
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
         self
     }
 
-    /// Set the command to run
-    ///
-    /// The command is provided as a list of strings.
-    ///
-    /// NOTE: This will override any existing command
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container =
-    ///     Container::new(connection.client(), "rustlang/rust").cmd(["echo", "Hello!"]);
+    /// Only match containers whose name matches `pattern`
     ///
-    /// // We can now start our container, and the command "echo Hello!" will run
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// The name is matched without Docker's leading `/`.
     #[must_use]
-    pub fn cmd(mut self, cmd: impl IntoIterator<Item = impl Into<String>>) -> Self {
-        self.options.cmd = Some(cmd.into_iter().map(Into::into).collect());
+    pub fn name(mut self, pattern: Regex) -> Self {
+        self.name = Some(pattern);
         self
     }
 
-    /// Sets the container's name
-    ///
-    /// # Examples
+    /// Only match containers with the given status
     ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust").with_name("my_container");
-    ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// NOTE: This implies [`ListFilter::all`], otherwise only running containers could match.
     #[must_use]
-    pub fn with_name(mut self, name: impl Into<String>) -> Self {
-        let name = name.into();
-        self.options.name = Some(name.clone());
-        self.name = Some(name);
+    pub fn status(mut self, status: ContainerStatus) -> Self {
+        self.status = Some(status);
+        self.all = true;
         self
     }
 
-    /// Set a list of volume binds
-    ///
-    /// These binds are in the standard `host:dest[:options]` format. For more information, see
-    /// the [Docker documentation](https://docs.docker.com/engine/storage/bind-mounts/).
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust")
-    ///     // Mount './my-host-dir' at '/some/container/dir' and make it read-only
-    ///     .binds(["./my-host-dir:/some/container/dir:ro"]);
-    ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// Include containers that aren't running (equivalent to `docker ps --all`)
     #[must_use]
-    pub fn binds(mut self, binds: impl IntoIterator<Item = impl Into<String>>) -> Self {
-        self.options.binds = Some(binds.into_iter().map(Into::into).collect());
+    pub fn all(mut self, all: bool) -> Self {
+        self.all = all;
         self
     }
 
-    /// Add entries to the container's `/etc/hosts` (equivalent to `--add-host`)
-    ///
-    /// Each item should be `"hostname:IP"` (e.g. `"host.docker.internal:host-gateway"`).
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust")
-    ///     // Bind `host.docker.internal` (in the container) to the host gateway
-    ///     .extra_hosts(["host.docker.internal:host-gateway"]);
+    fn to_options(&self) -> ListContainersOptions<String> {
+        let mut filters = HashMap::new();
+        if !self.labels.is_empty() {
+            filters.insert(String::from("label"), self.labels.clone());
+        }
+        if let Some(status) = &self.status {
+            filters.insert(String::from("status"), vec![status.as_str().to_string()]);
+        }
+
+        ListContainersOptions {
+            all: self.all,
+            filters,
+            ..Default::default()
+        }
+    }
+
+    fn matches_name(&self, names: Option<&[String]>) -> bool {
+        let Some(pattern) = &self.name else {
+            return true;
+        };
+
+        names.unwrap_or_default().iter().any(|name| {
+            let name = name.strip_prefix('/').unwrap_or(name);
+            pattern.is_match(name)
+        })
+    }
+}
+
+/// How [`Container::wait`] treats a container that exits with a non-zero code
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WaitBehavior {
+    /// Return a [`bollard::errors::Error::DockerContainerWaitError`] for non-zero exits
+    #[default]
+    ErrorOnNonZero,
+    /// Return the exit code, whatever it is
+    ReturnExitCode,
+}
+
+/// How to retry transient daemon errors, see [`Container::retry_policy`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Make up to `attempts` attempts in total, waiting `backoff` before the first retry
     ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// The wait doubles after each retry, and is randomly jittered by up to 50% either way.
     #[must_use]
-    pub fn extra_hosts(mut self, hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
-        self.options.extra_hosts = Some(hosts.into_iter().map(Into::into).collect());
+    pub fn new(attempts: u32, backoff: Duration) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            backoff,
+        }
+    }
+}
+
+/// What [`Container::wait_for`] waits for (equivalent to `docker wait --condition`)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WaitCondition {
+    /// The container is not running, returning immediately if it already isn't
+    #[default]
+    NotRunning,
+    /// The container's next exit, even if its restart policy starts it again
+    NextExit,
+    /// The container is removed
+    Removed,
+}
+
+impl WaitCondition {
+    fn as_str(self) -> &'static str {
+        match self {
+            WaitCondition::NotRunning => "not-running",
+            WaitCondition::NextExit => "next-exit",
+            WaitCondition::Removed => "removed",
+        }
+    }
+}
+
+/// When to pull a container's image, see [`Container::pull_policy`] (equivalent to `--pull`)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PullPolicy {
+    /// Never pull, creating the container fails if the image isn't present locally
+    #[default]
+    Never,
+    /// Pull only if the image isn't present locally
+    Missing,
+    /// Always pull, even if the image is present locally
+    Always,
+}
+
+/// Which addresses to accept when resolving a host, see [`Container::add_host_resolved`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum IpFamily {
+    /// The first address returned, IPv4 or IPv6
+    #[default]
+    Any,
+    /// Only IPv4 addresses
+    V4,
+    /// Only IPv6 addresses
+    V6,
+}
+
+impl IpFamily {
+    fn matches(self, addr: IpAddr) -> bool {
+        match self {
+            IpFamily::Any => true,
+            IpFamily::V4 => addr.is_ipv4(),
+            IpFamily::V6 => addr.is_ipv6(),
+        }
+    }
+}
+
+/// One of a container's output streams, see [`Container::logs_reader`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogStream {
+    /// Standard output, including all output of containers with a TTY
+    Stdout,
+    /// Standard error
+    Stderr,
+}
+
+/// A query for a container's logs, see [`Container::logs_query`]
+///
+/// By default, all of stdout and stderr is returned, without following.
+///
+/// `since` and `until` take anything convertible to a [`SystemTime`], including
+/// `chrono::DateTime`.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::container::LogQuery;
+/// use std::time::{Duration, SystemTime};
+///
+/// // The last 100 stderr lines from the past hour
+/// let query = LogQuery::new()
+///     .tail(100)
+///     .since(SystemTime::now() - Duration::from_secs(60 * 60))
+///     .stdout(false)
+///     .timestamps(true);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogQuery {
+    follow: bool,
+    stdout: bool,
+    stderr: bool,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    timestamps: bool,
+    tail: Option<u64>,
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            follow: false,
+            stdout: true,
+            stderr: true,
+            since: None,
+            until: None,
+            timestamps: false,
+            tail: None,
+        }
+    }
+}
+
+impl LogQuery {
+    /// Create a query for all of stdout and stderr
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep streaming new output as it's written (equivalent to `--follow`)
+    #[must_use]
+    pub fn follow(mut self, follow: bool) -> Self {
+        self.follow = follow;
         self
     }
 
-    /// Add a mapping of container ports to host ports
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::bollard::models::{PortBinding, PortMap};
-    /// use docktopus::{Container, Runtime};
-    /// use std::collections::HashMap;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// // Bind port 80 on the host to port 8080 in the container
-    /// let mut bindings = HashMap::new();
-    /// bindings.insert(
-    ///     String::from("8080/tcp"),
-    ///     Some(vec![PortBinding {
-    ///         host_ip: Some("127.0.0.1".into()),
-    ///         host_port: Some("80".into()),
-    ///     }]),
-    /// );
-    ///
-    /// let runtime = Runtime::detect().await.expect("No runtime found")?;
-    /// let mut container = Container::builder()
-    ///     .image("rustlang/rust")
-    ///     .port_bindings(bindings)
-    ///     .build(runtime);
-    ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// Include stdout, defaults to `true`
     #[must_use]
-    pub fn port_bindings(mut self, port_bindings: PortMap) -> Self {
-        self.options.port_bindings = Some(port_bindings);
+    pub fn stdout(mut self, stdout: bool) -> Self {
+        self.stdout = stdout;
         self
     }
 
-    /// Set the runtime to use for this container (equivalent to `--runtime`)
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::{Container, Runtime};
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let runtime = Runtime::detect().await.expect("No runtime found")?;
-    /// let mut container = Container::builder()
-    ///     .image("rustlang/rust")
-    ///     // Use the Sysbox runtime
-    ///     .runtime("sysbox-runc")
-    ///     .build(runtime);
-    ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// Include stderr, defaults to `true`
     #[must_use]
-    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
-        self.options.runtime = Some(runtime.into());
+    pub fn stderr(mut self, stderr: bool) -> Self {
+        self.stderr = stderr;
         self
     }
 
-    /// Set the container's restart policy (equivalent to `--restart`)
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::bollard::models::{RestartPolicy, RestartPolicyNameEnum};
-    /// use docktopus::{Container, Runtime};
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let runtime = Runtime::detect().await.expect("No runtime found")?;
-    /// let mut container = Container::builder()
-    ///     .image("rustlang/rust")
-    ///     // Always restart the container, unless stopped manually
-    ///     .restart_policy(RestartPolicy {
-    ///         name: Some(RestartPolicyNameEnum::UNLESS_STOPPED),
-    ///         ..Default::default()
-    ///     })
-    ///     .build(runtime);
+    /// Only return output written at or after `since` (equivalent to `--since`)
     ///
-    /// // We can now start our container
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
+    /// NOTE: The daemon only has second precision, so this is truncated to the second.
     #[must_use]
-    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
-        self.options.restart_policy = Some(restart_policy);
+    pub fn since(mut self, since: impl Into<SystemTime>) -> Self {
+        self.since = Some(since.into());
         self
     }
 
-    /// Apply a configuration override
-    ///
-    /// This allows merging specific `bollard::container::Config` options
-    /// with the base configuration generated by `docktopus`.
-    ///
-    /// Fields set in the provided `config` will overwrite the defaults.
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// # use docktopus::DockerBuilder;
-    /// # use docktopus::container::Container;
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// // Configure bollard options for stdin
-    /// let bollard_config_override = docktopus::bollard::container::Config {
-    ///    attach_stdin: Some(true),
-    ///    open_stdin: Some(true),
-    ///    ..Default::default()
-    /// };
-    /// let mut container = Container::new(connection.client(), "rustlang/rust")
-    ///     .cmd(["cat"]) // Example command that reads stdin
-    ///     .config_override(bollard_config_override); // Apply stdin config
+    /// Only return output written before `until` (equivalent to `--until`)
     ///
-    /// // Now the container will be created with stdin attached and open
-    /// container.create().await?;
-    /// # Ok(()) }
-    /// ```
+    /// NOTE: The daemon only has second precision, so this is truncated to the second.
     #[must_use]
-    pub fn config_override(mut self, config: Config<String>) -> Self {
-        self.options.config_override = Some(config);
+    pub fn until(mut self, until: impl Into<SystemTime>) -> Self {
+        self.until = Some(until.into());
         self
     }
 
-    /// Get the container ID if it has been created
-    ///
-    /// This will only have a value if [`Container::create`] or [`Container::start`] has been
-    /// called prior.
+    /// Prefix each line with when it was written (equivalent to `--timestamps`)
     #[must_use]
-    pub fn id(&self) -> Option<&str> {
-        self.id.as_deref()
+    pub fn timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
     }
 
-    /// Get the container name if it has been created
-    ///
-    /// This will only have a value if [`Container::create`] or [`Container::start`] has been
-    /// called prior.
+    /// Only return the last `lines` lines (equivalent to `--tail`)
     #[must_use]
-    pub fn name(&self) -> Option<&str> {
-        self.name.as_deref()
+    pub fn tail(mut self, lines: u64) -> Self {
+        self.tail = Some(lines);
+        self
     }
+}
 
-    /// Attempt to create the container
-    ///
-    /// This will take the following into account:
-    ///
-    /// * [`Container::env`]
-    /// * [`Container::cmd`]
-    /// * [`Container::binds`]
-    /// * [`Container::name`]
-    ///
-    /// Be sure to set these before calling this!
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
-    ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
-    /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust")
-    ///     .env(["FOO=BAR", "BAZ=QUX"])
-    ///     .cmd(["echo", "Hello!"])
-    ///     .binds(["./host-data:/container-data"]);
-    ///
-    /// // The container is created using the above settings
-    /// container.create().await?;
-    ///
-    /// // Now it can be started
-    /// container.start(true).await?;
-    /// # Ok(()) }
-    /// ```
-    #[tracing::instrument(skip_all)]
-    pub async fn create(&mut self) -> Result<(), bollard::errors::Error> {
-        log::debug!("Creating container");
+impl From<LogQuery> for LogsOptions<String> {
+    fn from(query: LogQuery) -> Self {
+        // Times before the epoch can't be represented, and are clamped to it
+        let epoch_secs = |time: SystemTime| {
+            time.duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |since_epoch| {
+                    i64::try_from(since_epoch.as_secs()).unwrap_or(i64::MAX)
+                })
+        };
 
-        let mut config = Config {
-            image: Some(self.image.clone()),
-            cmd: self.options.cmd.clone(),
-            env: self.options.env.clone(),
-            attach_stdout: Some(true),
-            host_config: Some(HostConfig {
-                binds: self.options.binds.clone(),
-                extra_hosts: self.options.extra_hosts.clone(),
-                port_bindings: self.options.port_bindings.clone(),
-                restart_policy: self.options.restart_policy.clone(),
-                runtime: self.options.runtime.clone(),
-                ..Default::default()
-            }),
+        LogsOptions {
+            follow: query.follow,
+            stdout: query.stdout,
+            stderr: query.stderr,
+            since: query.since.map_or(0, epoch_secs),
+            until: query.until.map_or(0, epoch_secs),
+            timestamps: query.timestamps,
+            tail: query
+                .tail
+                .map_or_else(|| String::from("all"), |lines| lines.to_string()),
+        }
+    }
+}
+
+/// A single run of a container's healthcheck, see [`Container::health_log`]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct HealthCheckResult {
+    /// When the check started, as reported by the daemon (RFC 3339)
+    pub start: Option<String>,
+    /// When the check finished, as reported by the daemon (RFC 3339)
+    pub end: Option<String>,
+    /// The exit code of the check, `0` meaning healthy
+    pub exit_code: Option<i64>,
+    /// The output of the check
+    pub output: String,
+}
+
+/// New resource limits for a running container, see [`Container::update`]
+///
+/// Limits left as `None` are unchanged. Memory values use the same format as
+/// [`SystemRequirements`](crate::config::SystemRequirements), e.g. `"512M"` or `"1G"`.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::container::ResourceUpdate;
+///
+/// // Throttle to half a CPU and 256 MiB of memory, with no swap
+/// let limits = ResourceUpdate {
+///     memory: Some(String::from("256M")),
+///     memory_swap: Some(String::from("256M")),
+///     nano_cpus: Some(500_000_000),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResourceUpdate {
+    /// The memory limit (equivalent to `--memory`)
+    pub memory: Option<String>,
+    /// The total memory plus swap limit (equivalent to `--memory-swap`), `"-1"` for unlimited swap
+    pub memory_swap: Option<String>,
+    /// The relative CPU weight (equivalent to `--cpu-shares`)
+    pub cpu_shares: Option<i64>,
+    /// The CPUs the container may run on, e.g. `"0-3"` or `"0,1"` (equivalent to `--cpuset-cpus`)
+    pub cpuset_cpus: Option<String>,
+    /// The CPU quota in units of 10<sup>-9</sup> CPUs (equivalent to `--cpus`)
+    pub nano_cpus: Option<i64>,
+}
+
+impl ResourceUpdate {
+    fn to_options(&self) -> Result<UpdateContainerOptions<String>, Error> {
+        let memory_swap = self
+            .memory_swap
+            .as_deref()
+            .map(parse_memory_swap)
+            .transpose()?;
+        let cpu_shares = self
+            .cpu_shares
+            .map(|shares| {
+                isize::try_from(shares).map_err(|_| {
+                    Error::InvalidResourceLimit(format!("Invalid CPU shares: {shares}"))
+                })
+            })
+            .transpose()?;
+
+        Ok(UpdateContainerOptions {
+            memory: self.memory.as_deref().map(parse_memory_limit).transpose()?,
+            memory_swap,
+            cpu_shares,
+            cpuset_cpus: self.cpuset_cpus.clone(),
+            nano_cpus: self.nano_cpus,
             ..Default::default()
-        };
+        })
+    }
+}
 
-        // Apply overrides if present
-        if let Some(override_config) = &self.options.config_override {
-            if let Some(val) = &override_config.hostname {
-                config.hostname = Some(val.clone());
+/// A Docker event for a single container, see [`Container::events`]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ContainerEvent {
+    /// What happened, e.g. `start`, `die`, `oom`, or `health_status: healthy`
+    pub action: String,
+    /// When it happened, in nanoseconds since the Unix epoch
+    pub time_nano: Option<i64>,
+    /// Extra details, such as `exitCode` for `die` events and the container's labels
+    pub attributes: HashMap<String, String>,
+}
+
+impl ContainerEvent {
+    /// The exit code of the container's main process, for `die` events
+    #[must_use]
+    pub fn exit_code(&self) -> Option<i64> {
+        self.attributes.get("exitCode")?.parse().ok()
+    }
+}
+
+impl From<EventMessage> for ContainerEvent {
+    fn from(event: EventMessage) -> Self {
+        Self {
+            action: event.action.unwrap_or_default(),
+            time_nano: event
+                .time_nano
+                .or_else(|| event.time.map(|time| time.saturating_mul(1_000_000_000))),
+            attributes: event
+                .actor
+                .and_then(|actor| actor.attributes)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Which GPUs a container can use, see [`Container::gpus`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GpuDevices {
+    /// Every GPU on the host (`--gpus all`)
+    All,
+    /// Any `n` GPUs (`--gpus 2`)
+    Count(u32),
+    /// Specific GPUs, by index or UUID (`--gpus '"device=0,2"'`)
+    DeviceIds(Vec<String>),
+}
+
+/// A request for GPUs, see [`Container::gpus`]
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::container::GpuRequest;
+///
+/// // `--gpus all`
+/// let all = GpuRequest::all();
+///
+/// // `--gpus '"device=0,1",capabilities=compute,utility'`
+/// let some = GpuRequest::device_ids(["0", "1"]).capabilities([["compute", "utility"]]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GpuRequest {
+    devices: GpuDevices,
+    capabilities: Vec<Vec<String>>,
+}
+
+impl GpuRequest {
+    /// Request `devices`, with the default `gpu` capability
+    #[must_use]
+    pub fn new(devices: GpuDevices) -> Self {
+        Self {
+            devices,
+            capabilities: vec![vec![String::from("gpu")]],
+        }
+    }
+
+    /// Request every GPU on the host
+    #[must_use]
+    pub fn all() -> Self {
+        Self::new(GpuDevices::All)
+    }
+
+    /// Request any `count` GPUs
+    #[must_use]
+    pub fn count(count: u32) -> Self {
+        Self::new(GpuDevices::Count(count))
+    }
+
+    /// Request specific GPUs, by index or UUID
+    #[must_use]
+    pub fn device_ids(ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::new(GpuDevices::DeviceIds(
+            ids.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Set the required driver capabilities, defaults to `[["gpu"]]`
+    ///
+    /// Each inner list is a set of capabilities that must *all* be supported, and the request is
+    /// satisfied if *any* of the sets are.
+    #[must_use]
+    pub fn capabilities(
+        mut self,
+        capabilities: impl IntoIterator<Item = impl IntoIterator<Item = impl Into<String>>>,
+    ) -> Self {
+        self.capabilities = capabilities
+            .into_iter()
+            .map(|set| set.into_iter().map(Into::into).collect())
+            .collect();
+        self
+    }
+
+    fn into_device_request(self) -> DeviceRequest {
+        let (count, device_ids) = match self.devices {
+            // Docker's CLI uses -1 for `all`
+            GpuDevices::All => (Some(-1), None),
+            GpuDevices::Count(count) => (Some(i64::from(count)), None),
+            GpuDevices::DeviceIds(ids) => (None, Some(ids)),
+        };
+
+        DeviceRequest {
+            driver: Some(String::new()),
+            count,
+            device_ids,
+            capabilities: Some(self.capabilities),
+            options: Some(HashMap::new()),
+        }
+    }
+}
+
+/// A host device exposed to a container, see [`Container::devices`]
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::container::DeviceMapping;
+///
+/// // `--device /dev/net/tun`
+/// let tun = DeviceMapping::new("/dev/net/tun");
+/// assert_eq!(tun.path_in_container, "/dev/net/tun");
+/// assert_eq!(tun.cgroup_permissions, "rwm");
+///
+/// // `--device /dev/sdc:/dev/xvdc:r`
+/// let disk: DeviceMapping = "/dev/sdc:/dev/xvdc:r".parse()?;
+/// assert_eq!(disk.path_on_host, "/dev/sdc");
+/// assert_eq!(disk.path_in_container, "/dev/xvdc");
+/// assert_eq!(disk.cgroup_permissions, "r");
+/// # Ok::<(), docktopus::container::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceMapping {
+    /// The device's path on the host
+    pub path_on_host: String,
+    /// Where the device appears in the container
+    pub path_in_container: String,
+    /// Any combination of `r` (read), `w` (write) and `m` (mknod)
+    pub cgroup_permissions: String,
+}
+
+impl DeviceMapping {
+    /// Expose `path` at the same location in the container, with `rwm` permissions
+    #[must_use]
+    pub fn new(path: impl Into<String>) -> Self {
+        let path = path.into();
+        Self {
+            path_in_container: path.clone(),
+            path_on_host: path,
+            cgroup_permissions: String::from("rwm"),
+        }
+    }
+}
+
+impl FromStr for DeviceMapping {
+    type Err = Error;
+
+    /// Parse the `--device` shorthand, `host[:container][:permissions]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_permissions =
+            |part: &str| !part.is_empty() && part.chars().all(|c| matches!(c, 'r' | 'w' | 'm'));
+
+        let parts = s.split(':').collect::<Vec<_>>();
+        let (host, container, permissions) = match parts[..] {
+            [host] => (host, host, "rwm"),
+            // Like the Docker CLI, `host:perms` keeps the host path
+            [host, perms] if is_permissions(perms) => (host, host, perms),
+            [host, container] => (host, container, "rwm"),
+            [host, container, perms] => (host, container, perms),
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "invalid device `{s}`, expected `host[:container][:permissions]`"
+                )));
+            }
+        };
+
+        if !host.starts_with('/') || !container.starts_with('/') {
+            return Err(Error::InvalidConfig(format!(
+                "invalid device `{s}`, device paths must be absolute"
+            )));
+        }
+        if !is_permissions(permissions) {
+            return Err(Error::InvalidConfig(format!(
+                "invalid device permissions `{permissions}`, expected a combination of `rwm`"
+            )));
+        }
+
+        Ok(Self {
+            path_on_host: host.to_string(),
+            path_in_container: container.to_string(),
+            cgroup_permissions: permissions.to_string(),
+        })
+    }
+}
+
+impl From<DeviceMapping> for bollard::models::DeviceMapping {
+    fn from(mapping: DeviceMapping) -> Self {
+        Self {
+            path_on_host: Some(mapping.path_on_host),
+            path_in_container: Some(mapping.path_in_container),
+            cgroup_permissions: Some(mapping.cgroup_permissions),
+        }
+    }
+}
+
+/// When a container is considered ready, see [`Container::logs_until_ready`]
+#[derive(Clone, Debug)]
+pub enum Readiness {
+    /// The container's healthcheck reports it as healthy, see [`Container::wait_until_healthy`]
+    Healthy,
+    /// A line matching the pattern appears in the logs, see [`Container::wait_for_log`]
+    LogMatches(Regex),
+    /// A TCP connection can be made to the host port published for this container port
+    ///
+    /// NOTE: This connects from the machine running this code, so it only works with a local
+    ///       daemon.
+    Port(u16),
+}
+
+/// An event from [`Container::provision`]
+#[derive(Debug)]
+pub enum ProvisionEvent {
+    /// Progress pulling the image
+    Pull(PullProgress),
+    /// The container was created with this ID, this is the final event of a successful provision
+    Created(String),
+    /// Provisioning failed, this is always the final event
+    Failed(Error),
+}
+
+/// A restart of a container by the daemon's restart policy, see [`Container::restart_events`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct RestartEvent {
+    /// How many times the daemon has restarted the container, including this restart
+    pub restart_count: i64,
+    /// The exit code that triggered the restart
+    pub exit_code: Option<i64>,
+}
+
+/// The state of a [`Container::restart_events`] stream
+struct RestartState<S> {
+    events: S,
+    client: Arc<Docker>,
+    id: String,
+    /// How many restarts have been seen since the last manual start, if known
+    restarts: Option<i64>,
+    /// An exit that hasn't been followed by a start yet
+    exit: Option<ContainerEvent>,
+}
+
+/// The captured result of running a container to completion
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct RunOutput {
+    /// The exit code of the container's main process
+    pub exit_code: i64,
+    /// Everything written to stdout
+    pub stdout: String,
+    /// Everything written to stderr
+    pub stderr: String,
+}
+
+/// Options for [`Container::attach`]
+///
+/// By default, stdin, stdout, and stderr are all attached, and only output written after
+/// attaching is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::container::AttachOptions;
+///
+/// // Replay everything written so far, and don't send any input
+/// let options = AttachOptions::new().stdin(false).logs(true);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttachOptions {
+    stdin: bool,
+    stdout: bool,
+    stderr: bool,
+    logs: bool,
+    detach_keys: Option<String>,
+}
+
+impl Default for AttachOptions {
+    fn default() -> Self {
+        Self {
+            stdin: true,
+            stdout: true,
+            stderr: true,
+            logs: false,
+            detach_keys: None,
+        }
+    }
+}
+
+impl AttachOptions {
+    /// Attach to stdin, stdout, and stderr
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach to stdin, defaults to `true`
+    ///
+    /// NOTE: Input is only delivered if the container was created with stdin open, see the
+    ///       [`Container::config_override`] example.
+    #[must_use]
+    pub fn stdin(mut self, stdin: bool) -> Self {
+        self.stdin = stdin;
+        self
+    }
+
+    /// Attach to stdout, defaults to `true`
+    #[must_use]
+    pub fn stdout(mut self, stdout: bool) -> Self {
+        self.stdout = stdout;
+        self
+    }
+
+    /// Attach to stderr, defaults to `true`
+    #[must_use]
+    pub fn stderr(mut self, stderr: bool) -> Self {
+        self.stderr = stderr;
+        self
+    }
+
+    /// Replay the output written before attaching, defaults to `false`
+    #[must_use]
+    pub fn logs(mut self, logs: bool) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    /// Override the key sequence for detaching (equivalent to `--detach-keys`), e.g. `ctrl-x`
+    #[must_use]
+    pub fn detach_keys(mut self, keys: impl Into<String>) -> Self {
+        self.detach_keys = Some(keys.into());
+        self
+    }
+
+    fn to_options(&self) -> AttachContainerOptions<String> {
+        AttachContainerOptions {
+            stdin: Some(self.stdin),
+            stdout: Some(self.stdout),
+            stderr: Some(self.stderr),
+            stream: Some(true),
+            logs: Some(self.logs),
+            detach_keys: self.detach_keys.clone(),
+        }
+    }
+}
+
+/// An attached container's streams, see [`Container::attach`]
+pub struct AttachHandle {
+    /// The container's output
+    ///
+    /// Without a TTY, this is [`LogOutput::StdOut`] and [`LogOutput::StdErr`] frames. With a TTY,
+    /// the streams can't be told apart, and everything is [`LogOutput::Console`].
+    pub output: Pin<Box<dyn Stream<Item = Result<LogOutput, bollard::errors::Error>> + Send>>,
+    /// The container's stdin
+    pub input: Pin<Box<dyn AsyncWrite + Send>>,
+    /// Whether the container has a TTY, see [`AttachHandle::output`]
+    pub tty: bool,
+}
+
+impl core::fmt::Debug for AttachHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AttachHandle")
+            .field("tty", &self.tty)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An auditable record of exactly what was run, see [`Container::run_with_provenance`]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Provenance {
+    /// The image reference the container was configured with (e.g. `alpine:latest`)
+    pub image: String,
+    /// The ID of the image the container was actually created from
+    pub image_id: Option<String>,
+    /// The repository digest of the image, if it was pulled from a registry
+    pub image_digest: Option<String>,
+    /// The version of the Docker daemon
+    pub daemon_version: Option<String>,
+    /// The API version of the Docker daemon
+    pub api_version: Option<String>,
+    /// The daemon platform in `os/arch` form
+    pub platform: Option<String>,
+    /// When the container was started, as reported by the daemon (RFC 3339)
+    pub started_at: Option<String>,
+    /// When the container exited, as reported by the daemon (RFC 3339)
+    pub finished_at: Option<String>,
+}
+
+/// A [Docker](https://en.wikipedia.org/wiki/Docker_(software)) container
+///
+/// Cloning a `Container` is cheap, and both clones refer to the same container on the daemon
+/// (by id), so one can be handed to a monitoring task while the other controls the lifecycle.
+/// Changes made through one clone afterwards (e.g. creating the container) aren't seen by the
+/// other, clone after [`Container::create`] to share it.
+#[derive(Debug)]
+pub struct Container {
+    id: Option<String>,
+    name: Option<String>,
+    image: String,
+    client: Arc<Docker>,
+    options: ContainerOptions,
+    /// Snapshot images to remove alongside the container, see [`Container::snapshot_and_restart`]
+    snapshots: Vec<String>,
+    /// The last inspect result, see [`Container::inspect_cache_ttl`]
+    inspect_cache: tokio::sync::Mutex<Option<(Instant, ContainerInspectResponse)>>,
+    /// A timeout for the next long-running operation, see [`Container::with_operation_timeout`]
+    operation_timeout: Mutex<Option<Duration>>,
+}
+
+impl Clone for Container {
+    /// Clone the handle, without any cached inspect result or pending operation timeout
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            image: self.image.clone(),
+            client: self.client.clone(),
+            options: self.options.clone(),
+            snapshots: self.snapshots.clone(),
+            inspect_cache: tokio::sync::Mutex::default(),
+            operation_timeout: Mutex::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct ContainerOptions {
+    name: Option<String>,
+    env: Option<Vec<String>>,
+    cmd: Option<Vec<String>>,
+    entrypoint: Option<Vec<String>>,
+    working_dir: Option<String>,
+    binds: Option<Vec<String>>,
+    extra_hosts: Option<Vec<String>>,
+    resolved_hosts: Vec<(String, String, IpFamily)>,
+    runtime: Option<String>,
+    port_bindings: Option<PortMap>,
+    publish_all_ports: Option<bool>,
+    restart_policy: Option<RestartPolicy>,
+    readonly_rootfs: Option<bool>,
+    privileged: Option<bool>,
+    auto_remove: Option<bool>,
+    tmpfs: Option<HashMap<String, String>>,
+    memory: Option<i64>,
+    memory_swap: Option<i64>,
+    memory_reservation: Option<i64>,
+    memory_swappiness: Option<i64>,
+    shm_size: Option<i64>,
+    ipc_mode: Option<String>,
+    pid_mode: Option<String>,
+    cpu_shares: Option<i64>,
+    cpuset_cpus: Option<String>,
+    nano_cpus: Option<i64>,
+    device_requests: Option<Vec<DeviceRequest>>,
+    blkio_weight_device: Option<Vec<ResourcesBlkioWeightDevice>>,
+    devices: Option<Vec<bollard::models::DeviceMapping>>,
+    cap_add: Option<Vec<String>>,
+    cap_drop: Option<Vec<String>>,
+    ulimits: Option<Vec<ResourcesUlimits>>,
+    sysctls: Option<HashMap<String, String>>,
+    log_config: Option<HostConfigLogConfig>,
+    stop_signal: Option<String>,
+    stop_timeout: Option<Duration>,
+    healthcheck: Option<HealthConfig>,
+    labels: Option<HashMap<String, String>>,
+    graceful_force: bool,
+    pull_policy: PullPolicy,
+    log_pull_progress: bool,
+    wait_behavior: WaitBehavior,
+    network: Option<String>,
+    network_aliases: Option<Vec<String>>,
+    static_ips: HashMap<String, StaticIps>,
+    inspect_cache_ttl: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    config_override: Option<Config<String>>,
+}
+
+/// Addresses requested for a container on a single network
+#[derive(Debug, Default, Clone, Copy)]
+struct StaticIps {
+    ipv4: Option<Ipv4Addr>,
+    ipv6: Option<Ipv6Addr>,
+}
+
+impl StaticIps {
+    fn addresses(self) -> impl Iterator<Item = IpAddr> {
+        self.ipv4
+            .map(IpAddr::V4)
+            .into_iter()
+            .chain(self.ipv6.map(IpAddr::V6))
+    }
+
+    fn to_ipam_config(self) -> EndpointIpamConfig {
+        EndpointIpamConfig {
+            ipv4_address: self.ipv4.map(|ip| ip.to_string()),
+            ipv6_address: self.ipv6.map(|ip| ip.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Container {
+    /// Create a new `Container`
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn new<T>(client: Arc<Docker>, image: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            id: None,
+            name: None,
+            image: image.into(),
+            client,
+            options: ContainerOptions::default(),
+            snapshots: Vec::new(),
+            inspect_cache: tokio::sync::Mutex::default(),
+            operation_timeout: Mutex::default(),
+        }
+    }
+
+    /// Attempt to fetch an existing container by its ID
+    ///
+    /// # Errors
+    ///
+    /// * Docker inspect fails
+    /// * The container isn't found
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// // We can now start our container and grab its id
+    /// container.start(false).await?;
+    ///
+    /// let id = container.id().unwrap();
+    ///
+    /// let container2 = Container::from_id(connection.client(), id).await?;
+    ///
+    /// assert_eq!(container.id(), container2.id());
+    /// # Ok(()) }
+    /// ```
+    pub async fn from_id<T>(client: Arc<Docker>, id: T) -> Result<Self, Error>
+    where
+        T: AsRef<str>,
+    {
+        let ContainerInspectResponse {
+            id: Some(id),
+            name,
+            config:
+                Some(ContainerConfig {
+                    env,
+                    cmd,
+                    entrypoint,
+                    working_dir,
+                    stop_signal,
+                    stop_timeout,
+                    image: Some(image),
+                    labels,
+                    healthcheck,
+                    ..
+                }),
+            mounts,
+            host_config,
+            ..
+        } = client
+            .inspect_container(id.as_ref(), None::<InspectContainerOptions>)
+            .await?
+        else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let binds = mounts.map(|mounts| {
+            mounts
+                .into_iter()
+                .filter_map(|mount| {
+                    if !matches!(mount.typ, Some(MountPointTypeEnum::BIND)) {
+                        return None;
+                    }
+                    let source = mount.source?;
+                    let dest = mount.destination?;
+                    let mut bind = format!("{}:{}", source, dest);
+                    if let Some(mode) = mount.mode {
+                        bind.push(':');
+                        bind.push_str(&mode);
+                    }
+                    Some(bind)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut extra_hosts = None;
+        let mut runtime = None;
+        let mut restart_policy = None;
+        let mut port_bindings = None;
+        let mut publish_all_ports = None;
+        let mut readonly_rootfs = None;
+        let mut privileged = None;
+        let mut auto_remove = None;
+        let mut tmpfs = None;
+        let mut memory = None;
+        let mut memory_swap = None;
+        let mut memory_reservation = None;
+        let mut memory_swappiness = None;
+        let mut shm_size = None;
+        let mut ipc_mode = None;
+        let mut pid_mode = None;
+        let mut cpu_shares = None;
+        let mut cpuset_cpus = None;
+        let mut nano_cpus = None;
+        let mut device_requests = None;
+        let mut blkio_weight_device = None;
+        let mut devices = None;
+        let mut cap_add = None;
+        let mut cap_drop = None;
+        let mut ulimits = None;
+        let mut sysctls = None;
+        let mut log_config = None;
+        let mut network = None;
+        if let Some(hc) = host_config {
+            network = hc.network_mode;
+            extra_hosts = hc.extra_hosts;
+            runtime = hc.runtime;
+            restart_policy = hc.restart_policy;
+            port_bindings = hc.port_bindings;
+            publish_all_ports = hc.publish_all_ports;
+            readonly_rootfs = hc.readonly_rootfs;
+            privileged = hc.privileged;
+            auto_remove = hc.auto_remove;
+            tmpfs = hc.tmpfs;
+            memory = hc.memory;
+            memory_swap = hc.memory_swap;
+            memory_reservation = hc.memory_reservation;
+            memory_swappiness = hc.memory_swappiness;
+            shm_size = hc.shm_size;
+            ipc_mode = hc.ipc_mode;
+            pid_mode = hc.pid_mode;
+            cpu_shares = hc.cpu_shares;
+            cpuset_cpus = hc.cpuset_cpus;
+            nano_cpus = hc.nano_cpus;
+            device_requests = hc.device_requests;
+            blkio_weight_device = hc.blkio_weight_device;
+            devices = hc.devices;
+            cap_add = hc.cap_add;
+            cap_drop = hc.cap_drop;
+            ulimits = hc.ulimits;
+            sysctls = hc.sysctls;
+            log_config = hc.log_config;
+        }
+
+        let options = ContainerOptions {
+            name: name.clone(),
+            env,
+            cmd,
+            entrypoint,
+            working_dir,
+            binds,
+            extra_hosts,
+            resolved_hosts: Vec::new(),
+            runtime,
+            port_bindings,
+            publish_all_ports,
+            restart_policy,
+            readonly_rootfs,
+            privileged,
+            auto_remove,
+            tmpfs,
+            memory,
+            memory_swap,
+            memory_reservation,
+            memory_swappiness,
+            shm_size,
+            ipc_mode,
+            pid_mode,
+            cpu_shares,
+            cpuset_cpus,
+            nano_cpus,
+            device_requests,
+            blkio_weight_device,
+            devices,
+            cap_add,
+            cap_drop,
+            ulimits,
+            sysctls,
+            log_config,
+            stop_signal,
+            stop_timeout: stop_timeout
+                .and_then(|secs| u64::try_from(secs).ok())
+                .map(Duration::from_secs),
+            healthcheck,
+            labels,
+            graceful_force: false,
+            pull_policy: PullPolicy::default(),
+            log_pull_progress: false,
+            wait_behavior: WaitBehavior::default(),
+            network,
+            network_aliases: None,
+            static_ips: HashMap::new(),
+            inspect_cache_ttl: None,
+            retry_policy: None,
+            config_override: None,
+        };
+
+        Ok(Self {
+            name,
+            id: Some(id),
+            image,
+            client,
+            options,
+            snapshots: Vec::new(),
+            inspect_cache: tokio::sync::Mutex::default(),
+            operation_timeout: Mutex::default(),
+        })
+    }
+
+    /// Attempt to fetch an existing container by its name
+    ///
+    /// The name is matched exactly, with or without Docker's leading `/`. Stopped containers are
+    /// included.
+    ///
+    /// # Errors
+    ///
+    /// * Docker fails to list the containers
+    /// * No container has the name, see [`Error::ContainerNotFound`]
+    /// * More than one container has the name, see [`Error::AmbiguousName`]
+    /// * Docker inspect fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").with_name("my-container");
+    /// container.start(false).await?;
+    ///
+    /// let container2 = Container::from_name(connection.client(), "my-container").await?;
+    ///
+    /// assert_eq!(container.id(), container2.id());
+    /// # Ok(()) }
+    /// ```
+    pub async fn from_name<T>(client: Arc<Docker>, name: T) -> Result<Self, Error>
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref().trim_start_matches('/');
+
+        // The daemon's `name` filter is a substring match, so filter exactly afterwards
+        let options = ListContainersOptions {
+            all: true,
+            filters: HashMap::from([(String::from("name"), vec![name.to_string()])]),
+            ..Default::default()
+        };
+        let summaries = client.list_containers(Some(options)).await?;
+
+        let mut ids = summaries
+            .into_iter()
+            .filter(|summary| {
+                summary
+                    .names
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|n| n.trim_start_matches('/') == name)
+            })
+            .filter_map(|summary| summary.id)
+            .collect::<Vec<_>>();
+
+        match ids.len() {
+            0 => Err(Error::ContainerNotFound),
+            1 => Self::from_id(client, ids.remove(0)).await,
+            _ => Err(Error::AmbiguousName {
+                name: name.to_string(),
+                ids,
+            }),
+        }
+    }
+
+    /// List existing containers matching `filter`
+    ///
+    /// Each returned `Container` is fully hydrated (see [`Container::from_id`]), so it can
+    /// immediately be stopped, removed, etc.
+    ///
+    /// # Errors
+    ///
+    /// * Docker fails to list the containers
+    /// * Any of the matching containers fail to be inspected
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, ListFilter};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// // Clean up everything our test harness spawned
+    /// let filter = ListFilter::new().label("com.myorg.harness").all(true);
+    /// for container in Container::list(connection.client(), filter).await? {
+    ///     let remove_container_options = docktopus::bollard::container::RemoveContainerOptions {
+    ///         force: true,
+    ///         ..Default::default()
+    ///     };
+    ///     container.remove(Some(remove_container_options)).await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn list(client: Arc<Docker>, filter: ListFilter) -> Result<Vec<Self>, Error> {
+        let summaries = client.list_containers(Some(filter.to_options())).await?;
+
+        let mut containers = Vec::with_capacity(summaries.len());
+        for summary in summaries {
+            if !filter.matches_name(summary.names.as_deref()) {
+                continue;
+            }
+
+            let Some(id) = summary.id else {
+                continue;
+            };
+
+            containers.push(Self::from_id(client.clone(), id).await?);
+        }
+
+        Ok(containers)
+    }
+
+    /// Set the environment variables for the container
+    ///
+    /// NOTE: This will override any existing variables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").env(["FOO=BAR", "BAZ=QUX"]);
+    ///
+    /// // We can now start our container, and the "FOO" and "BAZ" env vars will be set
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn env(mut self, env: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.env = Some(env.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the command to run
+    ///
+    /// The command is provided as a list of strings. If the image (or [`Container::entrypoint`])
+    /// has an entrypoint, these are passed to it as arguments.
+    ///
+    /// NOTE: This will override any existing command
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").cmd(["echo", "Hello!"]);
+    ///
+    /// // We can now start our container, and the command "echo Hello!" will run
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn cmd(mut self, cmd: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.cmd = Some(cmd.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the entrypoint, replacing the image's (equivalent to `--entrypoint`)
+    ///
+    /// As with Docker, the entrypoint and [`Container::cmd`] compose: the command is appended to
+    /// the entrypoint as arguments. An empty entrypoint resets it, so the command is run directly.
+    ///
+    /// NOTE: This will override any existing entrypoint. Setting an entrypoint does *not* clear
+    ///       the image's default command, set [`Container::cmd`] as well if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .entrypoint(["sh", "-c"])
+    ///     // Runs `sh -c 'echo $HOME'`
+    ///     .cmd(["echo $HOME"]);
+    ///
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn entrypoint(mut self, entrypoint: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.entrypoint = Some(entrypoint.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the working directory for the command (equivalent to `--workdir`)
+    ///
+    /// The directory is created if it doesn't exist in the image.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     .binds(["./my-crate:/src"])
+    ///     .working_dir("/src")
+    ///     .cmd(["cargo", "build"]);
+    ///
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn working_dir(mut self, working_dir: impl Into<String>) -> Self {
+        self.options.working_dir = Some(working_dir.into());
+        self
+    }
+
+    /// Sets the container's name
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust").with_name("my_container");
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.options.name = Some(name.clone());
+        self.name = Some(name);
+        self
+    }
+
+    /// Set a list of volume binds
+    ///
+    /// These binds are in the standard `host:dest[:options]` format. For more information, see
+    /// the [Docker documentation](https://docs.docker.com/engine/storage/bind-mounts/).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     // Mount './my-host-dir' at '/some/container/dir' and make it read-only
+    ///     .binds(["./my-host-dir:/some/container/dir:ro"]);
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn binds(mut self, binds: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.binds = Some(binds.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add entries to the container's `/etc/hosts` (equivalent to `--add-host`)
+    ///
+    /// Each item should be `"hostname:IP"` (e.g. `"host.docker.internal:host-gateway"`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     // Bind `host.docker.internal` (in the container) to the host gateway
+    ///     .extra_hosts(["host.docker.internal:host-gateway"]);
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn extra_hosts(mut self, hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.extra_hosts = Some(hosts.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add an `/etc/hosts` entry for `hostname`, pointing at the address of `target_host`
+    ///
+    /// Unlike [`Container::extra_hosts`], `target_host` is a name, which is resolved on the
+    /// machine running this code (not the Docker daemon) when the container is created. Only
+    /// addresses from `family` are considered, and the first match is used. The resolved entries
+    /// are added alongside any [`Container::extra_hosts`].
+    ///
+    /// If `target_host` can't be resolved, [`Container::create`] fails with
+    /// [`Error::HostResolution`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, IpFamily};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     // `db` (in the container) points to whatever `db.example.com` resolves to right now
+    ///     .add_host_resolved("db", "db.example.com", IpFamily::V4);
+    ///
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn add_host_resolved(
+        mut self,
+        hostname: impl Into<String>,
+        target_host: impl Into<String>,
+        family: IpFamily,
+    ) -> Self {
+        self.options
+            .resolved_hosts
+            .push((hostname.into(), target_host.into(), family));
+        self
+    }
+
+    /// Add a mapping of container ports to host ports
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::models::PortBinding;
+    /// use docktopus::container::Container;
+    /// use std::collections::HashMap;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// // Bind port 80 on the host to port 8080 in the container
+    /// let mut bindings = HashMap::new();
+    /// bindings.insert(
+    ///     String::from("8080/tcp"),
+    ///     Some(vec![PortBinding {
+    ///         host_ip: Some("127.0.0.1".into()),
+    ///         host_port: Some("80".into()),
+    ///     }]),
+    /// );
+    ///
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").port_bindings(bindings);
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn port_bindings(mut self, port_bindings: PortMap) -> Self {
+        self.options.port_bindings = Some(port_bindings);
+        self
+    }
+
+    /// Publish a container port on all of the host's interfaces (equivalent to `--publish`)
+    ///
+    /// This can be called multiple times, including for the same container port, to publish on
+    /// several host ports. See [`Container::publish_port_on`] to bind to a single interface.
+    ///
+    /// NOTE: [`Container::port_bindings`] replaces every published port, so it should be called
+    ///       *before* this if both are used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::config::Protocol;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "coredns/coredns")
+    ///     // Equivalent to `-p 53:53/udp -p 53:53/tcp`
+    ///     .publish_port(53, 53, Protocol::Udp)
+    ///     .publish_port(53, 53, Protocol::Tcp);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn publish_port(self, container_port: u16, host_port: u16, protocol: Protocol) -> Self {
+        self.publish(None, container_port, Some(host_port), protocol)
+    }
+
+    /// Publish a container port on a single host interface (equivalent to
+    /// `--publish host_ip:host_port:container_port`)
+    ///
+    /// See [`Container::publish_port`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::config::Protocol;
+    /// use docktopus::container::Container;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     // Only reachable from this machine
+    ///     .publish_port_on(Ipv4Addr::LOCALHOST.into(), 5432, 5432, Protocol::Tcp);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn publish_port_on(
+        self,
+        host_ip: IpAddr,
+        container_port: u16,
+        host_port: u16,
+        protocol: Protocol,
+    ) -> Self {
+        self.publish(Some(host_ip), container_port, Some(host_port), protocol)
+    }
+
+    /// Publish a port given as a `docker run --publish` style string
+    ///
+    /// The supported forms are:
+    ///
+    /// * `"80"` - container port 80 on a random host port
+    /// * `"8080:80"` - container port 80 on host port 8080
+    /// * `"127.0.0.1:8080:80"` - as above, but only on the given interface
+    /// * `"127.0.0.1::80"` - container port 80 on a random port on the given interface
+    /// * `"[::1]:8080:80"` - IPv6 addresses must be wrapped in brackets
+    ///
+    /// Any of these may end in `/tcp` (the default) or `/udp`. Port ranges aren't supported.
+    ///
+    /// See [`Container::publish_port`].
+    ///
+    /// # Errors
+    ///
+    /// The spec is malformed, see [`Error::InvalidConfig`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "coredns/coredns")
+    ///     .port("127.0.0.1:5353:53/udp")?
+    ///     .port("8080:8080")?;
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn port(self, spec: &str) -> Result<Self, Error> {
+        let (host_ip, host_port, container_port, protocol) = parse_port_spec(spec)?;
+        Ok(self.publish(host_ip, container_port, host_port, protocol))
+    }
+
+    fn publish(
+        mut self,
+        host_ip: Option<IpAddr>,
+        container_port: u16,
+        host_port: Option<u16>,
+        protocol: Protocol,
+    ) -> Self {
+        self.options
+            .port_bindings
+            .get_or_insert_with(HashMap::new)
+            .entry(format!("{container_port}/{protocol}"))
+            .or_default()
+            .get_or_insert_with(Vec::new)
+            .push(PortBinding {
+                host_ip: host_ip.map(|ip| ip.to_string()),
+                // An empty host port has the daemon pick one
+                host_port: Some(host_port.map(|port| port.to_string()).unwrap_or_default()),
+            });
+        self
+    }
+
+    /// Publish every port the image exposes on random host ports (equivalent to
+    /// `--publish-all`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "nginx").publish_all_ports(true);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn publish_all_ports(mut self, publish: bool) -> Self {
+        self.options.publish_all_ports = Some(publish);
+        self
+    }
+
+    /// Set the runtime to use for this container (equivalent to `--runtime`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     // Use the Sysbox runtime
+    ///     .runtime("sysbox-runc");
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.options.runtime = Some(runtime.into());
+        self
+    }
+
+    /// Set how readily the container's memory is swapped out (equivalent to `--memory-swappiness`)
+    ///
+    /// `swappiness` ranges from 0 (avoid swapping) to 100 (swap aggressively). If unset, the
+    /// host's value is inherited.
+    ///
+    /// NOTE: Values outside of 0-100 are rejected by [`Container::validate`] and
+    ///       [`Container::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "redis")
+    ///     // Keep the working set in memory
+    ///     .memory_swappiness(0);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn memory_swappiness(mut self, swappiness: i64) -> Self {
+        self.options.memory_swappiness = Some(swappiness);
+        self
+    }
+
+    /// Set the memory limit (equivalent to `--memory`)
+    ///
+    /// `memory` uses the same format as [`SystemRequirements`](crate::config::SystemRequirements),
+    /// e.g. `"512M"` or `"1G"`.
+    ///
+    /// # Errors
+    ///
+    /// * `memory` can't be parsed, see [`Error::InvalidResourceLimit`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "redis")
+    ///     .memory("512M")?
+    ///     // No swap on top of the memory limit
+    ///     .memory_swap("512M")?;
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn memory(mut self, memory: impl Into<String>) -> Result<Self, Error> {
+        self.options.memory = Some(parse_memory_limit(&memory.into())?);
+        Ok(self)
+    }
+
+    /// Set the total memory plus swap limit (equivalent to `--memory-swap`)
+    ///
+    /// `swap` is either a memory string (see [`Container::memory`]), or `"-1"` for unlimited swap.
+    ///
+    /// NOTE: A limit below [`Container::memory`] is rejected by [`Container::validate`] and
+    ///       [`Container::create`].
+    ///
+    /// # Errors
+    ///
+    /// * `swap` can't be parsed, see [`Error::InvalidResourceLimit`]
+    pub fn memory_swap(mut self, swap: impl Into<String>) -> Result<Self, Error> {
+        self.options.memory_swap = Some(parse_memory_swap(&swap.into())?);
+        Ok(self)
+    }
+
+    /// Set the size of `/dev/shm` (equivalent to `--shm-size`)
+    ///
+    /// Docker defaults to 64MB, which is too small for headless browsers and some databases.
+    /// `size` uses the same format as [`Container::memory`].
+    ///
+    /// # Errors
+    ///
+    /// * `size` can't be parsed, see [`Error::InvalidResourceLimit`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "browserless/chrome").shm_size("1G")?;
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn shm_size(mut self, size: impl Into<String>) -> Result<Self, Error> {
+        self.options.shm_size = Some(parse_memory_limit(&size.into())?);
+        Ok(self)
+    }
+
+    /// Limit how many CPUs the container may use (equivalent to `--cpus`)
+    ///
+    /// Fractional values are allowed, e.g. `1.5` allows one and a half CPUs worth of time.
+    ///
+    /// NOTE: Negative values are rejected by [`Container::validate`] and [`Container::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     .cpus(1.5)
+    ///     // Half the default weight when competing with other containers
+    ///     .cpu_shares(512);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn cpus(mut self, cpus: f64) -> Self {
+        self.options.nano_cpus = Some((cpus * 1e9).round() as i64);
+        self
+    }
+
+    /// Set the relative CPU weight (equivalent to `--cpu-shares`)
+    ///
+    /// The default weight is 1024. This only matters when containers compete for CPU time.
+    #[must_use]
+    pub fn cpu_shares(mut self, shares: i64) -> Self {
+        self.options.cpu_shares = Some(shares);
+        self
+    }
+
+    /// Apply the resource limits from `requirements`
+    ///
+    /// The memory limit, swap, and reservation, CPU shares, cpuset, and CPU limit are taken
+    /// from [`SystemRequirements::to_host_config`]. Limits that `requirements` leaves unset keep
+    /// their current value, so this can be combined with [`Container::memory`],
+    /// [`Container::cpus`], etc.
+    ///
+    /// NOTE: As with [`SystemRequirements::to_host_config`], memory strings that can't be parsed
+    ///       are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::config::SystemRequirements;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let requirements: SystemRequirements =
+    ///     serde_yaml::from_str(&std::fs::read_to_string("requirements.yml")?)?;
+    /// requirements.check()?;
+    ///
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").apply_requirements(&requirements);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn apply_requirements(mut self, requirements: &SystemRequirements) -> Self {
+        let limits = requirements.to_host_config();
+        let options = &mut self.options;
+        options.memory = limits.memory.or(options.memory);
+        options.memory_swap = limits.memory_swap.or(options.memory_swap);
+        options.memory_reservation = limits.memory_reservation.or(options.memory_reservation);
+        options.cpu_shares = limits.cpu_shares.or(options.cpu_shares);
+        options.cpuset_cpus = limits.cpuset_cpus.or(options.cpuset_cpus.take());
+        options.nano_cpus = limits.nano_cpus.or(options.nano_cpus);
+        self
+    }
+
+    /// Give the container access to GPUs (equivalent to `--gpus`)
+    ///
+    /// This requires a GPU runtime on the host, such as the NVIDIA Container Toolkit.
+    ///
+    /// NOTE: This will override any previous GPU request
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, GpuRequest};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "pytorch/pytorch")
+    ///     // Equivalent to `--gpus all`
+    ///     .gpus(GpuRequest::all());
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn gpus(mut self, request: GpuRequest) -> Self {
+        self.options.device_requests = Some(vec![request.into_device_request()]);
+        self
+    }
+
+    /// Set the relative block IO weight of a single device (equivalent to `--blkio-weight-device`)
+    ///
+    /// `device` is the path of a block device on the host, e.g. `/dev/sda`, and `weight` ranges
+    /// from 10 to 1000. This may be called multiple times to weight several devices.
+    ///
+    /// NOTE: Invalid devices and weights are rejected by [`Container::validate`] and
+    ///       [`Container::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     // Favor the data disk over the OS disk
+    ///     .blkio_device_weight("/dev/sdb", 900)
+    ///     .blkio_device_weight("/dev/sda", 100);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn blkio_device_weight(mut self, device: impl Into<String>, weight: u16) -> Self {
+        self.options
+            .blkio_weight_device
+            .get_or_insert_with(Vec::new)
+            .push(ResourcesBlkioWeightDevice {
+                path: Some(device.into()),
+                weight: Some(usize::from(weight)),
+            });
+        self
+    }
+
+    /// Set the host devices exposed to the container (equivalent to `--device`)
+    ///
+    /// NOTE: This will override any previously set devices, including those added with
+    ///       [`Container::device_str`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, DeviceMapping};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .devices([DeviceMapping::new("/dev/fuse"), DeviceMapping::new("/dev/net/tun")]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn devices(mut self, devices: impl IntoIterator<Item = DeviceMapping>) -> Self {
+        self.options.devices = Some(devices.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Expose a host device given in the `--device` shorthand, `host[:container][:permissions]`
+    ///
+    /// The container path defaults to the host path, and the permissions to `rwm`. See
+    /// [`DeviceMapping`].
+    ///
+    /// NOTE: The device is added to any previously set devices. Since [`Container::devices`]
+    ///       replaces all devices, it should be called *before* this if both are used.
+    ///
+    /// # Errors
+    ///
+    /// The device string is malformed, see [`Error::InvalidConfig`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .device_str("/dev/fuse")?
+    ///     .device_str("/dev/sdc:/dev/xvdc:r")?;
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn device_str(mut self, device: &str) -> Result<Self, Error> {
+        let mapping = device.parse::<DeviceMapping>()?;
+        self.options
+            .devices
+            .get_or_insert_with(Vec::new)
+            .push(mapping.into());
+        Ok(self)
+    }
+
+    /// Add Linux capabilities to the container (equivalent to `--cap-add`)
+    ///
+    /// Capabilities may be given with or without the `CAP_` prefix, in any case, e.g.
+    /// `"net_admin"` or `"CAP_NET_ADMIN"`. `"ALL"` adds every capability.
+    ///
+    /// NOTE: This will override any previously added capabilities. Capabilities in a
+    ///       [`Container::config_override`] are added to these, rather than replacing them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     // Drop everything, and only add back what's needed to manage interfaces
+    ///     .cap_drop(["ALL"])
+    ///     .cap_add(["NET_ADMIN", "NET_RAW"]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn cap_add(mut self, caps: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.options.cap_add = Some(normalize_capabilities(caps));
+        self
+    }
+
+    /// Drop Linux capabilities from the container (equivalent to `--cap-drop`)
+    ///
+    /// Capabilities are given as in [`Container::cap_add`]. `"ALL"` drops every capability.
+    ///
+    /// NOTE: This will override any previously dropped capabilities. Capabilities in a
+    ///       [`Container::config_override`] are added to these, rather than replacing them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "nginx")
+    ///     .cap_drop(["cap_sys_chroot", "mknod"]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn cap_drop(mut self, caps: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.options.cap_drop = Some(normalize_capabilities(caps));
+        self
+    }
+
+    /// Set a resource limit for the container's processes (equivalent to `--ulimit name=soft:hard`)
+    ///
+    /// `name` is the limit without the `RLIMIT_` prefix, e.g. `nofile` or `nproc`. This may be
+    /// called multiple times to set several limits, setting the same limit again replaces it.
+    ///
+    /// NOTE: A soft limit above the hard limit is rejected by [`Container::validate`] and
+    ///       [`Container::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     .ulimit("nofile", 65536, 65536)
+    ///     .ulimit("nproc", 4096, 8192);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn ulimit(mut self, name: impl Into<String>, soft: i64, hard: i64) -> Self {
+        let name = name.into();
+        let ulimits = self.options.ulimits.get_or_insert_with(Vec::new);
+        ulimits.retain(|ulimit| ulimit.name.as_ref() != Some(&name));
+        ulimits.push(ResourcesUlimits {
+            name: Some(name),
+            soft: Some(soft),
+            hard: Some(hard),
+        });
+        self
+    }
+
+    /// Set a namespaced kernel parameter (equivalent to `--sysctl key=value`)
+    ///
+    /// This may be called multiple times to set several parameters, setting the same key again
+    /// replaces its value.
+    ///
+    /// Docker only permits sysctls that are namespaced, so they only affect the container:
+    ///
+    /// * IPC: `kernel.msgmax`, `kernel.msgmnb`, `kernel.msgmni`, `kernel.sem`, `kernel.shmall`,
+    ///   `kernel.shmmax`, `kernel.shmmni`, `kernel.shm_rmid_forced` and `fs.mqueue.*`. These
+    ///   can't be set with `--ipc=host`.
+    /// * Network: `net.*`. These can't be set when using the host's network.
+    ///
+    /// Anything else is rejected by the daemon when the container is created.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .sysctl("net.ipv4.ip_forward", "1")
+    ///     .sysctl("net.core.somaxconn", "1024");
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn sysctl(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options
+            .sysctls
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the logging driver for the container (equivalent to `--log-driver`)
+    ///
+    /// For example, `json-file` (Docker's default), `local`, `journald` or `syslog`. If unset,
+    /// the daemon's default driver is used. See [`Container::log_options`] to configure it.
+    ///
+    /// NOTE: Not every driver supports reading logs back, so [`Container::logs`] may not work
+    ///       with drivers such as `syslog`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "nginx").log_driver("journald");
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn log_driver(mut self, driver: impl Into<String>) -> Self {
+        self.options
+            .log_config
+            .get_or_insert_with(HostConfigLogConfig::default)
+            .typ = Some(driver.into());
+        self
+    }
+
+    /// Set the options of the logging driver (equivalent to `--log-opt`)
+    ///
+    /// The available options depend on the driver, see [`Container::log_driver`]. For
+    /// `json-file` and `local`, `max-size` and `max-file` cap the disk space used by logs.
+    ///
+    /// NOTE: This will override any existing log options
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "nginx")
+    ///     .log_driver("json-file")
+    ///     // Keep at most 3 files of 10 MB each
+    ///     .log_options([("max-size", "10m"), ("max-file", "3")]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn log_options(
+        mut self,
+        options: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.options
+            .log_config
+            .get_or_insert_with(HostConfigLogConfig::default)
+            .config = Some(
+            options
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Set the container's healthcheck (equivalent to the `--health-*` flags)
+    ///
+    /// `test` is in the same form as a Dockerfile `HEALTHCHECK`, e.g.
+    /// `["CMD-SHELL", "curl -f http://localhost/ || exit 1"]`. The container is considered
+    /// unhealthy after `retries` consecutive failures, though failures during `start_period`
+    /// aren't counted.
+    ///
+    /// See also: [`Container::wait_until_healthy`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres").healthcheck(
+    ///     vec![String::from("CMD"), String::from("pg_isready")],
+    ///     Duration::from_secs(5),
+    ///     Duration::from_secs(3),
+    ///     5,
+    ///     Duration::from_secs(10),
+    /// );
+    ///
+    /// container.start(false).await?;
+    /// container.wait_until_healthy(Duration::from_secs(60)).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn healthcheck(
+        mut self,
+        test: Vec<String>,
+        interval: Duration,
+        timeout: Duration,
+        retries: u64,
+        start_period: Duration,
+    ) -> Self {
+        // Docker expects durations in nanoseconds
+        let nanos = |duration: Duration| i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+
+        self.options.healthcheck = Some(HealthConfig {
+            test: Some(test),
+            interval: Some(nanos(interval)),
+            timeout: Some(nanos(timeout)),
+            retries: Some(i64::try_from(retries).unwrap_or(i64::MAX)),
+            start_period: Some(nanos(start_period)),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Have the daemon remove the container once it exits (equivalent to `--rm`)
+    ///
+    /// This only covers containers that exit on their own, or are stopped. A container that is
+    /// still running when the process using it dies (e.g. a test that panics) is left behind,
+    /// see [`ContainerGuard`] for that.
+    ///
+    /// NOTE: This can't be combined with a [`Container::restart_policy`], which is rejected by
+    ///       [`Container::validate`] and [`Container::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .cmd(["echo", "Hello!"])
+    ///     .auto_remove(true);
+    ///
+    /// // The container is removed as soon as `echo` exits
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn auto_remove(mut self, auto_remove: bool) -> Self {
+        self.options.auto_remove = Some(auto_remove);
+        self
+    }
+
+    /// Run the container in privileged mode (equivalent to `--privileged`)
+    ///
+    /// A privileged container has every capability and access to all of the host's devices.
+    /// Prefer [`Container::cap_add`] and [`Container::devices`] where possible.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "docker:dind")
+    ///     // Docker-in-Docker needs full access to the host
+    ///     .privileged(true);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn privileged(mut self, privileged: bool) -> Self {
+        self.options.privileged = Some(privileged);
+        self
+    }
+
+    /// Set the IPC namespace (equivalent to `--ipc`)
+    ///
+    /// `mode` is passed to Docker as is, e.g. `"host"`, `"shareable"`, or `"container:<name|id>"`
+    /// to share another container's shared memory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "myorg/shm-reader")
+    ///     .ipc_mode("container:shm-writer");
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn ipc_mode(mut self, mode: impl Into<String>) -> Self {
+        self.options.ipc_mode = Some(mode.into());
+        self
+    }
+
+    /// Set the PID namespace (equivalent to `--pid`)
+    ///
+    /// `mode` is passed to Docker as is, e.g. `"host"` or `"container:<name|id>"` to see another
+    /// container's processes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// // A debugging sidecar that can trace the processes of `app`
+    /// let mut container = Container::new(connection.client(), "nicolaka/netshoot")
+    ///     .pid_mode("container:app")
+    ///     .cap_add(["SYS_PTRACE"]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn pid_mode(mut self, mode: impl Into<String>) -> Self {
+        self.options.pid_mode = Some(mode.into());
+        self
+    }
+
+    /// Make the root filesystem read-only (equivalent to `--read-only`)
+    ///
+    /// See [`Container::read_only_with_writable`] to keep some paths writable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .cmd(["echo", "Hello!"])
+    ///     .read_only_rootfs(true);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn read_only_rootfs(mut self, read_only: bool) -> Self {
+        self.options.readonly_rootfs = Some(read_only);
+        self
+    }
+
+    /// Make the root filesystem read-only (equivalent to `--read-only`), keeping `paths` writable
+    ///
+    /// Most images need a few writable paths (e.g. `/tmp` or `/run`) to work, so each of `paths`
+    /// has a tmpfs mounted over it with [`DEFAULT_TMPFS_OPTIONS`] (64 MiB, no setuid or devices).
+    /// Use [`Container::tmpfs`] afterwards to change the options of any of them.
+    ///
+    /// NOTE: Anything written to these paths is lost when the container stops.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "nginx")
+    ///     .read_only_with_writable(&["/tmp", "/var/cache/nginx", "/run"])
+    ///     // nginx caches more than the default allows
+    ///     .tmpfs([("/var/cache/nginx", "rw,size=256m")]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn read_only_with_writable(mut self, paths: &[&str]) -> Self {
+        self.options.readonly_rootfs = Some(true);
+        self.tmpfs(paths.iter().map(|path| (*path, DEFAULT_TMPFS_OPTIONS)))
+    }
+
+    /// Mount tmpfs filesystems, as `(path, options)` pairs (equivalent to `--tmpfs path:options`)
+    ///
+    /// `options` are standard tmpfs mount options, such as `rw,size=64m`. Empty `options` use the
+    /// daemon's defaults (equivalent to `--tmpfs path`). This replaces any tmpfs previously set
+    /// for the same path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .tmpfs([("/scratch", "rw,size=1g"), ("/run", "")]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn tmpfs(
+        mut self,
+        mounts: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        let tmpfs = self.options.tmpfs.get_or_insert_with(HashMap::new);
+        for (path, options) in mounts {
+            tmpfs.insert(path.into(), options.into());
+        }
+        self
+    }
+
+    /// Set the container's restart policy (equivalent to `--restart`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::models::{RestartPolicy, RestartPolicyNameEnum};
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     // Always restart the container, unless stopped manually
+    ///     .restart_policy(RestartPolicy {
+    ///         name: Some(RestartPolicyNameEnum::UNLESS_STOPPED),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.options.restart_policy = Some(restart_policy);
+        self
+    }
+
+    /// Set the container's labels (equivalent to `--label`)
+    ///
+    /// Labels are key-value metadata, useful for filtering containers later on.
+    ///
+    /// NOTE: This will override any existing labels. A [`Container::config_override`] that
+    ///       explicitly sets `labels` will take precedence over these.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     .labels([("com.myorg.stack", "foo"), ("com.myorg.tier", "backend")]);
+    ///
+    /// // We can now start our container
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn labels(
+        mut self,
+        labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.options.labels = Some(
+            labels
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Add labels read from a file
+    ///
+    /// Files ending in `.yml` or `.yaml` are read as a YAML map of labels. Anything else is read
+    /// as one `key=value` pair per line, where blank lines and lines starting with `#` are
+    /// ignored, and a value may be wrapped in single or double quotes.
+    ///
+    /// NOTE: The labels are merged into any existing labels, replacing those with the same keys.
+    ///       Since [`Container::labels`] replaces all labels, it should be called *before* this
+    ///       if both are used.
+    ///
+    /// # Errors
+    ///
+    /// * The file can't be read
+    /// * The file is malformed, see [`Error::InvalidConfig`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// // traefik.labels:
+    /// //   traefik.enable=true
+    /// //   traefik.http.routers.web.rule="Host(`example.com`)"
+    /// let mut container = Container::new(connection.client(), "nginx")
+    ///     .labels([("com.myorg.stack", "foo")])
+    ///     .labels_from_file("traefik.labels")?;
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn labels_from_file(mut self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+
+        let yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml");
+        let labels = if yaml {
+            parse_yaml_labels(&content)?
+        } else {
+            parse_labels(&content)?
+        };
+
+        self.options
+            .labels
+            .get_or_insert_with(HashMap::new)
+            .extend(labels);
+        Ok(self)
+    }
+
+    /// Set the signal sent to the container's main process to stop it (equivalent to
+    /// `--stop-signal`)
+    ///
+    /// `signal` may be a name (`"SIGINT"`) or a number (`"2"`). If unset, the image's
+    /// `STOPSIGNAL` (or `SIGTERM`) is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     // Fast shutdown, rather than waiting for clients to disconnect
+    ///     .stop_signal("SIGINT");
+    ///
+    /// container.start(false).await?;
+    /// container.stop().await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn stop_signal(mut self, signal: impl Into<String>) -> Self {
+        self.options.stop_signal = Some(signal.into());
+        self
+    }
+
+    /// Set how long the container has to exit after the stop signal before it's killed
+    /// (equivalent to `--stop-timeout`)
+    ///
+    /// This is used by [`Container::stop`], as well as when the daemon stops the container. If
+    /// unset, Docker's default of 10 seconds is used. Docker only supports whole seconds, so this
+    /// is rounded up.
+    ///
+    /// NOTE: The request is still subject to the client's timeout. For timeouts approaching it,
+    ///       see [`Container::with_operation_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     .stop_timeout(Duration::from_secs(60));
+    ///
+    /// container.start(false).await?;
+    ///
+    /// // Postgres gets up to a minute to checkpoint before it's killed
+    /// container.stop().await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn stop_timeout(mut self, timeout: Duration) -> Self {
+        self.options.stop_timeout = Some(timeout);
+        self
+    }
+
+    /// Gracefully stop the container before a forced [`Container::remove`]
+    ///
+    /// By default, removing a running container with `force` kills it immediately. With this
+    /// set, the container is first stopped using its configured stop signal and timeout (see
+    /// [`Container::stop_signal`] and [`Container::stop_timeout`]), giving stateful processes a
+    /// chance to flush before removal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     .stop_timeout(Duration::from_secs(30))
+    ///     .graceful_force(true);
+    ///
+    /// container.start(false).await?;
+    ///
+    /// // Postgres gets up to 30 seconds to shut down cleanly before it's removed
+    /// let remove_container_options = bollard::container::RemoveContainerOptions {
+    ///     force: true,
+    ///     ..Default::default()
+    /// };
+    /// container.remove(Some(remove_container_options)).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn graceful_force(mut self, graceful: bool) -> Self {
+        self.options.graceful_force = graceful;
+        self
+    }
+
+    /// Pull the image when creating the container, if it isn't present locally
+    ///
+    /// By default, creating a container from a missing image fails. For progress reporting or
+    /// private registries, pull the image ahead of time with
+    /// [`DockerBuilder::pull_image_with_progress`](crate::DockerBuilder::pull_image_with_progress).
+    ///
+    /// This is shorthand for [`Container::pull_policy`] with [`PullPolicy::Missing`] (or
+    /// [`PullPolicy::Never`] if `pull` is `false`), without progress logging.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "hello-world").pull_on_missing(true);
+    ///
+    /// // `hello-world` is pulled first if needed
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn pull_on_missing(mut self, pull: bool) -> Self {
+        self.options.pull_policy = if pull {
+            PullPolicy::Missing
+        } else {
+            PullPolicy::Never
+        };
+        self.options.log_pull_progress = false;
+        self
+    }
+
+    /// Set when the image is pulled (equivalent to `--pull`)
+    ///
+    /// The image is pulled, if needed, whenever the container is created. This includes
+    /// [`Container::start`] and [`Container::run_and_capture`] on a container that hasn't been
+    /// created yet, so with [`PullPolicy::Always`], each fresh run uses the latest image for the
+    /// tag.
+    ///
+    /// If `log_progress` is `true`, each pull progress event is logged at the `info` level,
+    /// otherwise progress is silently discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, PullPolicy};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine:latest")
+    ///     .cmd(["cat", "/etc/alpine-release"])
+    ///     .pull_policy(PullPolicy::Always, true);
+    ///
+    /// // `alpine:latest` is re-pulled first, even if it's cached
+    /// let output = container.run_and_capture(None).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn pull_policy(mut self, policy: PullPolicy, log_progress: bool) -> Self {
+        self.options.pull_policy = policy;
+        self.options.log_pull_progress = log_progress;
+        self
+    }
+
+    /// Set how [`Container::wait`] treats non-zero exit codes
+    ///
+    /// By default, a non-zero exit is reported as an error. Use [`WaitBehavior::ReturnExitCode`]
+    /// to get the exit code back regardless.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, WaitBehavior};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .cmd(["sh", "-c", "exit 3"])
+    ///     .wait_behavior(WaitBehavior::ReturnExitCode);
+    ///
+    /// container.start(false).await?;
+    /// assert_eq!(container.wait().await?, 3);
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn wait_behavior(mut self, behavior: WaitBehavior) -> Self {
+        self.options.wait_behavior = behavior;
+        self
+    }
+
+    /// Attach the container to a network when it's created (equivalent to `--network`)
+    ///
+    /// This is the container's primary network. To attach it to more networks, see
+    /// [`Container::connect`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust").network("backend");
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn network(mut self, name: impl Into<String>) -> Self {
+        self.options.network = Some(name.into());
+        self
+    }
+
+    /// Set DNS aliases for the container on its primary network (equivalent to `--network-alias`)
+    ///
+    /// Other containers on the network can reach this one by any of these names. This requires
+    /// a user-defined network, see [`Container::network`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres")
+    ///     .network("backend")
+    ///     .network_aliases(["db", "postgres"]);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn network_aliases(mut self, aliases: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.network_aliases = Some(aliases.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Request a static IPv4 address on `network`
+    ///
+    /// If `network` is the container's primary network (see [`Container::network`]), the address
+    /// is assigned at [`Container::create`]. Otherwise, it's assigned when the container is
+    /// attached with [`Container::connect`].
+    ///
+    /// The network must be user-defined, and if it has a known subnet, the address must fall
+    /// within it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .network("my-network")
+    ///     .with_ipv4("my-network", Ipv4Addr::new(172, 28, 5, 10));
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn with_ipv4(mut self, network: impl Into<String>, address: Ipv4Addr) -> Self {
+        self.options
+            .static_ips
+            .entry(network.into())
+            .or_default()
+            .ipv4 = Some(address);
+        self
+    }
+
+    /// Request a static IPv6 address on `network`
+    ///
+    /// See [`Container::with_ipv4`] for when the address is assigned. The network must have IPv6
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .with_ipv6("my-v6-network", "fd00:dead:beef::10".parse::<Ipv6Addr>().unwrap());
+    ///
+    /// container.create().await?;
+    /// container.connect("my-v6-network").await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn with_ipv6(mut self, network: impl Into<String>, address: Ipv6Addr) -> Self {
+        self.options
+            .static_ips
+            .entry(network.into())
+            .or_default()
+            .ipv6 = Some(address);
+        self
+    }
+
+    /// Share inspect results between calls made within `ttl` of each other
+    ///
+    /// By default, every call to [`Container::status`], [`Container::health_status`],
+    /// [`Container::health_log`] and [`Container::image_digest`] asks the daemon for the
+    /// container's state. A supervisor polling several of these per tick can instead set a short
+    /// `ttl` (e.g. 100ms), so they all share a single inspect.
+    ///
+    /// The tradeoff is staleness: a result may be up to `ttl` old, so a change made outside of
+    /// this `Container` (e.g. the process exiting, or `docker stop`) can go unnoticed for that
+    /// long. The cache is cleared by methods that change the container's state through this
+    /// handle (e.g. [`Container::stop`]), and can be cleared manually with
+    /// [`Container::clear_inspect_cache`] when a fresh result is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::from_id(connection.client(), "my-service")
+    ///     .await?
+    ///     .inspect_cache_ttl(Duration::from_millis(100));
+    ///
+    /// // Both of these are answered by one inspect
+    /// let status = container.status().await?;
+    /// let health = container.health_status().await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn inspect_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.options.inspect_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Retry idempotent operations that fail with a transient daemon error
+    ///
+    /// Under load, the daemon can reset connections or time out. With a policy set,
+    /// [`Container::status`], [`Container::exists`], [`Container::stop`], and anything that
+    /// inspects the container (e.g. [`Container::health_status`]) are retried on these errors.
+    /// Errors reported by the daemon itself (e.g. `404`) are never retried.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::from_id(connection.client(), "my-service")
+    ///     .await?
+    ///     .retry_policy(RetryPolicy::new(3, Duration::from_millis(100)));
+    ///
+    /// let status = container.status().await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.options.retry_policy = Some(policy);
+        self
+    }
+
+    /// Apply a configuration override
+    ///
+    /// This allows merging specific `bollard::container::Config` options
+    /// with the base configuration generated by `docktopus`.
+    ///
+    /// Fields set in the provided `config` will overwrite the defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use docktopus::DockerBuilder;
+    /// # use docktopus::container::Container;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// // Configure bollard options for stdin
+    /// let bollard_config_override = docktopus::bollard::container::Config {
+    ///    attach_stdin: Some(true),
+    ///    open_stdin: Some(true),
+    ///    ..Default::default()
+    /// };
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     .cmd(["cat"]) // Example command that reads stdin
+    ///     .config_override(bollard_config_override); // Apply stdin config
+    ///
+    /// // Now the container will be created with stdin attached and open
+    /// container.create().await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn config_override(mut self, config: Config<String>) -> Self {
+        self.options.config_override = Some(config);
+        self
+    }
+
+    /// Get the container ID if it has been created
+    ///
+    /// This will only have a value if [`Container::create`] or [`Container::start`] has been
+    /// called prior.
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Get the client this container was created with
+    ///
+    /// This is an escape hatch for calling Docker APIs that aren't wrapped here.
+    #[must_use]
+    pub fn client(&self) -> Arc<Docker> {
+        self.client.clone()
+    }
+
+    /// Get the container name if it has been created
+    ///
+    /// This will only have a value if [`Container::create`] or [`Container::start`] has been
+    /// called prior.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Check the container's configuration for combinations that Docker would reject
+    ///
+    /// This is called automatically by [`Container::create`], but can be used to fail fast
+    /// before any daemon interaction.
+    ///
+    /// # Errors
+    ///
+    /// * The image is empty
+    /// * A restart policy is combined with `auto_remove` (`--restart` with `--rm`)
+    /// * The memory swappiness is outside of 0-100
+    /// * The memory swap limit is below the memory limit
+    /// * The CPU limit is negative
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::models::{HostConfig, RestartPolicy, RestartPolicyNameEnum};
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::new(connection.client(), "rustlang/rust")
+    ///     .restart_policy(RestartPolicy {
+    ///         name: Some(RestartPolicyNameEnum::ALWAYS),
+    ///         ..Default::default()
+    ///     })
+    ///     .config_override(docktopus::bollard::container::Config {
+    ///         host_config: Some(HostConfig {
+    ///             auto_remove: Some(true),
+    ///             ..Default::default()
+    ///         }),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // A container can't be both restarted and removed when it exits
+    /// assert!(container.validate().is_err());
+    /// # Ok(()) }
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_config(&self.container_config())
+    }
+
+    /// Attempt to create the container
+    ///
+    /// This will take the following into account:
+    ///
+    /// * [`Container::env`]
+    /// * [`Container::cmd`]
+    /// * [`Container::binds`]
+    /// * [`Container::name`]
+    /// * [`Container::labels`]
+    ///
+    /// Be sure to set these before calling this!
+    ///
+    /// # Errors
+    ///
+    /// * The configuration is invalid, see [`Container::validate`]
+    /// * The image isn't available locally (or can't be pulled, see [`Container::pull_policy`]),
+    ///   see [`Error::ImageNotFound`]
+    /// * A [`Container::add_host_resolved`] target can't be resolved, see
+    ///   [`Error::HostResolution`]
+    /// * Docker fails to create the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     .env(["FOO=BAR", "BAZ=QUX"])
+    ///     .cmd(["echo", "Hello!"])
+    ///     .binds(["./host-data:/container-data"]);
+    ///
+    /// // The container is created using the above settings
+    /// container.create().await?;
+    ///
+    /// // Now it can be started
+    /// container.start(true).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip_all)]
+    pub async fn create(&mut self) -> Result<(), Error> {
+        self.create_with(true).await
+    }
+
+    /// Pull the image (per the [`PullPolicy`]) and create the container, reporting progress
+    ///
+    /// This is [`Container::create`] as a stream, for tools that want to show pull progress. The
+    /// stream yields any [`ProvisionEvent::Pull`] events, then ends with either
+    /// [`ProvisionEvent::Created`] or [`ProvisionEvent::Failed`]. Once it's done,
+    /// [`Container::id`] returns the created container's ID.
+    ///
+    /// NOTE: The container is not started, see [`Container::start`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, ProvisionEvent, PullPolicy};
+    /// use futures_util::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres:16")
+    ///     .pull_policy(PullPolicy::Missing, false);
+    ///
+    /// let mut events = container.provision();
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         ProvisionEvent::Pull(progress) => println!("{}", progress.status),
+    ///         ProvisionEvent::Created(id) => println!("Created {id}"),
+    ///         ProvisionEvent::Failed(e) => return Err(e),
+    ///     }
+    /// }
+    /// drop(events);
+    ///
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn provision(&mut self) -> impl Stream<Item = ProvisionEvent> + Send + Unpin + '_ {
+        enum State {
+            Start,
+            Pulling(Pin<Box<dyn Stream<Item = Result<PullProgress, Error>> + Send>>),
+            Creating,
+            Done,
+        }
+
+        Box::pin(futures_util::stream::unfold(
+            (self, State::Start),
+            |(container, mut state)| async move {
+                loop {
+                    match state {
+                        State::Start => {
+                            // Fail fast, before a potentially long pull
+                            let config = container.container_config();
+                            if let Err(e) = validate_config(&config) {
+                                return Some((ProvisionEvent::Failed(e), (container, State::Done)));
+                            }
+
+                            let image = config.image.unwrap_or_default();
+                            state = match container.needs_pull(&image).await {
+                                Ok(true) => State::Pulling(Box::pin(container.pull_stream(&image))),
+                                Ok(false) => State::Creating,
+                                Err(e) => {
+                                    return Some((
+                                        ProvisionEvent::Failed(e),
+                                        (container, State::Done),
+                                    ));
+                                }
+                            };
+                        }
+                        State::Pulling(mut pull) => match pull.next().await {
+                            Some(Ok(progress)) => {
+                                return Some((
+                                    ProvisionEvent::Pull(progress),
+                                    (container, State::Pulling(pull)),
+                                ));
+                            }
+                            Some(Err(e)) => {
+                                return Some((ProvisionEvent::Failed(e), (container, State::Done)));
+                            }
+                            None => state = State::Creating,
+                        },
+                        State::Creating => {
+                            let event = match container.create_with(false).await {
+                                Ok(()) => ProvisionEvent::Created(
+                                    container.id.clone().unwrap_or_default(),
+                                ),
+                                Err(e) => ProvisionEvent::Failed(e),
+                            };
+                            return Some((event, (container, State::Done)));
+                        }
+                        State::Done => return None,
+                    }
+                }
+            },
+        ))
+    }
+
+    /// [`Container::create`], only pulling the image (per the [`PullPolicy`]) if `pull` is set
+    async fn create_with(&mut self, pull: bool) -> Result<(), Error> {
+        log::debug!("Creating container");
+
+        let mut config = self.container_config();
+        validate_config(&config)?;
+        for conflict in self.override_conflicts() {
+            log::warn!("{conflict}");
+        }
+
+        if let Some(network) = config
+            .host_config
+            .as_ref()
+            .and_then(|host_config| host_config.network_mode.as_deref())
+        {
+            self.check_static_ips(network).await?;
+        }
+
+        if let Some(image) = config.image.as_deref().filter(|_| pull) {
+            if self.needs_pull(image).await? {
+                self.pull(image).await?;
+            }
+        }
+
+        if !self.options.resolved_hosts.is_empty() {
+            let host_config = config.host_config.get_or_insert_with(HostConfig::default);
+            let extra_hosts = host_config.extra_hosts.get_or_insert_with(Vec::new);
+            for (hostname, target, family) in &self.options.resolved_hosts {
+                let addr = resolve_host(target, *family).await?;
+                log::debug!("Resolved `{target}` to `{addr}` for `{hostname}`");
+                extra_hosts.push(format!("{hostname}:{addr}"));
+            }
+        }
+
+        let opts = self
+            .options
+            .name
+            .as_ref()
+            .map(|name| CreateContainerOptions {
+                name: name.clone(),
+                ..Default::default()
+            });
+        let image = config.image.clone().unwrap_or_default();
+        let ContainerCreateResponse { id, warnings } = self
+            .client
+            .create_container(opts, config)
+            .await
+            .map_err(|e| {
+                if is_missing_image(&e) {
+                    Error::ImageNotFound(image)
+                } else {
+                    Error::Bollard(e)
+                }
+            })?;
+        for warning in warnings {
+            log::warn!("{}", warning);
+        }
+
+        self.id = Some(id);
+        Ok(())
+    }
+
+    /// Discard any cached inspect result, see [`Container::inspect_cache_ttl`]
+    ///
+    /// The next call that needs the container's state will ask the daemon.
+    pub async fn clear_inspect_cache(&self) {
+        *self.inspect_cache.lock().await = None;
+    }
+
+    fn invalidate_cached_inspect(&mut self) {
+        *self.inspect_cache.get_mut() = None;
+    }
+
+    /// Inspect the container, reusing a result younger than [`Container::inspect_cache_ttl`]
+    async fn inspect(&self, id: &str) -> Result<ContainerInspectResponse, Error> {
+        let inspect = || async {
+            self.retrying(|| {
+                self.client
+                    .inspect_container(id, None::<InspectContainerOptions>)
+            })
+            .await
+        };
+
+        let Some(ttl) = self.options.inspect_cache_ttl else {
+            return Ok(inspect().await?);
+        };
+
+        // Held across the request, so concurrent callers wait for (and share) one inspect
+        let mut cache = self.inspect_cache.lock().await;
+        if let Some((at, inspect)) = &*cache {
+            if at.elapsed() < ttl {
+                return Ok(inspect.clone());
+            }
+        }
+
+        let inspect = inspect().await?;
+        *cache = Some((Instant::now(), inspect.clone()));
+        Ok(inspect)
+    }
+
+    /// Run `op`, retrying transient errors per the [`Container::retry_policy`]
+    async fn retrying<T, F, Fut>(&self, mut op: F) -> Result<T, bollard::errors::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, bollard::errors::Error>>,
+    {
+        let Some(policy) = self.options.retry_policy else {
+            return op().await;
+        };
+
+        let mut delay = policy.backoff;
+        let mut attempt = 1;
+        loop {
+            match op().await {
+                Err(e) if attempt < policy.attempts && is_transient(&e) => {
+                    log::debug!(
+                        "Transient Docker error (attempt {attempt}/{}): {e}",
+                        policy.attempts
+                    );
+                    tokio::time::sleep(crate::builder::jitter(delay)).await;
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Whether `image` should be pulled before creating the container, per the [`PullPolicy`]
+    async fn needs_pull(&self, image: &str) -> Result<bool, Error> {
+        match self.options.pull_policy {
+            PullPolicy::Never => Ok(false),
+            PullPolicy::Always => Ok(true),
+            PullPolicy::Missing => match self.client.inspect_image(image).await {
+                Ok(_) => Ok(false),
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => {
+                    log::info!("Image `{image}` not found locally, pulling");
+                    Ok(true)
+                }
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+
+    /// Pull `image`, yielding its progress
+    fn pull_stream(
+        &self,
+        image: &str,
+    ) -> impl Stream<Item = Result<PullProgress, Error>> + Send + use<> {
+        let image = image.to_string();
+        self.client
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: image.clone(),
+                    ..Default::default()
+                }),
+                None,
+                None,
+            )
+            .map_ok(PullProgress::from)
+            .map_err(move |e| match e {
+                bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                } => Error::ImageNotFound(image.clone()),
+                e => Error::Bollard(e),
+            })
+    }
+
+    /// Pull `image`, logging progress if requested
+    async fn pull(&self, image: &str) -> Result<(), Error> {
+        let log_progress = self.options.log_pull_progress;
+        self.pull_stream(image)
+            .try_for_each(|progress| async move {
+                if log_progress {
+                    match progress.percentage {
+                        Some(percentage) => log::info!(
+                            "{}: {} ({percentage:.0}%)",
+                            progress.id.as_deref().unwrap_or(image),
+                            progress.status
+                        ),
+                        None => log::info!(
+                            "{}: {}",
+                            progress.id.as_deref().unwrap_or(image),
+                            progress.status
+                        ),
+                    }
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    /// Build the creation config from the options set on this container
+    // Bollard represents exposed ports as a map to empty values
+    #[allow(clippy::zero_sized_map_values)]
+    pub(crate) fn container_config(&self) -> Config<String> {
+        let mut config = Config {
+            image: Some(self.image.clone()),
+            cmd: self.options.cmd.clone(),
+            entrypoint: self.options.entrypoint.clone(),
+            working_dir: self.options.working_dir.clone(),
+            stop_signal: self.options.stop_signal.clone(),
+            stop_timeout: self.options.stop_timeout.map(duration_secs),
+            env: self.options.env.clone(),
+            labels: self.options.labels.clone(),
+            healthcheck: self.options.healthcheck.clone(),
+            // Published ports must also be exposed
+            exposed_ports: self.options.port_bindings.as_ref().map(|bindings| {
+                bindings
+                    .keys()
+                    .map(|port| (port.clone(), HashMap::new()))
+                    .collect()
+            }),
+            attach_stdout: Some(true),
+            host_config: Some(HostConfig {
+                binds: self.options.binds.clone(),
+                extra_hosts: self.options.extra_hosts.clone(),
+                port_bindings: self.options.port_bindings.clone(),
+                publish_all_ports: self.options.publish_all_ports,
+                restart_policy: self.options.restart_policy.clone(),
+                runtime: self.options.runtime.clone(),
+                readonly_rootfs: self.options.readonly_rootfs,
+                privileged: self.options.privileged,
+                auto_remove: self.options.auto_remove,
+                tmpfs: self.options.tmpfs.clone(),
+                memory: self.options.memory,
+                memory_swap: self.options.memory_swap,
+                memory_reservation: self.options.memory_reservation,
+                memory_swappiness: self.options.memory_swappiness,
+                shm_size: self.options.shm_size,
+                ipc_mode: self.options.ipc_mode.clone(),
+                pid_mode: self.options.pid_mode.clone(),
+                cpu_shares: self.options.cpu_shares,
+                cpuset_cpus: self.options.cpuset_cpus.clone(),
+                nano_cpus: self.options.nano_cpus,
+                device_requests: self.options.device_requests.clone(),
+                blkio_weight_device: self.options.blkio_weight_device.clone(),
+                devices: self.options.devices.clone(),
+                cap_add: self.options.cap_add.clone(),
+                cap_drop: self.options.cap_drop.clone(),
+                ulimits: self.options.ulimits.clone(),
+                sysctls: self.options.sysctls.clone(),
+                log_config: self.options.log_config.clone(),
+                network_mode: self.options.network.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Apply overrides if present
+        if let Some(override_config) = &self.options.config_override {
+            if let Some(val) = &override_config.hostname {
+                config.hostname = Some(val.clone());
             }
             if let Some(val) = &override_config.domainname {
                 config.domainname = Some(val.clone());
@@ -597,296 +3794,2917 @@ impl Container {
                 // Prefer override env vars entirely if specified
                 config.env = Some(val.clone());
             }
-            if let Some(val) = &override_config.cmd {
-                // Prefer override cmd entirely if specified
-                config.cmd = Some(val.clone());
+            if let Some(val) = &override_config.cmd {
+                // Prefer override cmd entirely if specified
+                config.cmd = Some(val.clone());
+            }
+            if let Some(val) = &override_config.healthcheck {
+                config.healthcheck = Some(val.clone());
+            }
+            if let Some(val) = override_config.args_escaped {
+                config.args_escaped = Some(val);
+            }
+            if let Some(val) = &override_config.image {
+                config.image = Some(val.clone());
+            }
+            if let Some(val) = &override_config.volumes {
+                config.volumes = Some(val.clone());
+            }
+            if let Some(val) = &override_config.working_dir {
+                config.working_dir = Some(val.clone());
+            }
+            if let Some(val) = &override_config.entrypoint {
+                config.entrypoint = Some(val.clone());
+            }
+            if let Some(val) = override_config.network_disabled {
+                config.network_disabled = Some(val);
+            }
+            if let Some(val) = &override_config.mac_address {
+                config.mac_address = Some(val.clone());
+            }
+            if let Some(val) = &override_config.on_build {
+                config.on_build = Some(val.clone());
+            }
+            if let Some(val) = &override_config.labels {
+                // Prefer override labels entirely if specified
+                config.labels = Some(val.clone());
+            }
+            if let Some(val) = &override_config.stop_signal {
+                config.stop_signal = Some(val.clone());
             }
-            if let Some(val) = &override_config.healthcheck {
-                config.healthcheck = Some(val.clone());
+            if let Some(val) = override_config.stop_timeout {
+                config.stop_timeout = Some(val);
             }
-            if let Some(val) = override_config.args_escaped {
-                config.args_escaped = Some(val);
+            if let Some(val) = &override_config.shell {
+                config.shell = Some(val.clone());
             }
-            if let Some(val) = &override_config.image {
-                config.image = Some(val.clone());
+            if let Some(val) = &override_config.networking_config {
+                config.networking_config = Some(val.clone());
             }
-            if let Some(val) = &override_config.volumes {
-                config.volumes = Some(val.clone());
+            // HostConfig needs separate merging
+            if let Some(override_host_config) = &override_config.host_config {
+                let mut host_config = config.host_config.unwrap_or_default();
+                if let Some(val) = &override_host_config.binds {
+                    // Prefer override binds entirely if specified
+                    host_config.binds = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.network_mode {
+                    host_config.network_mode = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.port_bindings {
+                    // Prefer override port_bindings entirely if specified
+                    host_config.port_bindings = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.restart_policy {
+                    host_config.restart_policy = Some(val.clone());
+                }
+                if let Some(val) = override_host_config.auto_remove {
+                    host_config.auto_remove = Some(val);
+                }
+                if let Some(val) = &override_host_config.volume_driver {
+                    host_config.volume_driver = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.volumes_from {
+                    host_config.volumes_from = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.mounts {
+                    host_config.mounts = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.cap_add {
+                    // Capabilities are merged with those set on the builder
+                    merge_capabilities(&mut host_config.cap_add, val);
+                }
+                if let Some(val) = &override_host_config.cap_drop {
+                    merge_capabilities(&mut host_config.cap_drop, val);
+                }
+                if let Some(val) = &override_host_config.cgroupns_mode {
+                    host_config.cgroupns_mode = Some(*val);
+                }
+                if let Some(val) = &override_host_config.dns {
+                    host_config.dns = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.dns_options {
+                    host_config.dns_options = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.dns_search {
+                    host_config.dns_search = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.extra_hosts {
+                    // Prefer override extra_hosts entirely if specified
+                    host_config.extra_hosts = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.group_add {
+                    host_config.group_add = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.ipc_mode {
+                    host_config.ipc_mode = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.cgroup {
+                    host_config.cgroup = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.links {
+                    host_config.links = Some(val.clone());
+                }
+                if let Some(val) = override_host_config.oom_score_adj {
+                    host_config.oom_score_adj = Some(val);
+                }
+                if let Some(val) = &override_host_config.pid_mode {
+                    host_config.pid_mode = Some(val.clone());
+                }
+                if let Some(val) = override_host_config.privileged {
+                    host_config.privileged = Some(val);
+                }
+                if let Some(val) = override_host_config.publish_all_ports {
+                    host_config.publish_all_ports = Some(val);
+                }
+                if let Some(val) = override_host_config.readonly_rootfs {
+                    host_config.readonly_rootfs = Some(val);
+                }
+                if let Some(val) = &override_host_config.security_opt {
+                    host_config.security_opt = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.storage_opt {
+                    host_config.storage_opt = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.tmpfs {
+                    host_config.tmpfs = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.uts_mode {
+                    host_config.uts_mode = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.userns_mode {
+                    host_config.userns_mode = Some(val.clone());
+                }
+                if let Some(val) = override_host_config.shm_size {
+                    host_config.shm_size = Some(val);
+                }
+                if let Some(val) = override_host_config.memory {
+                    host_config.memory = Some(val);
+                }
+                if let Some(val) = override_host_config.memory_swap {
+                    host_config.memory_swap = Some(val);
+                }
+                if let Some(val) = override_host_config.memory_reservation {
+                    host_config.memory_reservation = Some(val);
+                }
+                if let Some(val) = override_host_config.memory_swappiness {
+                    host_config.memory_swappiness = Some(val);
+                }
+                if let Some(val) = override_host_config.cpu_shares {
+                    host_config.cpu_shares = Some(val);
+                }
+                if let Some(val) = &override_host_config.cpuset_cpus {
+                    host_config.cpuset_cpus = Some(val.clone());
+                }
+                if let Some(val) = override_host_config.nano_cpus {
+                    host_config.nano_cpus = Some(val);
+                }
+                if let Some(val) = &override_host_config.device_requests {
+                    host_config.device_requests = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.blkio_weight_device {
+                    host_config.blkio_weight_device = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.devices {
+                    host_config.devices = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.ulimits {
+                    host_config.ulimits = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.sysctls {
+                    host_config.sysctls = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.log_config {
+                    host_config.log_config = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.runtime {
+                    // Prefer override runtime if specified
+                    host_config.runtime = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.console_size {
+                    host_config.console_size = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.isolation {
+                    host_config.isolation = Some(*val);
+                }
+                if let Some(val) = &override_host_config.masked_paths {
+                    host_config.masked_paths = Some(val.clone());
+                }
+                if let Some(val) = &override_host_config.readonly_paths {
+                    host_config.readonly_paths = Some(val.clone());
+                }
+                config.host_config = Some(host_config);
             }
-            if let Some(val) = &override_config.working_dir {
-                config.working_dir = Some(val.clone());
+        }
+
+        // Aliases and static addresses on the primary network have to be assigned at creation
+        let primary_network = config
+            .host_config
+            .as_ref()
+            .and_then(|host_config| host_config.network_mode.clone());
+        if let Some(network) = primary_network {
+            let ips = self.options.static_ips.get(&network);
+            if ips.is_some() || self.options.network_aliases.is_some() {
+                let endpoint = config
+                    .networking_config
+                    .get_or_insert_with(|| NetworkingConfig {
+                        endpoints_config: HashMap::new(),
+                    })
+                    .endpoints_config
+                    .entry(network)
+                    .or_default();
+                if let Some(ips) = ips {
+                    endpoint.ipam_config = Some(ips.to_ipam_config());
+                }
+                if let Some(aliases) = &self.options.network_aliases {
+                    endpoint.aliases = Some(aliases.clone());
+                }
             }
-            if let Some(val) = &override_config.entrypoint {
-                config.entrypoint = Some(val.clone());
+        }
+
+        config
+    }
+
+    /// Describe every option that was set with a builder method, but is silently replaced by a
+    /// different value in the [`Container::config_override`]
+    fn override_conflicts(&self) -> Vec<String> {
+        fn conflict<T: PartialEq + core::fmt::Debug>(
+            field: &str,
+            builder: Option<&T>,
+            override_value: Option<&T>,
+        ) -> Option<String> {
+            match (builder, override_value) {
+                (Some(builder), Some(override_value)) if builder != override_value => {
+                    Some(format!(
+                        "`{field}` is set to {builder:?}, but the config override replaces it with \
+                     {override_value:?}"
+                    ))
+                }
+                _ => None,
+            }
+        }
+
+        let Some(override_config) = &self.options.config_override else {
+            return Vec::new();
+        };
+        let options = &self.options;
+        let host = override_config.host_config.as_ref();
+
+        [
+            conflict("env", options.env.as_ref(), override_config.env.as_ref()),
+            conflict("cmd", options.cmd.as_ref(), override_config.cmd.as_ref()),
+            conflict(
+                "entrypoint",
+                options.entrypoint.as_ref(),
+                override_config.entrypoint.as_ref(),
+            ),
+            conflict(
+                "working_dir",
+                options.working_dir.as_ref(),
+                override_config.working_dir.as_ref(),
+            ),
+            conflict(
+                "stop_signal",
+                options.stop_signal.as_ref(),
+                override_config.stop_signal.as_ref(),
+            ),
+            conflict(
+                "stop_timeout",
+                options.stop_timeout.map(duration_secs).as_ref(),
+                override_config.stop_timeout.as_ref(),
+            ),
+            conflict(
+                "labels",
+                options.labels.as_ref(),
+                override_config.labels.as_ref(),
+            ),
+            conflict(
+                "healthcheck",
+                options.healthcheck.as_ref(),
+                override_config.healthcheck.as_ref(),
+            ),
+            conflict(
+                "binds",
+                options.binds.as_ref(),
+                host.and_then(|h| h.binds.as_ref()),
+            ),
+            conflict(
+                "extra_hosts",
+                options.extra_hosts.as_ref(),
+                host.and_then(|h| h.extra_hosts.as_ref()),
+            ),
+            conflict(
+                "port_bindings",
+                options.port_bindings.as_ref(),
+                host.and_then(|h| h.port_bindings.as_ref()),
+            ),
+            conflict(
+                "publish_all_ports",
+                options.publish_all_ports.as_ref(),
+                host.and_then(|h| h.publish_all_ports.as_ref()),
+            ),
+            conflict(
+                "restart_policy",
+                options.restart_policy.as_ref(),
+                host.and_then(|h| h.restart_policy.as_ref()),
+            ),
+            conflict(
+                "network_mode",
+                options.network.as_ref(),
+                host.and_then(|h| h.network_mode.as_ref()),
+            ),
+            conflict(
+                "runtime",
+                options.runtime.as_ref(),
+                host.and_then(|h| h.runtime.as_ref()),
+            ),
+            conflict(
+                "readonly_rootfs",
+                options.readonly_rootfs.as_ref(),
+                host.and_then(|h| h.readonly_rootfs.as_ref()),
+            ),
+            conflict(
+                "auto_remove",
+                options.auto_remove.as_ref(),
+                host.and_then(|h| h.auto_remove.as_ref()),
+            ),
+            conflict(
+                "privileged",
+                options.privileged.as_ref(),
+                host.and_then(|h| h.privileged.as_ref()),
+            ),
+            conflict(
+                "tmpfs",
+                options.tmpfs.as_ref(),
+                host.and_then(|h| h.tmpfs.as_ref()),
+            ),
+            conflict(
+                "memory",
+                options.memory.as_ref(),
+                host.and_then(|h| h.memory.as_ref()),
+            ),
+            conflict(
+                "memory_swap",
+                options.memory_swap.as_ref(),
+                host.and_then(|h| h.memory_swap.as_ref()),
+            ),
+            conflict(
+                "memory_reservation",
+                options.memory_reservation.as_ref(),
+                host.and_then(|h| h.memory_reservation.as_ref()),
+            ),
+            conflict(
+                "memory_swappiness",
+                options.memory_swappiness.as_ref(),
+                host.and_then(|h| h.memory_swappiness.as_ref()),
+            ),
+            conflict(
+                "shm_size",
+                options.shm_size.as_ref(),
+                host.and_then(|h| h.shm_size.as_ref()),
+            ),
+            conflict(
+                "ipc_mode",
+                options.ipc_mode.as_ref(),
+                host.and_then(|h| h.ipc_mode.as_ref()),
+            ),
+            conflict(
+                "pid_mode",
+                options.pid_mode.as_ref(),
+                host.and_then(|h| h.pid_mode.as_ref()),
+            ),
+            conflict(
+                "cpu_shares",
+                options.cpu_shares.as_ref(),
+                host.and_then(|h| h.cpu_shares.as_ref()),
+            ),
+            conflict(
+                "cpuset_cpus",
+                options.cpuset_cpus.as_ref(),
+                host.and_then(|h| h.cpuset_cpus.as_ref()),
+            ),
+            conflict(
+                "nano_cpus",
+                options.nano_cpus.as_ref(),
+                host.and_then(|h| h.nano_cpus.as_ref()),
+            ),
+            conflict(
+                "device_requests",
+                options.device_requests.as_ref(),
+                host.and_then(|h| h.device_requests.as_ref()),
+            ),
+            conflict(
+                "blkio_weight_device",
+                options.blkio_weight_device.as_ref(),
+                host.and_then(|h| h.blkio_weight_device.as_ref()),
+            ),
+            conflict(
+                "devices",
+                options.devices.as_ref(),
+                host.and_then(|h| h.devices.as_ref()),
+            ),
+            conflict(
+                "ulimits",
+                options.ulimits.as_ref(),
+                host.and_then(|h| h.ulimits.as_ref()),
+            ),
+            conflict(
+                "sysctls",
+                options.sysctls.as_ref(),
+                host.and_then(|h| h.sysctls.as_ref()),
+            ),
+            conflict(
+                "log_config",
+                options.log_config.as_ref(),
+                host.and_then(|h| h.log_config.as_ref()),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Ensure any static addresses requested on `network` fall within its subnets
+    async fn check_static_ips(&self, network: &str) -> Result<(), Error> {
+        let Some(ips) = self.options.static_ips.get(network) else {
+            return Ok(());
+        };
+
+        let inspect = self
+            .client
+            .inspect_network(network, None::<InspectNetworkOptions<String>>)
+            .await?;
+        let subnets = inspect
+            .ipam
+            .and_then(|ipam| ipam.config)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|config| config.subnet?.parse::<IpNet>().ok())
+            .collect::<Vec<_>>();
+
+        for address in ips.addresses() {
+            check_address_in_subnets(network, address, &subnets)?;
+        }
+
+        Ok(())
+    }
+
+    /// Attempt to start the container
+    ///
+    /// NOTE: If the container has not yet been created, this will attempt to call [`Container::create`] first.
+    ///
+    /// `wait_for_exit` will wait for the container to exit before returning.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").cmd(["echo", "Hello!"]);
+    ///
+    /// // We can now start our container, and the command "echo Hello!" will run.
+    /// let wait_for_exit = true;
+    /// container.start(wait_for_exit).await?;
+    ///
+    /// // Since we waited for the container to exit, we don't have to stop it.
+    /// // It can now just be removed.
+    /// container.remove(None).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn start(&mut self, wait_for_exit: bool) -> Result<(), Error> {
+        self.invalidate_cached_inspect();
+        if self.id.is_none() {
+            self.create().await?;
+        }
+
+        log::debug!("Starting container");
+        let id = self.id.as_ref().unwrap();
+        self.client
+            .start_container(id, None::<StartContainerOptions<String>>)
+            .await?;
+
+        if wait_for_exit {
+            self.wait().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Connect the container to an additional network
+    ///
+    /// Any static addresses requested for `network` with [`Container::with_ipv4`] or
+    /// [`Container::with_ipv6`] are assigned here.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * A requested static address is outside of the network's subnets
+    /// * Docker fails to connect the container (e.g. the network does not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .with_ipv4("backend", Ipv4Addr::new(10, 5, 0, 20));
+    ///
+    /// container.create().await?;
+    /// container.connect("backend").await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn connect(&self, network: &str) -> Result<(), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        self.check_static_ips(network).await?;
+
+        let endpoint_config = EndpointSettings {
+            ipam_config: self
+                .options
+                .static_ips
+                .get(network)
+                .map(|ips| ips.to_ipam_config()),
+            ..Default::default()
+        };
+
+        self.client
+            .connect_network(
+                network,
+                ConnectNetworkOptions {
+                    container: id.as_str(),
+                    endpoint_config,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Checks if the container has not exited and is marked as `healthy`
+    ///
+    /// NOTE: If the container has not yet been created, this will immediately return `None`.
+    ///
+    /// # Errors
+    ///
+    /// * Failed to get the list of containers
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    /// use tokio::time;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "rustlang/rust").cmd(["echo", "Hello!"]);
+    ///
+    /// let wait_for_exit = false;
+    /// container.start(wait_for_exit).await?;
+    ///
+    /// loop {
+    ///     let status = container.status().await?.unwrap();
+    ///     if status.is_active() {
+    ///         time::sleep(Duration::from_secs(5)).await;
+    ///         continue;
+    ///     }
+    ///
+    ///     println!("Container exited!");
+    ///     break;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn status(&self) -> Result<Option<ContainerStatus>, Error> {
+        let Some(id) = self.id.as_deref() else {
+            return Ok(None);
+        };
+
+        if self.options.inspect_cache_ttl.is_some() {
+            let inspect = match self.inspect(id).await {
+                Err(Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404,
+                    ..
+                })) => return Ok(None),
+                res => res?,
+            };
+            let Some(status) = inspect.state.and_then(|state| state.status) else {
+                return Ok(None);
+            };
+
+            return ContainerStatus::from_str(status.as_ref()).map(Some);
+        }
+
+        let mut filters = HashMap::new();
+        let _ = filters.insert("id", vec![id]);
+
+        let options = Some(ListContainersOptions {
+            all: true,
+            filters,
+            ..Default::default()
+        });
+
+        // The container may have been removed since it was created
+        let containers = self
+            .retrying(|| self.client.list_containers(options.clone()))
+            .await?;
+        let Some(status) = containers
+            .first()
+            .and_then(|container| container.state.as_ref())
+        else {
+            return Ok(None);
+        };
+
+        ContainerStatus::from_str(status.as_str()).map(Some)
+    }
+
+    /// Check whether the container still exists on the daemon
+    ///
+    /// NOTE: If the container has not yet been created, this will immediately return `false`.
+    ///       This always asks the daemon, ignoring [`Container::inspect_cache_ttl`].
+    ///
+    /// # Errors
+    ///
+    /// * Docker fails to inspect the container, for any reason other than it not existing
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::from_id(connection.client(), "my-container").await?;
+    ///
+    /// if !container.exists().await? {
+    ///     println!("Container was removed");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn exists(&self) -> Result<bool, Error> {
+        let Some(id) = self.id.as_deref() else {
+            return Ok(false);
+        };
+
+        match self
+            .retrying(|| {
+                self.client
+                    .inspect_container(id, None::<InspectContainerOptions>)
+            })
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Check whether the container is currently running
+    ///
+    /// NOTE: If the container has not yet been created, or has been removed, this returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// * Docker fails to get the container's status, see [`Container::status`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::from_id(connection.client(), "worker").await?;
+    ///
+    /// loop {
+    ///     if !container.is_running().await? {
+    ///         container.start(false).await?;
+    ///     }
+    ///     tokio::time::sleep(Duration::from_secs(5)).await;
+    /// }
+    /// # }
+    /// ```
+    pub async fn is_running(&self) -> Result<bool, Error> {
+        Ok(self.status().await? == Some(ContainerStatus::Running))
+    }
+
+    /// Check whether the container is running and, if it has a healthcheck, passing it
+    ///
+    /// A running container without a healthcheck is considered healthy. A container that is
+    /// still in its healthcheck start period is not.
+    ///
+    /// NOTE: If the container has not yet been created, or has been removed, this returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// * Docker fails to inspect the container, for any reason other than it not existing
+    pub async fn is_healthy(&self) -> Result<bool, Error> {
+        let Some(id) = &self.id else {
+            return Ok(false);
+        };
+
+        let inspect = match self.inspect(id).await {
+            Err(Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404,
+                ..
+            })) => return Ok(false),
+            res => res?,
+        };
+        let running = inspect
+            .state
+            .as_ref()
+            .and_then(|state| state.running)
+            .unwrap_or(false);
+
+        Ok(running
+            && matches!(
+                HealthStatus::from_inspect(&inspect),
+                HealthStatus::Healthy | HealthStatus::None
+            ))
+    }
+
+    /// Get the current health of the container
+    ///
+    /// Containers without a healthcheck are reported as [`HealthStatus::None`].
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to inspect the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, HealthStatus};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::from_id(connection.client(), "my-service").await?;
+    ///
+    /// if container.health_status().await? == HealthStatus::Unhealthy {
+    ///     eprintln!("my-service is unhealthy!");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn health_status(&self) -> Result<HealthStatus, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let inspect = self.inspect(id).await?;
+        Ok(HealthStatus::from_inspect(&inspect))
+    }
+
+    /// Get the results of the most recent healthcheck runs, oldest first
+    ///
+    /// Docker only keeps the last few results. This will be empty if the container has no
+    /// healthcheck, or it hasn't run yet.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created, or has been removed ([`Error::ContainerNotFound`])
+    /// * Docker fails to inspect the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::from_id(connection.client(), "my-service").await?;
+    ///
+    /// for result in container.health_log().await? {
+    ///     if result.exit_code != Some(0) {
+    ///         eprintln!("Healthcheck failed: {}", result.output);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn health_log(&self) -> Result<Vec<HealthCheckResult>, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let inspect = match self.inspect(id).await {
+            Err(Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404,
+                ..
+            })) => return Err(Error::ContainerNotFound),
+            res => res?,
+        };
+        let log = inspect
+            .state
+            .and_then(|state| state.health)
+            .and_then(|health| health.log)
+            .unwrap_or_default();
+
+        Ok(log
+            .into_iter()
+            .map(|result| HealthCheckResult {
+                start: result.start,
+                end: result.end,
+                exit_code: result.exit_code,
+                output: result.output.unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Wait for the container's healthcheck to report it as `healthy`
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created, or is removed while waiting
+    ///   ([`Error::ContainerNotFound`])
+    /// * The container has no healthcheck
+    /// * The container isn't healthy within `timeout`, in which case the last failed check (if
+    ///   it can be fetched) is returned in [`Error::HealthTimeout`]
+    /// * Docker fails to inspect the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, Error};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres");
+    /// container.start(false).await?;
+    ///
+    /// match container.wait_until_healthy(Duration::from_secs(30)).await {
+    ///     Err(Error::HealthTimeout { last_failure: Some(failure), .. }) => {
+    ///         eprintln!("Postgres never became healthy: {}", failure.output);
+    ///     }
+    ///     res => res?,
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn wait_until_healthy(&self, timeout: Duration) -> Result<(), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let poll = async {
+            loop {
+                let inspect = match self
+                    .client
+                    .inspect_container(id, None::<InspectContainerOptions>)
+                    .await
+                {
+                    Err(bollard::errors::Error::DockerResponseServerError {
+                        status_code: 404,
+                        ..
+                    }) => return Err(Error::ContainerNotFound),
+                    res => res?,
+                };
+                let Some(health) = inspect.state.and_then(|state| state.health) else {
+                    return Err(Error::InvalidConfig(String::from(
+                        "the container has no healthcheck",
+                    )));
+                };
+
+                if health.status == Some(HealthStatusEnum::HEALTHY) {
+                    return Ok(());
+                }
+
+                tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
             }
-            if let Some(val) = override_config.network_disabled {
-                config.network_disabled = Some(val);
+        };
+
+        match tokio::time::timeout(timeout, poll).await {
+            Ok(res) => res,
+            Err(_) => {
+                // The timeout is the error worth reporting, even if the log can't be fetched
+                let last_failure = self.health_log().await.ok().and_then(|log| {
+                    log.into_iter()
+                        .rev()
+                        .find(|result| result.exit_code != Some(0))
+                });
+                Err(Error::HealthTimeout {
+                    timeout,
+                    last_failure,
+                })
             }
-            if let Some(val) = &override_config.mac_address {
-                config.mac_address = Some(val.clone());
+        }
+    }
+
+    /// Give the next long-running operation `timeout`, instead of the client-wide timeout
+    ///
+    /// The timeout set on the Docker client (e.g. with [`DockerBuilder`](crate::DockerBuilder))
+    /// applies to every request. Some operations can legitimately take longer, such as stopping
+    /// a container with a long grace period, or committing a large one. Rather than raising the
+    /// timeout for everything, it can be extended for just the next one of:
+    ///
+    /// * [`Container::stop`]
+    /// * [`Container::restart`]
+    /// * [`Container::remove`]
+    /// * [`Container::snapshot_and_restart`] (the commit, stop, and removal)
+    /// * [`Container::upload`], [`Container::copy_to`], and [`Container::copy_path_to`]
+    ///
+    /// Other operations don't use, or reset, the timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::from_id(connection.client(), "postgres").await?;
+    ///
+    /// // Postgres can take a while to shut down cleanly
+    /// container
+    ///     .with_operation_timeout(Duration::from_secs(300))
+    ///     .stop()
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn with_operation_timeout(&mut self, timeout: Duration) -> &mut Self {
+        *self
+            .operation_timeout
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(timeout);
+        self
+    }
+
+    /// The client for a long-running operation, consuming any [`Container::with_operation_timeout`]
+    fn operation_client(&self) -> Arc<Docker> {
+        let timeout = self
+            .operation_timeout
+            .lock()
+            .map_or(None, |mut timeout| timeout.take());
+        match timeout {
+            Some(timeout) => Arc::new((*self.client).clone().with_timeout(timeout)),
+            None => self.client.clone(),
+        }
+    }
+
+    /// Options for stopping the container, honoring [`Container::stop_timeout`]
+    fn stop_options(&self) -> Option<StopContainerOptions> {
+        self.options
+            .stop_timeout
+            .map(|timeout| StopContainerOptions {
+                t: duration_secs(timeout),
+            })
+    }
+
+    /// Stop a running container
+    ///
+    /// The container is sent its stop signal, and killed if it hasn't exited within its stop
+    /// timeout. See [`Container::stop_signal`] and [`Container::stop_timeout`].
+    ///
+    /// NOTE: It is not an error to call this on a container that has not been started,
+    ///       it will simply do nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// // Does nothing, the container isn't started
+    /// container.stop().await?;
+    ///
+    /// // Stops the running container
+    /// container.start(false).await?;
+    /// container.stop().await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip_all)]
+    pub async fn stop(&mut self) -> Result<(), bollard::errors::Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            log::warn!("Container not started");
+            return Ok(());
+        };
+
+        let client = self.operation_client();
+        self.retrying(|| client.stop_container(id, self.stop_options()))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Immediately send a signal to the container's main process (equivalent to `docker kill`)
+    ///
+    /// `signal` may be a name (`"SIGTERM"`) or a number (`"15"`). If `None`, `SIGKILL` is sent.
+    ///
+    /// Unlike [`Container::stop`], there is no grace period.
+    ///
+    /// NOTE: It is not an error to call this on a container that has not been started,
+    ///       it will simply do nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// container.start(false).await?;
+    ///
+    /// // Simulate a crash
+    /// container.kill(None).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn kill(&mut self, signal: Option<&str>) -> Result<(), bollard::errors::Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            log::warn!("Container not started");
+            return Ok(());
+        };
+
+        let options = KillContainerOptions {
+            signal: signal.unwrap_or("SIGKILL"),
+        };
+        self.client.kill_container(id, Some(options)).await?;
+
+        Ok(())
+    }
+
+    /// Stop a running container, force-killing it if it ignores its stop signal
+    ///
+    /// The container is sent its stop signal (`SIGTERM`, unless set with
+    /// [`Container::stop_signal`]) and given up to `timeout` to exit. If it's still running after
+    /// that, it's sent `SIGKILL`. Unlike [`Container::stop`], the grace period is enforced here
+    /// rather than by the daemon, so it isn't truncated to whole seconds.
+    ///
+    /// This returns once the container is no longer running.
+    ///
+    /// NOTE: It is not an error to call this on a container that has not been started or has
+    ///       already exited, it will simply do nothing.
+    ///
+    /// # Errors
+    ///
+    /// * Docker fails to signal the container
+    /// * Docker fails to wait for the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// container.start(false).await?;
+    ///
+    /// // Give the container 5 seconds to shut down before killing it
+    /// container.stop_graceful(Duration::from_secs(5)).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn stop_graceful(&mut self, timeout: Duration) -> Result<(), Error> {
+        let Some(id) = self.id.clone() else {
+            log::warn!("Container not started");
+            return Ok(());
+        };
+
+        let signal = self.options.stop_signal.clone();
+        let signal = signal.as_deref().unwrap_or("SIGTERM");
+        if !signal_running(self.kill(Some(signal)).await)? {
+            return Ok(());
+        }
+
+        // The exit code doesn't matter here, so bypass the `WaitBehavior`
+        let exited = wait_for_exit_code(
+            &self.client,
+            &id,
+            WaitCondition::NotRunning,
+            WaitBehavior::ReturnExitCode,
+        );
+        match tokio::time::timeout(timeout, exited).await {
+            Ok(result) => {
+                result?;
+                return Ok(());
             }
-            if let Some(val) = &override_config.on_build {
-                config.on_build = Some(val.clone());
+            Err(_) => log::warn!("Container didn't exit within {timeout:?}, killing it"),
+        }
+
+        // It may have exited between the timeout and the kill
+        if signal_running(self.kill(None).await)? {
+            wait_for_exit_code(
+                &self.client,
+                &id,
+                WaitCondition::NotRunning,
+                WaitBehavior::ReturnExitCode,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Restart a container, keeping its ID
+    ///
+    /// `timeout` is how long to wait for the container to stop before killing it, rounded up to
+    /// whole seconds. If `None`, the container's [`Container::stop_timeout`] is used, or Docker's
+    /// default of 10 seconds if it has none.
+    ///
+    /// NOTE: It is not an error to call this on a container that has not been started,
+    ///       it will simply do nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// container.start(false).await?;
+    ///
+    /// // Give the container 30 seconds to shut down before it's killed
+    /// container.restart(Some(Duration::from_secs(30))).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn restart(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<(), bollard::errors::Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            log::warn!("Container not started");
+            return Ok(());
+        };
+
+        let t = timeout
+            .or(self.options.stop_timeout)
+            .map_or(DEFAULT_STOP_TIMEOUT_SECS, |timeout| {
+                isize::try_from(duration_secs(timeout)).unwrap_or(isize::MAX)
+            });
+
+        self.operation_client()
+            .restart_container(id, Some(RestartContainerOptions { t }))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Snapshot the container's filesystem and restart it from the snapshot
+    ///
+    /// This commits the container to a new image (equivalent to `docker commit`), removes it, and
+    /// then creates and starts a replacement from that image with the same options. Any changes
+    /// made to the writable layer at runtime, such as files written by the process, carry over to
+    /// the new container.
+    ///
+    /// NOTE: Only the filesystem is preserved. In-memory state, running processes, and the
+    ///       contents of tmpfs mounts are lost, and the new container has a new ID.
+    ///
+    /// The new container always uses the snapshot image, replacing any image set by
+    /// [`Container::config_override`].
+    ///
+    /// The snapshot image is in use by the new container, so it can't be removed immediately.
+    /// Unless `keep_image` is `true`, it is removed along with the container by
+    /// [`Container::remove`].
+    ///
+    /// NOTE: If the container is dropped without calling [`Container::remove`], the snapshot
+    ///       images are left behind, and need to be removed manually (e.g. with `docker rmi`).
+    ///
+    /// Returns the reference (`repository:tag`) of the snapshot image.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to commit, remove, or recreate the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine:latest")
+    ///     .cmd(["sh", "-c", "touch /warm && sleep infinity"]);
+    ///
+    /// container.start(false).await?;
+    ///
+    /// // `/warm` exists in the new container before its command runs
+    /// let image = container.snapshot_and_restart(false).await?;
+    /// println!("Restarted from {image}");
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn snapshot_and_restart(&mut self, keep_image: bool) -> Result<String, Error> {
+        let Some(id) = self.id.clone() else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        // The commit response doesn't reliably include the image ID, so tag it instead
+        let repo = String::from("docktopus-snapshot");
+        let tag = format!(
+            "{}-{}",
+            &id[..id.len().min(12)],
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+        );
+        let image = format!("{repo}:{tag}");
+
+        log::debug!("Committing container `{id}` to `{image}`");
+        let client = self.operation_client();
+        client
+            .commit_container(
+                CommitContainerOptions {
+                    container: id.clone(),
+                    repo,
+                    tag,
+                    pause: true,
+                    ..Default::default()
+                },
+                Config::<String>::default(),
+            )
+            .await?;
+
+        client.stop_container(&id, self.stop_options()).await?;
+        client
+            .remove_container(&id, None::<RemoveContainerOptions>)
+            .await?;
+        self.id = None;
+
+        // The snapshot only exists locally, so it can never be pulled
+        self.image.clone_from(&image);
+        self.options.pull_policy = PullPolicy::Never;
+        // Otherwise the override would restart the container from its original image
+        if let Some(config) = &mut self.options.config_override {
+            config.image = None;
+        }
+        if !keep_image {
+            self.snapshots.push(image.clone());
+        }
+
+        self.start(false).await?;
+        Ok(image)
+    }
+
+    /// Change the resource limits of the container without recreating it (equivalent to
+    /// `docker update`)
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * A memory value can't be parsed, see [`Error::InvalidResourceLimit`]
+    /// * Docker rejects the new limits (e.g. a memory limit above the swap limit)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, ResourceUpdate};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    /// container.start(false).await?;
+    ///
+    /// // Throttle a noisy neighbor
+    /// container
+    ///     .update(ResourceUpdate {
+    ///         cpu_shares: Some(256),
+    ///         nano_cpus: Some(500_000_000),
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn update(&mut self, limits: ResourceUpdate) -> Result<(), Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        self.client
+            .update_container(id, limits.to_options()?)
+            .await?;
+        Ok(())
+    }
+
+    /// Pause a running container (equivalent to `docker pause`)
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The container is not running, see [`Error::InvalidStateTransition`]
+    /// * Docker fails to pause the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, ContainerStatus};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// container.start(false).await?;
+    /// container.pause().await?;
+    ///
+    /// assert_eq!(container.status().await?, Some(ContainerStatus::Paused));
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip_all)]
+    pub async fn pause(&mut self) -> Result<(), Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        match self.client.pause_container(id).await {
+            Ok(()) => Ok(()),
+            Err(e) => Err(self.map_conflict(e, ContainerStatus::Paused).await),
+        }
+    }
+
+    /// Resume a paused container (equivalent to `docker unpause`)
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The container is not paused, see [`Error::NotPaused`]
+    /// * Docker fails to unpause the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, ContainerStatus};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// container.start(false).await?;
+    /// container.pause().await?;
+    /// container.unpause().await?;
+    ///
+    /// assert_eq!(container.status().await?, Some(ContainerStatus::Running));
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip_all)]
+    pub async fn unpause(&mut self) -> Result<(), Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        match self.client.unpause_container(id).await {
+            Ok(()) => Ok(()),
+            Err(e) => match self.map_conflict(e, ContainerStatus::Running).await {
+                Error::InvalidStateTransition { from, .. } => Err(Error::NotPaused(from)),
+                e => Err(e),
+            },
+        }
+    }
+
+    /// Rename the container (equivalent to `docker rename`)
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The name is taken by another container, see [`Error::Conflict`]
+    /// * Docker fails to rename the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, Error};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    /// container.create().await?;
+    ///
+    /// match container.rename("builder").await {
+    ///     Err(Error::Conflict { existing, .. }) => {
+    ///         eprintln!("`builder` is already taken by {existing}");
+    ///     }
+    ///     res => res?,
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn rename(&mut self, name: &str) -> Result<(), Error> {
+        self.invalidate_cached_inspect();
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        if let Err(e) = self
+            .client
+            .rename_container(id, RenameContainerOptions { name })
+            .await
+        {
+            let is_conflict = matches!(
+                e,
+                bollard::errors::Error::DockerResponseServerError {
+                    status_code: 409,
+                    ..
+                }
+            );
+            if !is_conflict {
+                return Err(e.into());
             }
-            if let Some(val) = &override_config.labels {
-                config.labels = Some(val.clone());
+
+            return match self.find_by_name(name).await? {
+                Some(existing) => Err(Error::Conflict {
+                    name: name.to_string(),
+                    existing,
+                }),
+                None => Err(e.into()),
+            };
+        }
+
+        self.name = Some(name.to_string());
+        self.options.name = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Rename the container, forcibly removing any other container that has the name
+    ///
+    /// NOTE: The conflicting container is killed and removed along with its anonymous volumes.
+    ///       Prefer [`Container::rename`] unless the other container is known to be disposable.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to remove the conflicting container, or to rename this one
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    /// container.create().await?;
+    ///
+    /// // Replaces a leftover `builder` from a previous run
+    /// container.rename_force("builder").await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn rename_force(&mut self, name: &str) -> Result<(), Error> {
+        match self.rename(name).await {
+            Err(Error::Conflict { existing, .. }) if Some(&existing) != self.id.as_ref() => {
+                log::warn!("Removing container `{existing}` to take its name `{name}`");
+                self.client
+                    .remove_container(
+                        &existing,
+                        Some(RemoveContainerOptions {
+                            force: true,
+                            v: true,
+                            ..Default::default()
+                        }),
+                    )
+                    .await?;
+                self.rename(name).await
             }
-            if let Some(val) = &override_config.stop_signal {
-                config.stop_signal = Some(val.clone());
+            res => res,
+        }
+    }
+
+    /// Find the ID of the container with exactly the given name
+    async fn find_by_name(&self, name: &str) -> Result<Option<String>, Error> {
+        let name = name.strip_prefix('/').unwrap_or(name);
+        let pattern = Regex::new(&format!("^{}$", regex::escape(name)))
+            .expect("escaped names are valid patterns");
+        let filter = ListFilter::new().all(true).name(pattern);
+
+        let containers = self
+            .client
+            .list_containers(Some(filter.to_options()))
+            .await?;
+        Ok(containers
+            .into_iter()
+            .find(|container| filter.matches_name(container.names.as_deref()))
+            .and_then(|container| container.id))
+    }
+
+    /// Turn a `409 Conflict` from the daemon into an [`Error::InvalidStateTransition`]
+    async fn map_conflict(&self, e: bollard::errors::Error, to: ContainerStatus) -> Error {
+        if !matches!(
+            e,
+            bollard::errors::Error::DockerResponseServerError {
+                status_code: 409,
+                ..
             }
-            if let Some(val) = override_config.stop_timeout {
-                config.stop_timeout = Some(val);
+        ) {
+            return Error::Bollard(e);
+        }
+
+        match self.status().await {
+            Ok(Some(from)) => Error::InvalidStateTransition { from, to },
+            _ => Error::Bollard(e),
+        }
+    }
+
+    /// Remove a container
+    ///
+    /// NOTE: To remove a running container, a [`RemoveContainerOptions`] must be provided
+    ///       with the `force` flag set. By default, this kills the container immediately, see
+    ///       [`Container::graceful_force`] to have it stopped gracefully first.
+    ///
+    /// See also: [`bollard::container::RemoveContainerOptions`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// // Start our container
+    /// container.start(false).await?;
+    ///
+    /// let remove_container_options = bollard::container::RemoveContainerOptions {
+    ///     force: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // Kills the container and removes it
+    /// container.remove(Some(remove_container_options)).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`RemoveContainerOptions::force`]: bollard::container::RemoveContainerOptions::force
+    #[tracing::instrument(skip(self))]
+    pub async fn remove(
+        mut self,
+        options: Option<bollard::container::RemoveContainerOptions>,
+    ) -> Result<(), bollard::errors::Error> {
+        let Some(id) = self.id.take() else {
+            log::warn!("Container not started");
+            return Ok(());
+        };
+
+        let client = self.operation_client();
+        let force = options.as_ref().is_some_and(|options| options.force);
+        if force && self.options.graceful_force {
+            // With no explicit timeout, the daemon honors the container's own stop signal and
+            // timeout (`--stop-signal`/`--stop-timeout`). This is a no-op if it isn't running.
+            log::debug!("Gracefully stopping container before forced removal");
+            client.stop_container(&id, self.stop_options()).await?;
+        }
+
+        client.remove_container(&id, options).await?;
+
+        // Newest first, as each snapshot is the parent of the next
+        for image in self.snapshots.iter().rev() {
+            if let Err(e) = self
+                .client
+                .remove_image(image, None::<RemoveImageOptions>, None)
+                .await
+            {
+                log::warn!("Failed to remove snapshot image `{image}`: {e}");
             }
-            if let Some(val) = &override_config.shell {
-                config.shell = Some(val.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Wait for a container to exit, returning its exit code
+    ///
+    /// Whether a non-zero exit code is returned or reported as an error depends on the
+    /// [`Container::wait_behavior`].
+    ///
+    /// NOTE: Unlike [`Container::stop`], calling this on a container that has not been created
+    ///       is an error ([`Error::ContainerNotFound`]), as there is no exit code to return.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The container exited with a non-zero code, and the [`WaitBehavior`] is [`WaitBehavior::ErrorOnNonZero`]
+    /// * Docker fails to wait for the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// // Start our container
+    /// container.start(false).await?;
+    ///
+    /// // Once this returns, we know that the container has exited.
+    /// let exit_code = container.wait().await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip_all)]
+    pub async fn wait(&self) -> Result<i64, Error> {
+        self.wait_for(WaitCondition::NotRunning).await
+    }
+
+    /// Wait for the container to reach `condition`, returning its exit code
+    ///
+    /// [`Container::wait`] waits for [`WaitCondition::NotRunning`], which a container with a
+    /// restart policy may never reach. Waiting for [`WaitCondition::NextExit`] returns as soon as
+    /// it exits, whether or not it's restarted.
+    ///
+    /// Whether a non-zero exit code is returned or reported as an error depends on the
+    /// [`Container::wait_behavior`].
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The container exited with a non-zero code, and the [`WaitBehavior`] is [`WaitBehavior::ErrorOnNonZero`]
+    /// * Docker fails to wait for the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::models::{RestartPolicy, RestartPolicyNameEnum};
+    /// use docktopus::container::{Container, WaitCondition};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "myorg/worker").restart_policy(
+    ///     RestartPolicy {
+    ///         name: Some(RestartPolicyNameEnum::ALWAYS),
+    ///         maximum_retry_count: None,
+    ///     },
+    /// );
+    /// container.start(false).await?;
+    ///
+    /// // Returns on the first crash, even though the container will be restarted
+    /// let exit_code = container.wait_for(WaitCondition::NextExit).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn wait_for(&self, condition: WaitCondition) -> Result<i64, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let exit_code =
+            wait_for_exit_code(&self.client, id, condition, self.options.wait_behavior).await?;
+        Ok(exit_code)
+    }
+
+    /// Fetch the container log stream
+    ///
+    /// NOTE: It is not an error to call this on a container that has not been started,
+    ///       it will simply do nothing and return `None`.
+    ///
+    /// See also:
+    ///
+    /// * [`bollard::container::LogsOptions`]
+    /// * [`bollard::container::LogOutput`]
+    /// * [`Container::collect_stdout`] and [`Container::collect_stderr`] to collect a single stream
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    ///
+    /// // Start our container and wait for it to exit
+    /// container.start(true).await?;
+    ///
+    /// // We want to collect logs from stderr
+    /// let logs_options = bollard::container::LogsOptions {
+    ///     stderr: true,
+    ///     follow: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // Get our log stream
+    /// let mut logs = container
+    ///     .logs(Some(logs_options))
+    ///     .await
+    ///     .expect("logs should be present");
+    ///
+    /// // Now we want to print anything from stderr
+    /// while let Some(Ok(out)) = logs.next().await {
+    ///     if let bollard::container::LogOutput::StdErr { message } = out {
+    ///         eprintln!("Uh oh! Something was written to stderr: {:?}", message);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn logs(
+        &self,
+        logs_options: Option<bollard::container::LogsOptions<String>>,
+    ) -> Option<impl Stream<Item = Result<bollard::container::LogOutput, bollard::errors::Error>>>
+    {
+        let Some(id) = &self.id else {
+            log::warn!("Container not created");
+            return None;
+        };
+
+        Some(self.client.logs(id, logs_options))
+    }
+
+    /// Fetch the container log stream for a [`LogQuery`]
+    ///
+    /// This is [`Container::logs`], without having to build the [`LogsOptions`] by hand.
+    ///
+    /// NOTE: It is not an error to call this on a container that has not been started,
+    ///       it will simply do nothing and return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, LogQuery};
+    /// use futures::StreamExt;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::from_id(connection.client(), "my-container").await?;
+    ///
+    /// // Print the last 10 lines from the past 5 minutes
+    /// let query = LogQuery::new()
+    ///     .tail(10)
+    ///     .since(SystemTime::now() - Duration::from_secs(5 * 60));
+    /// let mut logs = container.logs_query(query).await.expect("logs should be present");
+    /// while let Some(Ok(out)) = logs.next().await {
+    ///     print!("{out}");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn logs_query(
+        &self,
+        query: LogQuery,
+    ) -> Option<impl Stream<Item = Result<LogOutput, bollard::errors::Error>>> {
+        self.logs(Some(query.into())).await
+    }
+
+    /// Collect everything the container has written to stdout
+    ///
+    /// Any `stdout`/`stderr` flags in `logs_options` are overridden, everything else (e.g.
+    /// `follow` or `tail`) is respected.
+    ///
+    /// See also: [`Container::collect_stderr`], [`Container::logs`]
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to fetch the logs
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine").cmd(["echo", "Hello!"]);
+    ///
+    /// container.start(true).await?;
+    /// assert_eq!(container.collect_stdout(None).await?, "Hello!\n");
+    /// # Ok(()) }
+    /// ```
+    pub async fn collect_stdout(
+        &self,
+        logs_options: Option<LogsOptions<String>>,
+    ) -> Result<String, Error> {
+        self.collect_log_stream(single_stream_options(logs_options, true))
+            .await
+    }
+
+    /// Collect everything the container has written to stderr
+    ///
+    /// Any `stdout`/`stderr` flags in `logs_options` are overridden, everything else (e.g.
+    /// `follow` or `tail`) is respected.
+    ///
+    /// See also: [`Container::collect_stdout`], [`Container::logs`]
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to fetch the logs
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "alpine").cmd(["sh", "-c", "echo Uh oh! >&2"]);
+    ///
+    /// container.start(true).await?;
+    /// assert_eq!(container.collect_stderr(None).await?, "Uh oh!\n");
+    /// # Ok(()) }
+    /// ```
+    pub async fn collect_stderr(
+        &self,
+        logs_options: Option<LogsOptions<String>>,
+    ) -> Result<String, Error> {
+        self.collect_log_stream(single_stream_options(logs_options, false))
+            .await
+    }
+
+    /// Collect the container's combined stdout and stderr, in the order it was written
+    ///
+    /// If `logs_options` is `None`, both stdout and stderr are collected. Otherwise, the
+    /// options are used as-is, so at least one of `stdout` or `stderr` must be set.
+    ///
+    /// See also: [`Container::collect_stdout`] and [`Container::collect_stderr`] to keep the
+    /// streams apart
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to fetch the logs
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine").cmd(["echo", "Hello!"]);
+    ///
+    /// container.start(true).await?;
+    /// assert_eq!(container.logs_to_string(None).await?, "Hello!\n");
+    /// # Ok(()) }
+    /// ```
+    pub async fn logs_to_string(
+        &self,
+        logs_options: Option<LogsOptions<String>>,
+    ) -> Result<String, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let logs_options = logs_options.unwrap_or_else(|| LogsOptions {
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        });
+
+        // Decoded once at the end, as a character may be split across frames
+        let mut output = self.client.logs(id, Some(logs_options));
+        let mut bytes = BytesMut::new();
+        while let Some(out) = output.try_next().await? {
+            match out {
+                LogOutput::StdOut { message }
+                | LogOutput::StdErr { message }
+                | LogOutput::Console { message } => bytes.extend_from_slice(&message),
+                LogOutput::StdIn { .. } => {}
             }
-            // HostConfig needs separate merging
-            if let Some(override_host_config) = &override_config.host_config {
-                let mut host_config = config.host_config.unwrap_or_default();
-                if let Some(val) = &override_host_config.binds {
-                    // Prefer override binds entirely if specified
-                    host_config.binds = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.network_mode {
-                    host_config.network_mode = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.port_bindings {
-                    // Prefer override port_bindings entirely if specified
-                    host_config.port_bindings = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.restart_policy {
-                    host_config.restart_policy = Some(val.clone());
-                }
-                if let Some(val) = override_host_config.auto_remove {
-                    host_config.auto_remove = Some(val);
-                }
-                if let Some(val) = &override_host_config.volume_driver {
-                    host_config.volume_driver = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.volumes_from {
-                    host_config.volumes_from = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.mounts {
-                    host_config.mounts = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.cap_add {
-                    host_config.cap_add = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.cap_drop {
-                    host_config.cap_drop = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.cgroupns_mode {
-                    host_config.cgroupns_mode = Some(*val);
-                }
-                if let Some(val) = &override_host_config.dns {
-                    host_config.dns = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.dns_options {
-                    host_config.dns_options = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.dns_search {
-                    host_config.dns_search = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.extra_hosts {
-                    // Prefer override extra_hosts entirely if specified
-                    host_config.extra_hosts = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.group_add {
-                    host_config.group_add = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.ipc_mode {
-                    host_config.ipc_mode = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.cgroup {
-                    host_config.cgroup = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.links {
-                    host_config.links = Some(val.clone());
-                }
-                if let Some(val) = override_host_config.oom_score_adj {
-                    host_config.oom_score_adj = Some(val);
-                }
-                if let Some(val) = &override_host_config.pid_mode {
-                    host_config.pid_mode = Some(val.clone());
-                }
-                if let Some(val) = override_host_config.privileged {
-                    host_config.privileged = Some(val);
-                }
-                if let Some(val) = override_host_config.publish_all_ports {
-                    host_config.publish_all_ports = Some(val);
-                }
-                if let Some(val) = override_host_config.readonly_rootfs {
-                    host_config.readonly_rootfs = Some(val);
-                }
-                if let Some(val) = &override_host_config.security_opt {
-                    host_config.security_opt = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.storage_opt {
-                    host_config.storage_opt = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.tmpfs {
-                    host_config.tmpfs = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.uts_mode {
-                    host_config.uts_mode = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.userns_mode {
-                    host_config.userns_mode = Some(val.clone());
-                }
-                if let Some(val) = override_host_config.shm_size {
-                    host_config.shm_size = Some(val);
-                }
-                if let Some(val) = &override_host_config.sysctls {
-                    host_config.sysctls = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.runtime {
-                    // Prefer override runtime if specified
-                    host_config.runtime = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.console_size {
-                    host_config.console_size = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.isolation {
-                    host_config.isolation = Some(*val);
-                }
-                if let Some(val) = &override_host_config.masked_paths {
-                    host_config.masked_paths = Some(val.clone());
-                }
-                if let Some(val) = &override_host_config.readonly_paths {
-                    host_config.readonly_paths = Some(val.clone());
-                }
-                config.host_config = Some(host_config);
+        }
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Read one of the container's output streams as an [`AsyncRead`]
+    ///
+    /// Only the selected `stream` is read, stdout and stderr are never merged. Merging would
+    /// interleave them with no way to tell them apart, use [`Container::logs`] if both are
+    /// needed. Any `stdout`/`stderr` flags in `logs_options` are overridden, everything else (e.g.
+    /// `follow` or `tail`) is respected.
+    ///
+    /// Errors while fetching the logs are reported as [`std::io::Error`]s when reading.
+    ///
+    /// # Errors
+    ///
+    /// The container has not been created
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, LogStream};
+    /// use bollard::container::LogsOptions;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres");
+    /// container.start(false).await?;
+    ///
+    /// // Tee the server log into a file as it's written
+    /// let mut reader = container.logs_reader(
+    ///     Some(LogsOptions {
+    ///         follow: true,
+    ///         ..Default::default()
+    ///     }),
+    ///     LogStream::Stderr,
+    /// )?;
+    /// let mut file = tokio::fs::File::create("postgres.log").await?;
+    /// tokio::io::copy(&mut reader, &mut file).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn logs_reader(
+        &self,
+        logs_options: Option<LogsOptions<String>>,
+        stream: LogStream,
+    ) -> Result<impl AsyncRead + Send + Unpin + use<>, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let logs_options = single_stream_options(logs_options, stream == LogStream::Stdout);
+        let output = self
+            .client
+            .logs(id, Some(logs_options))
+            .try_filter_map(move |output| {
+                let bytes = match (output, stream) {
+                    (
+                        LogOutput::StdOut { message } | LogOutput::Console { message },
+                        LogStream::Stdout,
+                    )
+                    | (LogOutput::StdErr { message }, LogStream::Stderr) => Some(message),
+                    _ => None,
+                };
+                futures_util::future::ready(Ok(bytes))
+            })
+            .map_err(std::io::Error::other);
+
+        Ok(StreamReader::new(output))
+    }
+
+    async fn collect_log_stream(&self, logs_options: LogsOptions<String>) -> Result<String, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let (stdout, stderr) = demux_output(self.client.logs(id, Some(logs_options))).await?;
+        Ok(stdout + &stderr)
+    }
+
+    /// Wait for a line matching `pattern` to appear in the container's logs
+    ///
+    /// This is [`Container::wait_for_log_count`] with a `count` of 1, returning the matching line.
+    ///
+    /// # Errors
+    ///
+    /// See [`Container::wait_for_log_count`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use regex::Regex;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres");
+    /// container.start(false).await?;
+    ///
+    /// let ready = Regex::new("ready to accept connections").unwrap();
+    /// container
+    ///     .wait_for_log(&ready, Duration::from_secs(30))
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn wait_for_log(&self, pattern: &Regex, timeout: Duration) -> Result<String, Error> {
+        let mut lines = self.wait_for_log_count(pattern, 1, timeout).await?;
+        Ok(lines.remove(0))
+    }
+
+    /// Wait for `count` lines matching `pattern` to appear in the container's logs
+    ///
+    /// Both stdout and stderr are searched, starting from the very first line the container
+    /// logged, so lines written before this is called are counted too. The first `count`
+    /// matching lines are returned as soon as they're seen, if the pattern appears more than
+    /// `count` times, the extra lines are ignored.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Fewer than `count` lines matched within `timeout`, or before the container exited, see
+    ///   [`Error::LogTimeout`]
+    /// * Docker fails to fetch the logs
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use regex::Regex;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "my-worker-pool");
+    /// container.start(false).await?;
+    ///
+    /// // All 4 workers are up
+    /// let ready = Regex::new(r"worker \d+ ready").unwrap();
+    /// let lines = container
+    ///     .wait_for_log_count(&ready, 4, Duration::from_secs(30))
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn wait_for_log_count(
+        &self,
+        pattern: &Regex,
+        count: usize,
+        timeout: Duration,
+    ) -> Result<Vec<String>, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let mut logs = self.client.logs(
+            id,
+            Some(LogsOptions::<String> {
+                follow: true,
+                stdout: true,
+                stderr: true,
+                ..Default::default()
+            }),
+        );
+
+        let mut matcher = LogLineMatcher::new(pattern);
+        let follow = async {
+            while matcher.matches.len() < count {
+                let Some(output) = logs.try_next().await? else {
+                    // The container exited, the last line may not have a newline
+                    matcher.finish();
+                    break;
+                };
+                matcher.push(output);
             }
+            Ok::<_, Error>(())
+        };
+
+        // A timeout is handled the same way as the container exiting early
+        if let Ok(res) = tokio::time::timeout(timeout, follow).await {
+            res?;
         }
 
-        let opts = self
-            .options
-            .name
-            .as_ref()
-            .map(|name| CreateContainerOptions {
-                name: name.clone(),
+        let mut found = matcher.matches;
+        if found.len() < count {
+            return Err(Error::LogTimeout {
+                pattern: pattern.to_string(),
+                expected: count,
+                timeout,
+                found,
+            });
+        }
+
+        found.truncate(count);
+        Ok(found)
+    }
+
+    /// Follow the container's logs until it's ready, passing each line to `on_line`
+    ///
+    /// This is for the common pattern of watching a service start up: every line (from both
+    /// stdout and stderr, starting from the very first) is passed to `on_line` until the
+    /// `readiness` condition is met, at which point the logs stop being followed and this
+    /// returns.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The container isn't ready within `timeout`, or exits first, see [`Error::ReadyTimeout`]
+    /// * For [`Readiness::Healthy`], see [`Container::wait_until_healthy`]
+    /// * For [`Readiness::Port`], the port isn't published
+    /// * Docker fails to fetch the logs, or inspect the container
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::{Container, Readiness};
+    /// use regex::Regex;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "postgres");
+    /// container.start(false).await?;
+    ///
+    /// let ready = Regex::new("ready to accept connections").unwrap();
+    /// container
+    ///     .logs_until_ready(
+    ///         &Readiness::LogMatches(ready),
+    ///         Duration::from_secs(30),
+    ///         |line| println!("[postgres] {line}"),
+    ///     )
+    ///     .await?;
+    ///
+    /// // Postgres is up, and its logs are no longer printed
+    /// # Ok(()) }
+    /// ```
+    pub async fn logs_until_ready(
+        &self,
+        readiness: &Readiness,
+        timeout: Duration,
+        mut on_line: impl FnMut(&str),
+    ) -> Result<(), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let mut logs = self.client.logs(
+            id,
+            Some(LogsOptions::<String> {
+                follow: true,
+                stdout: true,
+                stderr: true,
                 ..Default::default()
+            }),
+        );
+
+        let pattern = match readiness {
+            Readiness::LogMatches(pattern) => Some(pattern),
+            Readiness::Healthy | Readiness::Port(_) => None,
+        };
+        let is_ready = |line: &str| pattern.is_some_and(|pattern| pattern.is_match(line));
+        let follow = async {
+            let mut lines = LineSplitter::default();
+            let mut ready = false;
+            while let Some(output) = logs.try_next().await? {
+                lines.push(output, |line| {
+                    on_line(line);
+                    ready |= is_ready(line);
+                });
+                if ready {
+                    return Ok(());
+                }
+            }
+
+            // The container exited, the last lines may not have a newline
+            lines.finish(|line| {
+                on_line(line);
+                ready |= is_ready(line);
             });
-        let ContainerCreateResponse { id, warnings } =
-            self.client.create_container(opts, config).await?;
-        for warning in warnings {
-            log::warn!("{}", warning);
+            if ready {
+                return Ok(());
+            }
+            Err(Error::ReadyTimeout(timeout))
+        };
+
+        let ready = async {
+            match readiness {
+                Readiness::Healthy => self.wait_until_healthy(timeout).await,
+                Readiness::Port(port) => self.wait_for_port(*port).await,
+                Readiness::LogMatches(_) => core::future::pending().await,
+            }
+        };
+
+        let res = tokio::time::timeout(timeout, async {
+            tokio::select! {
+                res = follow => res,
+                res = ready => res,
+            }
+        })
+        .await;
+        res.unwrap_or(Err(Error::ReadyTimeout(timeout)))
+    }
+
+    /// Wait until a TCP connection can be made to the host port published for `port`
+    async fn wait_for_port(&self, port: u16) -> Result<(), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let inspect = self
+            .client
+            .inspect_container(id, None::<InspectContainerOptions>)
+            .await?;
+        let binding = inspect
+            .network_settings
+            .and_then(|settings| settings.ports)
+            .and_then(|mut ports| ports.remove(&format!("{port}/tcp")))
+            .flatten()
+            .and_then(|bindings| bindings.into_iter().next())
+            .ok_or_else(|| Error::InvalidConfig(format!("port {port}/tcp is not published")))?;
+
+        let host_port = binding
+            .host_port
+            .as_deref()
+            .and_then(|host_port| host_port.parse::<u16>().ok())
+            .ok_or_else(|| Error::InvalidConfig(format!("port {port}/tcp has no host port")))?;
+        let host_ip = match binding.host_ip.as_deref().map(IpAddr::from_str) {
+            Some(Ok(ip)) if !ip.is_unspecified() => ip,
+            Some(Ok(IpAddr::V6(_))) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            _ => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        };
+
+        while tokio::net::TcpStream::connect((host_ip, host_port))
+            .await
+            .is_err()
+        {
+            tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+
+    /// Upload a tar archive into the container, extracting it at `path`
+    ///
+    /// `path` must be an existing directory in the container.
+    ///
+    /// See also: [`Container::upload_with_progress`]
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to extract the archive (e.g. `path` does not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine");
+    /// container.create().await?;
+    ///
+    /// let archive = std::fs::read("dataset.tar").unwrap();
+    /// container.upload("/tmp", archive).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self, archive))]
+    pub async fn upload(&self, path: &str, archive: impl Into<Bytes>) -> Result<(), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        self.operation_client()
+            .upload_to_container(id, Some(upload_options(path)), archive.into())
+            .await?;
+        Ok(())
+    }
+
+    /// Upload a tar archive into the container, reporting progress as it's sent
+    ///
+    /// `progress` is called with the total number of bytes sent so far. This is otherwise
+    /// identical to [`Container::upload`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Container::upload`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine");
+    /// container.create().await?;
+    ///
+    /// let archive = std::fs::read("dataset.tar").unwrap();
+    /// let total = archive.len();
+    /// container
+    ///     .upload_with_progress("/tmp", archive, move |sent| {
+    ///         println!("Uploaded {sent}/{total} bytes");
+    ///     })
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self, archive, progress))]
+    pub async fn upload_with_progress<F>(
+        &self,
+        path: &str,
+        archive: impl Into<Bytes>,
+        progress: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(u64) + Send + 'static,
+    {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        self.client
+            .upload_to_container_streaming(
+                id,
+                Some(upload_options(path)),
+                progress_stream(archive.into(), progress),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Download `path` from the container as a tar archive
+    ///
+    /// See also: [`Container::download_with_progress`]
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to archive `path` (e.g. it does not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine");
+    /// container.start(true).await?;
+    ///
+    /// let archive = container.download("/etc/os-release").await?;
+    /// std::fs::write("os-release.tar", archive).unwrap();
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn download(&self, path: &str) -> Result<Vec<u8>, Error> {
+        self.download_with_progress(path, |_| {}).await
+    }
+
+    /// Download `path` from the container as a tar archive, reporting progress as it's received
+    ///
+    /// `progress` is called with the total number of bytes received so far. This is otherwise
+    /// identical to [`Container::download`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Container::download`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine");
+    /// container.start(true).await?;
+    ///
+    /// let archive = container
+    ///     .download_with_progress("/usr", |received| {
+    ///         println!("Downloaded {received} bytes");
+    ///     })
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self, progress))]
+    pub async fn download_with_progress<F>(
+        &self,
+        path: &str,
+        mut progress: F,
+    ) -> Result<Vec<u8>, Error>
+    where
+        F: FnMut(u64),
+    {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let mut stream = self
+            .client
+            .download_from_container(id, Some(DownloadFromContainerOptions { path }));
+
+        let mut archive = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            archive.extend_from_slice(&chunk?);
+            progress(archive.len() as u64);
+        }
+
+        Ok(archive)
+    }
+
+    /// Stream a tar archive into the container, extracting it into `dest_dir`
+    ///
+    /// Unlike [`Container::upload`], the archive is read incrementally, so it never has to be
+    /// held in memory. `dest_dir` must already exist in the container.
+    ///
+    /// See also: [`Container::copy_path_to`]
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Reading `tar` fails
+    /// * Docker fails to extract the archive (e.g. `dest_dir` does not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine");
+    /// container.create().await?;
+    ///
+    /// let archive = tokio::fs::File::open("config.tar").await?;
+    /// container.copy_to("/etc", archive).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self, tar))]
+    pub async fn copy_to<R>(&self, dest_dir: &str, tar: R) -> Result<(), Error>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let read_error = Arc::new(Mutex::new(None));
+        self.operation_client()
+            .upload_to_container_streaming(
+                id,
+                Some(upload_options(dest_dir)),
+                reader_stream(tar, read_error.clone()),
+            )
+            .await?;
+
+        // A failed read ends the stream early, so the daemon may have accepted a truncated archive
+        let read_error = read_error.lock().map_or(None, |mut error| error.take());
+        if let Some(e) = read_error {
+            return Err(Error::Io(e));
         }
 
-        self.id = Some(id);
         Ok(())
     }
 
-    /// Attempt to start the container
+    /// Copy a local file or directory into the container's `dest_dir` (equivalent to `docker cp`)
+    ///
+    /// `local_path` is archived under its own file name, so copying `./config` into `/etc`
+    /// creates `/etc/config`. Directories are copied recursively.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * `local_path` can't be read
+    /// * Docker fails to extract the archive (e.g. `dest_dir` does not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "nginx");
+    /// container.create().await?;
+    ///
+    /// // Creates `/etc/nginx/nginx.conf`
+    /// container.copy_path_to("/etc/nginx", "./nginx.conf").await?;
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self, local_path))]
+    pub async fn copy_path_to(
+        &self,
+        dest_dir: &str,
+        local_path: impl AsRef<Path>,
+    ) -> Result<(), Error> {
+        if self.id.is_none() {
+            return Err(Error::ContainerNotFound);
+        }
+
+        let local_path = local_path.as_ref().to_path_buf();
+        let archive = tokio::task::spawn_blocking(move || tar_path(&local_path))
+            .await
+            .map_err(std::io::Error::other)??;
+
+        self.upload(dest_dir, archive).await
+    }
+
+    /// Stream `src_path` out of the container as a tar archive
+    ///
+    /// Unlike [`Container::download`], the archive is yielded in chunks as it's received, so it
+    /// never has to be held in memory.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error if:
+    ///
+    /// * The container has not been created
+    /// * Docker fails to archive `src_path` (e.g. it does not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use futures_util::TryStreamExt;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine");
+    /// container.start(true).await?;
+    ///
+    /// let mut file = tokio::fs::File::create("artifacts.tar").await?;
+    /// let mut archive = container.copy_from("/artifacts");
+    /// while let Some(chunk) = archive.try_next().await? {
+    ///     file.write_all(&chunk).await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn copy_from(
+        &self,
+        src_path: &str,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + Send + Unpin + use<> {
+        let Some(id) = &self.id else {
+            return futures_util::stream::iter([Err(Error::ContainerNotFound)]).left_stream();
+        };
+
+        self.client
+            .download_from_container(
+                id,
+                Some(DownloadFromContainerOptions {
+                    path: src_path.to_string(),
+                }),
+            )
+            .map_err(Error::from)
+            .right_stream()
+    }
+
+    /// Wait for a container to exit, returning its exit code and the last `tail_lines` lines of
+    /// its logs
     ///
-    /// NOTE: If the container has not yet been created, this will attempt to call [`Container::create`] first.
+    /// stdout and stderr are combined, in the order they were written. The logs are read once the
+    /// container has exited, so this saves a round-trip when diagnosing a failed run.
     ///
-    /// `wait_for_exit` will wait for the container to exit before returning.
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The container exited with a non-zero code, and the [`WaitBehavior`] is
+    ///   [`WaitBehavior::ErrorOnNonZero`]. The log tail is included in [`Error::NonZeroExit`].
+    /// * Docker fails to wait for the container, or to fetch its logs
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
+    /// use docktopus::container::{Container, Error};
     ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), docktopus::container::Error> {
     /// let connection = DockerBuilder::new().await?;
-    /// let mut container =
-    ///     Container::new(connection.client(), "rustlang/rust").cmd(["echo", "Hello!"]);
-    ///
-    /// // We can now start our container, and the command "echo Hello!" will run.
-    /// let wait_for_exit = true;
-    /// container.start(wait_for_exit).await?;
+    /// let mut container = Container::new(connection.client(), "rustlang/rust")
+    ///     .cmd(["cargo", "test"]);
+    /// container.start(false).await?;
     ///
-    /// // Since we waited for the container to exit, we don't have to stop it.
-    /// // It can now just be removed.
-    /// container.remove(None).await?;
+    /// match container.wait_with_logs(20).await {
+    ///     Ok((_, tail)) => println!("Passed, last lines: {tail:?}"),
+    ///     Err(Error::NonZeroExit { exit_code, tail }) => {
+    ///         eprintln!("Failed with {exit_code}:\n{}", tail.join("\n"));
+    ///     }
+    ///     Err(e) => return Err(e),
+    /// }
     /// # Ok(()) }
     /// ```
     #[tracing::instrument(skip(self))]
-    pub async fn start(&mut self, wait_for_exit: bool) -> Result<(), bollard::errors::Error> {
-        if self.id.is_none() {
-            self.create().await?;
+    pub async fn wait_with_logs(&self, tail_lines: usize) -> Result<(i64, Vec<String>), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let exit_code = wait_for_exit_code(
+            &self.client,
+            id,
+            WaitCondition::NotRunning,
+            WaitBehavior::ReturnExitCode,
+        )
+        .await?;
+
+        let mut output = Vec::new();
+        let mut logs = self.client.logs(
+            id,
+            Some(LogsOptions::<String> {
+                stdout: true,
+                stderr: true,
+                tail: tail_lines.to_string(),
+                ..Default::default()
+            }),
+        );
+        while let Some(log) = logs.try_next().await? {
+            output.extend_from_slice(&log.into_bytes());
         }
 
-        log::debug!("Starting container");
-        let id = self.id.as_ref().unwrap();
-        self.client
-            .start_container(id, None::<StartContainerOptions<String>>)
-            .await?;
+        let output = String::from_utf8_lossy(&output);
+        let lines = output.lines().collect::<Vec<_>>();
+        let tail = lines[lines.len().saturating_sub(tail_lines)..]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
 
-        if wait_for_exit {
-            self.wait().await?;
+        if exit_code != 0 && self.options.wait_behavior == WaitBehavior::ErrorOnNonZero {
+            return Err(Error::NonZeroExit { exit_code, tail });
         }
 
-        Ok(())
+        Ok((exit_code, tail))
     }
 
-    /// Checks if the container has not exited and is marked as `healthy`
+    /// Subscribe to the Docker events for this container (equivalent to `docker events`)
     ///
-    /// NOTE: If the container has not yet been created, this will immediately return `None`.
+    /// Only events from the time of the call onwards are yielded, including those that happen
+    /// before the stream is first polled. The stream never ends on its own, unless the connection
+    /// to the daemon is lost, in which case it ends cleanly rather than yielding an error.
     ///
     /// # Errors
     ///
-    /// * Failed to get the list of containers
-    /// * The container status could not be parsed
+    /// The stream yields an error if:
+    ///
+    /// * The container has not been created
+    /// * The daemon rejects the subscription
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use docktopus::DockerBuilder;
     /// use docktopus::container::Container;
-    /// use std::time::Duration;
-    /// use tokio::time;
+    /// use futures_util::TryStreamExt;
     ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), docktopus::container::Error> {
     /// let connection = DockerBuilder::new().await?;
-    /// let mut container =
-    ///     Container::new(connection.client(), "rustlang/rust").cmd(["echo", "Hello!"]);
+    /// let mut container = Container::new(connection.client(), "alpine").cmd(["false"]);
+    /// container.create().await?;
     ///
-    /// let wait_for_exit = false;
-    /// container.start(wait_for_exit).await?;
+    /// // Subscribe before starting, so no events are missed
+    /// let mut events = container.events();
+    /// container.start(false).await?;
     ///
-    /// loop {
-    ///     let status = container.status().await?.unwrap();
-    ///     if status.is_active() {
-    ///         time::sleep(Duration::from_secs(5)).await;
-    ///         continue;
+    /// while let Some(event) = events.try_next().await? {
+    ///     if event.action == "die" {
+    ///         println!("Exited with {:?}", event.exit_code());
+    ///         break;
     ///     }
-    ///
-    ///     println!("Container exited!");
-    ///     break;
     /// }
     /// # Ok(()) }
     /// ```
-    pub async fn status(&self) -> Result<Option<ContainerStatus>, Error> {
-        let Some(id) = self.id.as_deref() else {
-            return Ok(None);
+    pub fn events(
+        &self,
+    ) -> impl Stream<Item = Result<ContainerEvent, Error>> + Send + Unpin + use<> {
+        let Some(id) = &self.id else {
+            return futures_util::stream::iter([Err(Error::ContainerNotFound)]).left_stream();
         };
 
-        let mut filters = HashMap::new();
-        let _ = filters.insert("id", vec![id]);
-
-        let options = Some(ListContainersOptions {
-            all: true,
-            filters,
-            ..Default::default()
-        });
+        let filters = HashMap::from([
+            (String::from("type"), vec![String::from("container")]),
+            (String::from("container"), vec![id.clone()]),
+        ]);
+        // The subscription is only sent once the stream is polled, so ask the daemon to replay
+        // anything that happens in between
+        let since = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        self.client
+            .events(Some(EventsOptions::<String> {
+                since: Some(format!("{}.{:09}", since.as_secs(), since.subsec_nanos())),
+                filters,
+                ..Default::default()
+            }))
+            .take_while(|event| {
+                let disconnected = matches!(
+                    event,
+                    Err(bollard::errors::Error::HyperResponseError { .. }
+                        | bollard::errors::Error::IOError { .. })
+                );
+                if disconnected {
+                    log::debug!("Event stream disconnected: {:?}", event);
+                }
+                futures_util::future::ready(!disconnected)
+            })
+            .map_ok(ContainerEvent::from)
+            .map_err(Error::from)
+            .right_stream()
+    }
 
-        let containers = self.client.list_containers(options).await?;
-        let Some(status) = &containers[0].state else {
-            return Ok(None);
+    /// Observe the daemon restarting the container according to its [`Container::restart_policy`]
+    ///
+    /// Built on [`Container::events`], this yields a [`RestartEvent`] each time the container
+    /// exits and the daemon decides to restart it, which gives visibility into crash loops
+    /// managed by Docker itself.
+    ///
+    /// The stream ends once the container exits and is *not* restarted (e.g. the policy is
+    /// exhausted, or the container was stopped), when the container is removed, or if the
+    /// connection to the daemon is lost.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error if:
+    ///
+    /// * The container has not been created
+    /// * The daemon rejects the subscription
+    /// * Docker fails to inspect the container after it exits
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::models::{RestartPolicy, RestartPolicyNameEnum};
+    /// use docktopus::container::Container;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .cmd(["sh", "-c", "exit 3"])
+    ///     .restart_policy(RestartPolicy {
+    ///         name: Some(RestartPolicyNameEnum::ON_FAILURE),
+    ///         maximum_retry_count: Some(5),
+    ///     });
+    /// container.create().await?;
+    ///
+    /// // Subscribe before starting, so no restarts are missed
+    /// let mut restarts = container.restart_events();
+    /// container.start(false).await?;
+    ///
+    /// while let Some(restart) = restarts.try_next().await? {
+    ///     eprintln!(
+    ///         "Restart #{} after exiting with {:?}",
+    ///         restart.restart_count, restart.exit_code
+    ///     );
+    /// }
+    ///
+    /// println!("Gave up restarting");
+    /// # Ok(()) }
+    /// ```
+    pub fn restart_events(
+        &self,
+    ) -> impl Stream<Item = Result<RestartEvent, Error>> + Send + Unpin + use<> {
+        let state = RestartState {
+            events: self.events(),
+            client: self.client.clone(),
+            id: self.id.clone().unwrap_or_default(),
+            restarts: None,
+            exit: None,
         };
 
-        ContainerStatus::from_str(status.as_str()).map(Some)
+        Box::pin(futures_util::stream::unfold(
+            Some(state),
+            |state| async move {
+                let mut state = state?;
+                loop {
+                    let event = match state.events.try_next().await {
+                        Ok(Some(event)) => event,
+                        Ok(None) => return None,
+                        Err(e) => return Some((Err(e), None)),
+                    };
+                    match event.action.as_str() {
+                        "start" => {
+                            // A start that doesn't follow an exit is a manual one, which resets
+                            // the daemon's restart count
+                            let Some(exit) = state.exit.take() else {
+                                state.restarts = Some(0);
+                                continue;
+                            };
+                            let restart_count = state.restarts.unwrap_or_default() + 1;
+                            state.restarts = Some(restart_count);
+                            let restart = RestartEvent {
+                                restart_count,
+                                exit_code: exit.exit_code(),
+                            };
+                            return Some((Ok(restart), Some(state)));
+                        }
+                        "die" => state.exit = Some(event),
+                        "destroy" => return None,
+                        _ => continue,
+                    }
+
+                    // The daemon decides whether to restart before reporting the exit
+                    let inspect = match state
+                        .client
+                        .inspect_container(&state.id, None::<InspectContainerOptions>)
+                        .await
+                    {
+                        Ok(inspect) => inspect,
+                        Err(bollard::errors::Error::DockerResponseServerError {
+                            status_code: 404,
+                            ..
+                        }) => return None,
+                        Err(e) => return Some((Err(e.into()), None)),
+                    };
+                    let restarting = inspect.state.is_some_and(|state| {
+                        state.restarting == Some(true) || state.running == Some(true)
+                    });
+                    let restart_count = inspect.restart_count.unwrap_or_default();
+                    let seen = *state
+                        .restarts
+                        .get_or_insert(restart_count - i64::from(restarting));
+
+                    // If the events are being replayed after the fact, the container may have
+                    // been restarted and have exited again since, which only shows in the count
+                    if !restarting && restart_count <= seen {
+                        return None;
+                    }
+                }
+            },
+        ))
     }
 
-    /// Stop a running container
+    /// Get the repository digest of this container's image (e.g. `alpine@sha256:...`)
     ///
-    /// NOTE: It is not an error to call this on a container that has not been started,
-    ///       it will simply do nothing.
+    /// If the container has been created, the digest of the image it was *actually* created from
+    /// is returned, otherwise the configured image reference is resolved.
+    ///
+    /// This will be `None` for images that were built locally and never pushed or pulled.
+    ///
+    /// # Errors
+    ///
+    /// * Docker inspect fails (e.g. the image is not present locally)
     ///
     /// # Examples
     ///
@@ -897,208 +6715,1144 @@ impl Container {
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), docktopus::container::Error> {
     /// let connection = DockerBuilder::new().await?;
+    /// let container = Container::new(connection.client(), "alpine:latest");
     ///
-    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    /// if let Some(digest) = container.image_digest().await? {
+    ///     println!("Running {digest}");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn image_digest(&self) -> Result<Option<String>, Error> {
+        let image = match &self.id {
+            Some(id) => self
+                .inspect(id)
+                .await?
+                .image
+                .unwrap_or_else(|| self.image.clone()),
+            None => self.image.clone(),
+        };
+
+        let inspect = self.client.inspect_image(&image).await?;
+        Ok(inspect
+            .repo_digests
+            .and_then(|digests| digests.into_iter().next()))
+    }
+
+    /// Run a command in the running container, returning its combined stdout and stderr
     ///
-    /// // Does nothing, the container isn't started
-    /// container.stop().await?;
+    /// This is the exec equivalent of [`Container::run_and_capture`], for the common case of
+    /// running a command and reading its output. stdout and stderr are interleaved in the order
+    /// they were written.
     ///
-    /// // Stops the running container
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * The command exits with a non-zero code, see [`Error::ExecFailed`]
+    /// * Docker fails to run the command (e.g. the container isn't running)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "alpine:latest").cmd(["sleep", "infinity"]);
     /// container.start(false).await?;
-    /// container.stop().await?;
+    ///
+    /// let os_release = container.exec_output(["cat", "/etc/os-release"]).await?;
+    /// println!("{os_release}");
     /// # Ok(()) }
     /// ```
     #[tracing::instrument(skip_all)]
-    pub async fn stop(&mut self) -> Result<(), bollard::errors::Error> {
+    pub async fn exec_output(
+        &self,
+        cmd: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<String, Error> {
         let Some(id) = &self.id else {
-            log::warn!("Container not started");
-            return Ok(());
+            return Err(Error::ContainerNotFound);
         };
 
-        self.client
-            .stop_container(id, None::<StopContainerOptions>)
+        let cmd: Vec<String> = cmd.into_iter().map(Into::into).collect();
+        let exec = self
+            .client
+            .create_exec(
+                id,
+                CreateExecOptions::<String> {
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    cmd: Some(cmd.clone()),
+                    ..Default::default()
+                },
+            )
             .await?;
 
-        Ok(())
+        let mut bytes = Vec::new();
+        if let StartExecResults::Attached { mut output, .. } = self
+            .client
+            .start_exec(&exec.id, None::<StartExecOptions>)
+            .await?
+        {
+            while let Some(chunk) = output.try_next().await? {
+                bytes.extend_from_slice(&chunk.into_bytes());
+            }
+        }
+        let output = String::from_utf8_lossy(&bytes).into_owned();
+
+        // The output can end slightly before the daemon records the exit code
+        let exit_code = loop {
+            let inspect = self.client.inspect_exec(&exec.id).await?;
+            if inspect.running != Some(true) {
+                break inspect.exit_code.unwrap_or_default();
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+
+        if exit_code != 0 {
+            return Err(Error::ExecFailed {
+                cmd,
+                exit_code,
+                output,
+            });
+        }
+
+        Ok(output)
     }
 
-    /// Remove a container
+    /// Attach to the container's stdin, stdout, and stderr (equivalent to `docker attach`)
     ///
-    /// NOTE: To remove a running container, a [`RemoveContainerOptions`] must be provided
-    ///       with the `force` flag set.
+    /// Unlike [`Container::logs`], this allows sending input to the container. For input to be
+    /// delivered, the container must have been created with stdin open (see the
+    /// [`Container::config_override`] example).
     ///
-    /// See also: [`bollard::container::RemoveContainerOptions`]
+    /// NOTE: Output written before attaching is only included if [`AttachOptions::logs`] is set.
+    ///
+    /// # Errors
+    ///
+    /// * The container has not been created
+    /// * Docker fails to inspect or attach to the container
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use docktopus::DockerBuilder;
-    /// use docktopus::container::Container;
+    /// use docktopus::bollard::container::Config;
+    /// use docktopus::container::{AttachOptions, Container};
+    /// use futures::StreamExt;
+    /// use tokio::io::AsyncWriteExt;
     ///
     /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .cmd(["cat"])
+    ///     .config_override(Config {
+    ///         open_stdin: Some(true),
+    ///         ..Default::default()
+    ///     });
+    /// container.start(false).await?;
     ///
-    /// let mut container = Container::new(connection.client(), "rustlang/rust");
+    /// let mut attached = container.attach(AttachOptions::new()).await?;
+    /// attached.input.write_all(b"Hello!\n").await?;
+    /// if let Some(out) = attached.output.next().await {
+    ///     print!("{}", out?);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn attach(&self, options: AttachOptions) -> Result<AttachHandle, Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
+        };
+
+        let tty = self
+            .inspect(id)
+            .await?
+            .config
+            .and_then(|config| config.tty)
+            .unwrap_or(false);
+
+        let AttachContainerResults { output, input } = self
+            .client
+            .attach_container(id, Some(options.to_options()))
+            .await?;
+
+        Ok(AttachHandle { output, input, tty })
+    }
+
+    /// Resize the container's TTY (equivalent to the resize `docker attach` does for the terminal)
     ///
-    /// // Start our container
-    /// container.start(false).await?;
+    /// This should be called after [`Container::attach`], and again whenever the local terminal
+    /// is resized, so full screen programs render correctly. It only has an effect on containers
+    /// created with a TTY.
     ///
-    /// let remove_container_options = bollard::container::RemoveContainerOptions {
-    ///     force: true,
-    ///     ..Default::default()
-    /// };
+    /// # Errors
     ///
-    /// // Kills the container and removes it
-    /// container.remove(Some(remove_container_options)).await?;
+    /// * The container has not been created
+    /// * Docker fails to resize the TTY (e.g. the container isn't running)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::container::Config;
+    /// use docktopus::container::{AttachOptions, Container};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut container = Container::new(connection.client(), "alpine")
+    ///     .cmd(["sh"])
+    ///     .config_override(Config {
+    ///         tty: Some(true),
+    ///         open_stdin: Some(true),
+    ///         ..Default::default()
+    ///     });
+    /// container.start(false).await?;
+    ///
+    /// let _attached = container.attach(AttachOptions::new()).await?;
+    /// container.resize_tty(120, 40).await?;
     /// # Ok(()) }
     /// ```
-    ///
-    /// [`RemoveContainerOptions::force`]: bollard::container::RemoveContainerOptions::force
     #[tracing::instrument(skip(self))]
-    pub async fn remove(
-        mut self,
-        options: Option<bollard::container::RemoveContainerOptions>,
-    ) -> Result<(), bollard::errors::Error> {
-        let Some(id) = self.id.take() else {
-            log::warn!("Container not started");
-            return Ok(());
+    pub async fn resize_tty(&self, width: u16, height: u16) -> Result<(), Error> {
+        let Some(id) = &self.id else {
+            return Err(Error::ContainerNotFound);
         };
 
-        self.client.remove_container(&id, options).await?;
+        self.client
+            .resize_container_tty(id, ResizeContainerTtyOptions { width, height })
+            .await?;
+
         Ok(())
     }
 
-    /// Wait for a container to exit
+    /// Run the container to completion, capturing its exit code and output
     ///
-    /// NOTE: It is not an error to call this on a container that has not been started,
-    ///       it will simply do nothing.
+    /// This creates the container (if necessary), starts it, and waits up to `wait_timeout` for
+    /// it to exit. If `wait_timeout` is `None`, this will wait indefinitely.
+    ///
+    /// # Ordering guarantee
+    ///
+    /// Output collection begins *before* the container is started, by attaching to it in the same
+    /// way `docker run` does. Reading logs only after [`Container::wait`] returns is racy, a
+    /// container that exits instantly (or is auto-removed) can lose output. Here, every byte
+    /// written by the container is captured, no matter how quickly it exits.
+    ///
+    /// A non-zero exit code is *not* an error, it is reported in [`RunOutput::exit_code`].
+    ///
+    /// # Errors
+    ///
+    /// * The container fails to be created, attached to, or started
+    /// * The container does not exit within `wait_timeout`, see [`Error::WaitTimeout`]
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use docktopus::DockerBuilder;
     /// use docktopus::container::Container;
+    /// use std::time::Duration;
     ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), docktopus::container::Error> {
     /// let connection = DockerBuilder::new().await?;
+    /// let mut container =
+    ///     Container::new(connection.client(), "alpine:latest").cmd(["echo", "Hello!"]);
     ///
-    /// let mut container = Container::new(connection.client(), "rustlang/rust");
-    ///
-    /// // Start our container
-    /// container.start(false).await?;
-    ///
-    /// // Once this returns, we know that the container has exited.
-    /// container.wait().await?;
+    /// let output = container
+    ///     .run_and_capture(Some(Duration::from_secs(30)))
+    ///     .await?;
+    /// assert_eq!(output.exit_code, 0);
+    /// assert_eq!(output.stdout, "Hello!\n");
     /// # Ok(()) }
     /// ```
-    #[tracing::instrument(skip_all)]
-    pub async fn wait(&self) -> Result<(), bollard::errors::Error> {
-        let Some(id) = &self.id else {
-            log::warn!("Container not created");
-            return Ok(());
+    #[tracing::instrument(skip(self))]
+    pub async fn run_and_capture(
+        &mut self,
+        wait_timeout: Option<Duration>,
+    ) -> Result<RunOutput, Error> {
+        if self.id.is_none() {
+            self.create().await?;
+        }
+        let id = self.id.clone().ok_or(Error::ContainerNotFound)?;
+
+        // Attach *before* starting, so nothing written between start and attach is lost
+        let AttachContainerResults { output, .. } = self
+            .client
+            .attach_container(
+                &id,
+                Some(AttachContainerOptions::<String> {
+                    stdout: Some(true),
+                    stderr: Some(true),
+                    stream: Some(true),
+                    logs: Some(true),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        log::debug!("Starting container");
+        self.client
+            .start_container(&id, None::<StartContainerOptions<String>>)
+            .await?;
+
+        let run = async {
+            let (output, exit_code) = tokio::join!(
+                demux_output(output),
+                wait_for_exit_code(
+                    &self.client,
+                    &id,
+                    WaitCondition::NotRunning,
+                    WaitBehavior::ReturnExitCode
+                )
+            );
+            Ok::<_, Error>((output?, exit_code?))
+        };
+
+        let ((stdout, stderr), exit_code) = match wait_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, run)
+                .await
+                .map_err(|_| Error::WaitTimeout(timeout))??,
+            None => run.await?,
         };
 
-        wait_for_container(&self.client, id).await?;
-        Ok(())
+        Ok(RunOutput {
+            exit_code,
+            stdout,
+            stderr,
+        })
     }
 
-    /// Fetch the container log stream
+    /// Run the container to completion, recording where and what exactly ran
     ///
-    /// NOTE: It is not an error to call this on a container that has not been started,
-    ///       it will simply do nothing and return `None`.
+    /// This is [`Container::run_and_capture`] with no timeout, returning alongside the output a
+    /// [`Provenance`] record containing the resolved image ID and digest, the daemon version and
+    /// platform, and the start/finish timestamps reported by the daemon.
     ///
-    /// See also:
+    /// # Errors
     ///
-    /// * [`bollard::container::LogsOptions`]
-    /// * [`bollard::container::LogOutput`]
+    /// * See [`Container::run_and_capture`]
+    /// * Any of the daemon queries fail
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use docktopus::DockerBuilder;
     /// use docktopus::container::Container;
-    /// use futures::StreamExt;
     ///
     /// # #[tokio::main]
-    /// # async fn main() -> Result<(), docktopus::container::Error> {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let connection = DockerBuilder::new().await?;
-    /// let mut container = Container::new(connection.client(), "rustlang/rust");
-    ///
-    /// // Start our container and wait for it to exit
-    /// container.start(true).await?;
-    ///
-    /// // We want to collect logs from stderr
-    /// let logs_options = bollard::container::LogsOptions {
-    ///     stderr: true,
-    ///     follow: true,
-    ///     ..Default::default()
-    /// };
+    /// let mut container =
+    ///     Container::new(connection.client(), "alpine:latest").cmd(["echo", "Hello!"]);
     ///
-    /// // Get our log stream
-    /// let mut logs = container
-    ///     .logs(Some(logs_options))
-    ///     .await
-    ///     .expect("logs should be present");
+    /// let (output, provenance) = container.run_with_provenance().await?;
+    /// assert_eq!(output.stdout, "Hello!\n");
     ///
-    /// // Now we want to print anything from stderr
-    /// while let Some(Ok(out)) = logs.next().await {
-    ///     if let bollard::container::LogOutput::StdErr { message } = out {
-    ///         eprintln!("Uh oh! Something was written to stderr: {:?}", message);
-    ///     }
-    /// }
+    /// // Persist the record alongside any artifacts
+    /// std::fs::write("provenance.json", serde_json::to_string(&provenance)?)?;
     /// # Ok(()) }
     /// ```
-    #[tracing::instrument(skip(self))]
-    pub async fn logs(
-        &self,
-        logs_options: Option<bollard::container::LogsOptions<String>>,
-    ) -> Option<impl Stream<Item = Result<bollard::container::LogOutput, bollard::errors::Error>>>
-    {
-        let Some(id) = &self.id else {
-            log::warn!("Container not created");
-            return None;
+    #[tracing::instrument(skip_all)]
+    pub async fn run_with_provenance(&mut self) -> Result<(RunOutput, Provenance), Error> {
+        let version = self.client.version().await?;
+
+        let output = self.run_and_capture(None).await?;
+        let id = self.id.clone().ok_or(Error::ContainerNotFound)?;
+
+        let inspect = self
+            .client
+            .inspect_container(&id, None::<InspectContainerOptions>)
+            .await?;
+        let image_digest = self.image_digest().await?;
+
+        let (started_at, finished_at) = inspect
+            .state
+            .map(|state| (state.started_at, state.finished_at))
+            .unwrap_or_default();
+
+        let platform = match (version.os, version.arch) {
+            (Some(os), Some(arch)) => Some(format!("{os}/{arch}")),
+            _ => None,
         };
 
-        Some(self.client.logs(id, logs_options))
+        let provenance = Provenance {
+            image: self.image.clone(),
+            image_id: inspect.image,
+            image_digest,
+            daemon_version: version.version,
+            api_version: version.api_version,
+            platform,
+            started_at,
+            finished_at,
+        };
+
+        Ok((output, provenance))
+    }
+}
+
+/// A [`Container`] that is force-removed when dropped
+///
+/// This is useful for tests and short-lived workloads, where an early return or panic would
+/// otherwise leak the container.
+///
+/// Removal is asynchronous, so the guard captures the current Tokio runtime when it's created,
+/// and uses it to remove the container on drop:
+///
+/// * On a multi-threaded runtime, or outside of any runtime, the drop blocks until the container
+///   is removed.
+/// * On a current-thread runtime (e.g. a default `#[tokio::test]`), blocking would deadlock, so
+///   the removal is spawned instead. It will only complete if the runtime keeps running.
+///
+/// NOTE: The guard must be created within a Tokio runtime. Otherwise, nothing can be cleaned up,
+///       and an error is logged on drop.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use docktopus::DockerBuilder;
+/// use docktopus::container::{Container, ContainerGuard};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), docktopus::container::Error> {
+/// let connection = DockerBuilder::new().await?;
+/// let mut container = ContainerGuard::new(Container::new(connection.client(), "rustlang/rust"));
+///
+/// container.start(false).await?;
+///
+/// // The container is removed here, even if the above failed
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct ContainerGuard {
+    container: Option<Container>,
+    handle: Option<tokio::runtime::Handle>,
+}
+
+impl ContainerGuard {
+    /// Guard `container`, removing it when the guard is dropped
+    #[must_use]
+    pub fn new(container: Container) -> Self {
+        let handle = tokio::runtime::Handle::try_current().ok();
+        if handle.is_none() {
+            log::warn!("ContainerGuard created outside of a Tokio runtime, it can't clean up");
+        }
+
+        Self {
+            container: Some(container),
+            handle,
+        }
+    }
+
+    /// Disarm the guard, returning the container without removing it
+    #[must_use]
+    pub fn into_inner(mut self) -> Container {
+        self.container
+            .take()
+            .unwrap_or_else(|| unreachable!("only taken on drop"))
+    }
+}
+
+impl core::ops::Deref for ContainerGuard {
+    type Target = Container;
+
+    fn deref(&self) -> &Self::Target {
+        self.container.as_ref().expect("only taken on drop")
+    }
+}
+
+impl core::ops::DerefMut for ContainerGuard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.container.as_mut().expect("only taken on drop")
+    }
+}
+
+impl Drop for ContainerGuard {
+    fn drop(&mut self) {
+        let Some(container) = self.container.take() else {
+            return;
+        };
+        let Some(id) = container.id else {
+            return;
+        };
+
+        let Some(handle) = self.handle.take() else {
+            log::error!("No Tokio runtime available, leaking container `{id}`");
+            return;
+        };
+
+        let client = container.client;
+        let remove = async move {
+            let options = RemoveContainerOptions {
+                force: true,
+                v: true,
+                ..Default::default()
+            };
+            match client.remove_container(&id, Some(options)).await {
+                Ok(()) => log::debug!("Removed guarded container `{id}`"),
+                Err(e) => log::error!("Failed to remove guarded container `{id}`: {e}"),
+            }
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            // Blocking on a current-thread runtime from within would deadlock
+            Ok(current)
+                if current.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread =>
+            {
+                drop(handle.spawn(remove));
+            }
+            Ok(_) => tokio::task::block_in_place(|| handle.block_on(remove)),
+            Err(_) => {
+                // The runtime may have been shut down since the guard was created
+                let res = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+                    handle.block_on(remove);
+                }));
+                if res.is_err() {
+                    log::error!("Tokio runtime is gone, leaking guarded container");
+                }
+            }
+        }
+    }
+}
+
+/// Splits a log stream into lines, keeping those that match a pattern
+struct LogLineMatcher<'a> {
+    pattern: &'a Regex,
+    lines: LineSplitter,
+    matches: Vec<String>,
+}
+
+impl<'a> LogLineMatcher<'a> {
+    fn new(pattern: &'a Regex) -> Self {
+        Self {
+            pattern,
+            lines: LineSplitter::default(),
+            matches: Vec::new(),
+        }
+    }
+
+    /// Feed a chunk of output, which may contain any number of (partial) lines
+    fn push(&mut self, output: LogOutput) {
+        let pattern = self.pattern;
+        let matches = &mut self.matches;
+        self.lines.push(output, |line| {
+            if pattern.is_match(line) {
+                matches.push(line.to_string());
+            }
+        });
+    }
+
+    /// Check any trailing lines that were never terminated
+    fn finish(&mut self) {
+        let pattern = self.pattern;
+        let matches = &mut self.matches;
+        self.lines.finish(|line| {
+            if pattern.is_match(line) {
+                matches.push(line.to_string());
+            }
+        });
+    }
+}
+
+/// Splits container output into lines, keeping the partial lines of stdout and stderr apart
+#[derive(Default)]
+struct LineSplitter {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+impl LineSplitter {
+    /// Feed a chunk of output, passing each complete line to `on_line` (without its newline)
+    fn push(&mut self, output: LogOutput, mut on_line: impl FnMut(&str)) {
+        let partial = match output {
+            LogOutput::StdErr { .. } => &mut self.stderr,
+            _ => &mut self.stdout,
+        };
+        partial.extend_from_slice(&output.into_bytes());
+        while let Some(end) = partial.iter().position(|b| *b == b'\n') {
+            let line = partial.drain(..=end).collect::<Vec<_>>();
+            on_line(String::from_utf8_lossy(&line).trim_end_matches(['\n', '\r']));
+        }
+    }
+
+    /// Pass any trailing lines that were never terminated to `on_line`
+    fn finish(&mut self, mut on_line: impl FnMut(&str)) {
+        for partial in [&mut self.stdout, &mut self.stderr] {
+            let line = core::mem::take(partial);
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches('\r');
+            if !line.is_empty() {
+                on_line(line);
+            }
+        }
+    }
+}
+
+/// Restrict `logs_options` to only stdout, or only stderr
+fn single_stream_options(
+    logs_options: Option<LogsOptions<String>>,
+    stdout: bool,
+) -> LogsOptions<String> {
+    LogsOptions {
+        stdout,
+        stderr: !stdout,
+        ..logs_options.unwrap_or_default()
+    }
+}
+
+/// Whether `e` is the daemon reporting that a container's image doesn't exist locally
+fn is_missing_image(e: &bollard::errors::Error) -> bool {
+    matches!(
+        e,
+        bollard::errors::Error::DockerResponseServerError {
+            status_code: 404,
+            message,
+        } if message.contains("No such image")
+    )
+}
+
+/// Check that `address` is within one of `subnets` of the same IP version
+///
+/// If the network has no known subnets of that version, the daemon is left to decide.
+fn check_address_in_subnets(
+    network: &str,
+    address: IpAddr,
+    subnets: &[IpNet],
+) -> Result<(), Error> {
+    let mut same_family = subnets
+        .iter()
+        .filter(|subnet| subnet.addr().is_ipv4() == address.is_ipv4())
+        .peekable();
+    if same_family.peek().is_none() {
+        return Ok(());
+    }
+
+    if same_family.any(|subnet| subnet.contains(&address)) {
+        return Ok(());
+    }
+
+    Err(Error::InvalidConfig(format!(
+        "address `{address}` is not within any subnet of network `{network}`"
+    )))
+}
+
+fn upload_options(path: &str) -> UploadToContainerOptions<String> {
+    UploadToContainerOptions {
+        path: path.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Split an archive into chunks, calling `progress` with the running total as each is consumed
+///
+/// The chunks share the archive's buffer, so no data is copied.
+fn progress_stream<F>(archive: Bytes, mut progress: F) -> impl Stream<Item = Bytes> + Send + 'static
+where
+    F: FnMut(u64) + Send + 'static,
+{
+    let len = archive.len();
+    let chunks = (0..len)
+        .step_by(UPLOAD_PROGRESS_CHUNK_SIZE)
+        .map(move |start| archive.slice(start..len.min(start + UPLOAD_PROGRESS_CHUNK_SIZE)));
+
+    let mut sent = 0;
+    futures_util::stream::iter(chunks).inspect(move |chunk| {
+        sent += chunk.len() as u64;
+        progress(sent);
+    })
+}
+
+/// Read `reader` as a stream of chunks, storing the first read error in `error`
+///
+/// The upload stream can't carry errors, so the caller must check `error` once it's consumed.
+fn reader_stream<R>(
+    reader: R,
+    error: Arc<Mutex<Option<std::io::Error>>>,
+) -> impl Stream<Item = Bytes> + Send + 'static
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    futures_util::stream::unfold(Some(reader), move |reader| {
+        let error = error.clone();
+        async move {
+            let mut reader = reader?;
+            let mut chunk = BytesMut::with_capacity(UPLOAD_PROGRESS_CHUNK_SIZE);
+            match reader.read_buf(&mut chunk).await {
+                Ok(0) => None,
+                Ok(_) => Some((chunk.freeze(), Some(reader))),
+                Err(e) => {
+                    if let Ok(mut error) = error.lock() {
+                        *error = Some(e);
+                    }
+                    None
+                }
+            }
+        }
+    })
+}
+
+/// Parse a memory string into bytes, as expected by the daemon
+fn parse_memory_limit(memory: &str) -> Result<i64, Error> {
+    let bytes = parse_memory_string(memory).map_err(|e| match e {
+        DockerError::InvalidResourceLimit(msg) => Error::InvalidResourceLimit(msg),
+        e => Error::InvalidResourceLimit(e.to_string()),
+    })?;
+    i64::try_from(bytes)
+        .map_err(|_| Error::InvalidResourceLimit(format!("Invalid memory value: {memory}")))
+}
+
+/// Parse a swap limit, which is either a memory string or `"-1"` for unlimited swap
+fn parse_memory_swap(swap: &str) -> Result<i64, Error> {
+    match swap {
+        "-1" => Ok(-1),
+        swap => parse_memory_limit(swap),
+    }
+}
+
+/// Resolve `host` to its first address in `family`
+async fn resolve_host(host: &str, family: IpFamily) -> Result<IpAddr, Error> {
+    let err = |reason: String| Error::HostResolution {
+        host: host.to_string(),
+        family,
+        reason,
+    };
+
+    tokio::net::lookup_host((host, 0))
+        .await
+        .map_err(|e| err(e.to_string()))?
+        .map(|addr| addr.ip())
+        .find(|ip| family.matches(*ip))
+        .ok_or_else(|| err(String::from("no matching addresses")))
+}
+
+/// Parse `key=value` labels, one per line
+fn parse_labels(content: &str) -> Result<HashMap<String, String>, Error> {
+    let mut labels = HashMap::new();
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(Error::InvalidConfig(format!(
+                "expected `key=value` on line {} of labels file",
+                line_number + 1
+            )));
+        };
+
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(Error::InvalidConfig(format!(
+                "empty label key on line {} of labels file",
+                line_number + 1
+            )));
+        }
+
+        let value = value.trim();
+        let value = ['"', '\'']
+            .into_iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(quote)
+                    .and_then(|value| value.strip_suffix(quote))
+            })
+            .unwrap_or(value);
+
+        labels.insert(key.to_string(), value.to_string());
+    }
+
+    Ok(labels)
+}
+
+/// Parse labels from a YAML map, stringifying scalar values
+fn parse_yaml_labels(content: &str) -> Result<HashMap<String, String>, Error> {
+    let map: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(content)
+        .map_err(|e| Error::InvalidConfig(format!("invalid labels file: {e}")))?;
+
+    map.into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_yaml::Value::String(value) => value,
+                serde_yaml::Value::Bool(value) => value.to_string(),
+                serde_yaml::Value::Number(value) => value.to_string(),
+                _ => {
+                    return Err(Error::InvalidConfig(format!(
+                        "label `{key}` must be a string, number, or boolean"
+                    )));
+                }
+            };
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Archive a single file or directory, named after its last path component
+fn tar_path(path: &Path) -> std::io::Result<Vec<u8>> {
+    let name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("`{}` has no file name", path.display()),
+        )
+    })?;
+
+    crate::image::tar_path_as(path, name)
+}
+
+/// Parse a `[[host_ip:]host_port:]container_port[/protocol]` port spec, see [`Container::port`]
+fn parse_port_spec(spec: &str) -> Result<(Option<IpAddr>, Option<u16>, u16, Protocol), Error> {
+    let invalid = |reason: &str| Error::InvalidConfig(format!("invalid port `{spec}`: {reason}"));
+    let parse_port = |port: &str| match port.parse::<u16>() {
+        Ok(port) if port != 0 => Ok(port),
+        _ if port.contains('-') => Err(invalid("port ranges are not supported")),
+        _ => Err(invalid(&format!("`{port}` is not a valid port number"))),
+    };
+
+    let (ports, protocol) = match spec.rsplit_once('/') {
+        Some((ports, "tcp")) => (ports, Protocol::Tcp),
+        Some((ports, "udp")) => (ports, Protocol::Udp),
+        Some((_, protocol)) => {
+            return Err(invalid(&format!(
+                "unsupported protocol `{protocol}`, expected `tcp` or `udp`"
+            )));
+        }
+        None => (spec, Protocol::Tcp),
+    };
+
+    // IPv6 addresses contain colons, so they must be bracketed
+    let (host_ip, ports) = if let Some(rest) = ports.strip_prefix('[') {
+        let (ip, rest) = rest
+            .split_once("]:")
+            .ok_or_else(|| invalid("unterminated IPv6 address"))?;
+        let ip = ip
+            .parse::<Ipv6Addr>()
+            .map_err(|_| invalid(&format!("`{ip}` is not a valid IPv6 address")))?;
+        (Some(IpAddr::V6(ip)), rest)
+    } else {
+        match ports.split(':').collect::<Vec<_>>()[..] {
+            [ip, _, _] => {
+                let host_ip = ip
+                    .parse::<IpAddr>()
+                    .map_err(|_| invalid(&format!("`{ip}` is not a valid IP address")))?;
+                let (_, ports) = ports.split_once(':').unwrap_or_default();
+                (Some(host_ip), ports)
+            }
+            _ => (None, ports),
+        }
+    };
+
+    let parts = ports.split(':').collect::<Vec<_>>();
+    let (host_port, container_port) = match parts[..] {
+        [container] if host_ip.is_none() => (None, container),
+        [host, container] => (Some(host), container),
+        _ => {
+            return Err(invalid(
+                "expected `[[host_ip:]host_port:]container_port[/protocol]`",
+            ));
+        }
+    };
+
+    let host_port = host_port
+        .filter(|port| !port.is_empty())
+        .map(parse_port)
+        .transpose()?;
+    Ok((host_ip, host_port, parse_port(container_port)?, protocol))
+}
+
+/// A duration in whole seconds, as Docker expects for stop timeouts
+///
+/// This rounds up, as a sub-second grace period rounded down to 0 would have the daemon kill the
+/// container immediately.
+pub(crate) fn duration_secs(duration: Duration) -> i64 {
+    let secs = duration
+        .as_secs()
+        .saturating_add(u64::from(duration.subsec_nanos() > 0));
+    i64::try_from(secs).unwrap_or(i64::MAX)
+}
+
+/// Normalize a capability name to the `CAP_`-prefixed, uppercase form, e.g. `net_admin` to
+/// `CAP_NET_ADMIN`
+///
+/// `ALL` is left as is.
+fn normalize_capability(cap: &str) -> String {
+    let cap = cap.trim().to_ascii_uppercase();
+    if cap == "ALL" || cap.starts_with("CAP_") {
+        return cap;
+    }
+    format!("CAP_{cap}")
+}
+
+/// Normalize a list of capabilities, removing duplicates
+fn normalize_capabilities(caps: impl IntoIterator<Item = impl AsRef<str>>) -> Vec<String> {
+    let mut normalized = Vec::new();
+    for cap in caps {
+        let cap = normalize_capability(cap.as_ref());
+        if !normalized.contains(&cap) {
+            normalized.push(cap);
+        }
+    }
+    normalized
+}
+
+/// Add the capabilities from a config override to those already set
+fn merge_capabilities(caps: &mut Option<Vec<String>>, overrides: &[String]) {
+    let caps = caps.get_or_insert_with(Vec::new);
+    for cap in normalize_capabilities(overrides) {
+        if !caps.contains(&cap) {
+            caps.push(cap);
+        }
+    }
+}
+
+/// Check a fully merged creation config for combinations the daemon would reject
+fn validate_config(config: &Config<String>) -> Result<(), Error> {
+    if config
+        .image
+        .as_deref()
+        .is_none_or(|image| image.trim().is_empty())
+    {
+        return Err(Error::InvalidConfig(String::from(
+            "no image was given, pass one to `Container::new`",
+        )));
+    }
+
+    let Some(host_config) = &config.host_config else {
+        return Ok(());
+    };
+
+    let restarts = host_config
+        .restart_policy
+        .as_ref()
+        .and_then(|policy| policy.name)
+        .is_some_and(|name| {
+            !matches!(
+                name,
+                RestartPolicyNameEnum::EMPTY | RestartPolicyNameEnum::NO
+            )
+        });
+    if restarts && host_config.auto_remove == Some(true) {
+        return Err(Error::InvalidConfig(String::from(
+            "a restart policy cannot be combined with `auto_remove`, the container would be \
+             removed instead of restarted when it exits",
+        )));
+    }
+
+    if let Some(swappiness) = host_config.memory_swappiness {
+        if !(0..=100).contains(&swappiness) {
+            return Err(Error::InvalidConfig(format!(
+                "memory swappiness must be between 0 and 100, got {swappiness}"
+            )));
+        }
+    }
+
+    // 0 means unset, and a swap limit of -1 is unlimited
+    if let (Some(memory @ 1..), Some(swap @ 1..)) = (host_config.memory, host_config.memory_swap) {
+        if swap < memory {
+            return Err(Error::InvalidConfig(format!(
+                "memory swap limit ({swap} bytes) must be at least the memory limit ({memory} bytes)"
+            )));
+        }
+    }
+
+    if let Some(nano_cpus @ ..0) = host_config.nano_cpus {
+        return Err(Error::InvalidConfig(format!(
+            "CPU limit must be positive, got {nano_cpus} nano CPUs"
+        )));
+    }
+
+    for ulimit in host_config.ulimits.iter().flatten() {
+        if let (Some(soft), Some(hard)) = (ulimit.soft, ulimit.hard) {
+            // -1 is unlimited
+            if hard != -1 && (soft == -1 || soft > hard) {
+                return Err(Error::InvalidConfig(format!(
+                    "ulimit `{}` has a soft limit ({soft}) above its hard limit ({hard})",
+                    ulimit.name.as_deref().unwrap_or_default()
+                )));
+            }
+        }
+    }
+
+    for device in host_config.blkio_weight_device.iter().flatten() {
+        let path = device.path.as_deref().unwrap_or_default();
+        if !path.starts_with('/') {
+            return Err(Error::InvalidConfig(format!(
+                "blkio weight device must be an absolute path, got `{path}`"
+            )));
+        }
+        if let Some(weight) = device.weight {
+            if !(10..=1000).contains(&weight) {
+                return Err(Error::InvalidConfig(format!(
+                    "blkio weight for `{path}` must be between 10 and 1000, got {weight}"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Wait on several containers at once, returning the id and exit code of the first to exit
+///
+/// The waits on the remaining containers are cancelled, they are left running.
+///
+/// # Errors
+///
+/// * `ids` is empty, see [`Error::InvalidConfig`]
+/// * Waiting on any container fails before one exits (e.g. it doesn't exist)
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use docktopus::DockerBuilder;
+/// use docktopus::container::wait_any;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let connection = DockerBuilder::new().await?;
+/// let workers = vec![String::from("worker-1"), String::from("worker-2")];
+///
+/// let (id, exit_code) = wait_any(connection.client(), &workers).await?;
+/// println!("`{id}` exited with {exit_code}");
+/// # Ok(()) }
+/// ```
+pub async fn wait_any(client: Arc<Docker>, ids: &[String]) -> Result<(String, i64), Error> {
+    if ids.is_empty() {
+        return Err(Error::InvalidConfig(String::from(
+            "no containers to wait on",
+        )));
+    }
+
+    let waits = ids.iter().map(|id| {
+        let client = &client;
+        Box::pin(async move {
+            let exit_code = wait_for_exit_code(
+                client,
+                id,
+                WaitCondition::NotRunning,
+                WaitBehavior::ReturnExitCode,
+            )
+            .await?;
+            Ok::<_, Error>((id.clone(), exit_code))
+        })
+    });
+
+    // Dropping the other futures cancels their waits
+    let (first, _, _) = futures_util::future::select_all(waits).await;
+    first
+}
+
+/// Whether `err` is a transport failure that is safe to retry, see [`Container::retry_policy`]
+///
+/// Responses from the daemon, even `5xx`, are never considered transient.
+fn is_transient(err: &bollard::errors::Error) -> bool {
+    use std::io::ErrorKind;
+
+    let transient_io = |err: &std::io::Error| {
+        matches!(
+            err.kind(),
+            ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::BrokenPipe
+                | ErrorKind::TimedOut
+                | ErrorKind::UnexpectedEof
+        )
+    };
+
+    match err {
+        // For `HyperLegacyError`, the request never got a response (e.g. the connection was reset)
+        bollard::errors::Error::RequestTimeoutError
+        | bollard::errors::Error::HyperLegacyError { .. } => true,
+        bollard::errors::Error::IOError { err } => transient_io(err),
+        bollard::errors::Error::HyperResponseError { err } => {
+            err.is_incomplete_message() || err.is_closed() || err.is_timeout()
+        }
+        _ => false,
+    }
+}
+
+/// Drain a container output stream, splitting it into stdout and stderr
+///
+/// TTY output (which isn't multiplexed) is treated as stdout.
+async fn demux_output(
+    mut output: impl Stream<Item = Result<LogOutput, bollard::errors::Error>> + Unpin,
+) -> Result<(String, String), bollard::errors::Error> {
+    // Chunks may split multi-byte characters, so only decode once everything has arrived
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    while let Some(out) = output.try_next().await? {
+        match out {
+            LogOutput::StdErr { message } => stderr.extend_from_slice(&message),
+            LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                stdout.extend_from_slice(&message);
+            }
+            LogOutput::StdIn { .. } => {}
+        }
+    }
+
+    Ok((
+        String::from_utf8_lossy(&stdout).into_owned(),
+        String::from_utf8_lossy(&stderr).into_owned(),
+    ))
+}
+
+/// Whether a signal was delivered, treating a `409 Conflict` (the container isn't running) as not
+fn signal_running(result: Result<(), bollard::errors::Error>) -> Result<bool, Error> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 409, ..
+        }) => Ok(false),
+        Err(e) => Err(e.into()),
     }
 }
 
-async fn wait_for_container(docker: &Docker, id: &str) -> Result<(), bollard::errors::Error> {
+/// Wait for a container to reach `condition`, returning its exit code
+///
+/// With [`WaitBehavior::ErrorOnNonZero`], a non-zero exit is returned as a
+/// [`bollard::errors::Error::DockerContainerWaitError`].
+async fn wait_for_exit_code(
+    docker: &Docker,
+    id: &str,
+    condition: WaitCondition,
+    behavior: WaitBehavior,
+) -> Result<i64, bollard::errors::Error> {
     let options = WaitContainerOptions {
-        condition: "not-running",
+        condition: condition.as_str(),
     };
 
     let mut wait_stream = docker.wait_container(id, Some(options));
 
+    let mut exit_code = 0;
     while let Some(msg) = wait_stream.next().await {
-        match msg {
+        let error = match msg {
             Ok(msg) => {
-                if msg.status_code == 0 {
-                    break;
-                }
-
-                if let Some(err) = msg.error {
-                    log::error!("Failed to wait for container: {:?}", err.message);
-                    // TODO: These aren't the same error type, is this correct?
-                    return Err(bollard::errors::Error::DockerContainerWaitError {
-                        error: err.message.unwrap_or_default(),
-                        code: msg.status_code,
-                    });
-                }
+                exit_code = msg.status_code;
+                msg.error.and_then(|err| err.message)
+            }
+            // Bollard reports non-zero exits as errors
+            Err(bollard::errors::Error::DockerContainerWaitError { error, code }) => {
+                exit_code = code;
+                Some(error)
             }
             Err(e) => {
-                match &e {
-                    bollard::errors::Error::DockerContainerWaitError { error, code } => {
-                        log::error!("Container failed with status code `{}`: {error}", code);
-                    }
-                    _ => log::error!("Container failed with error: {:?}", e),
-                }
+                log::error!("Failed to wait for container: {:?}", e);
                 return Err(e);
             }
+        };
+
+        if exit_code != 0 && behavior == WaitBehavior::ErrorOnNonZero {
+            let error = error.unwrap_or_default();
+            log::error!("Container failed with status code `{exit_code}`: {error}");
+            return Err(bollard::errors::Error::DockerContainerWaitError {
+                error,
+                code: exit_code,
+            });
         }
     }
 
-    Ok(())
+    Ok(exit_code)
 }
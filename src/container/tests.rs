@@ -0,0 +1,1996 @@
+use super::{
+    Container, ContainerEvent, ContainerStatus, DEFAULT_TMPFS_OPTIONS, DeviceMapping, Error,
+    GpuRequest, HealthStatus, IpFamily, ListFilter, LogQuery, ProvisionEvent, PullPolicy,
+    ResourceUpdate, RetryPolicy, WaitBehavior, WaitCondition, check_address_in_subnets,
+    demux_output, duration_secs, is_missing_image, is_transient, parse_labels, parse_yaml_labels,
+    reader_stream, resolve_host, tar_path, wait_any,
+};
+use crate::config::SystemRequirements;
+use crate::fake_daemon::{FakeDaemon, Response};
+use bollard::container::{LogOutput, LogsOptions};
+use bollard::models::{
+    DeviceRequest, HostConfig, ResourcesBlkioWeightDevice, RestartPolicy, RestartPolicyNameEnum,
+};
+use bollard::{API_DEFAULT_VERSION, Docker};
+use core::net::{IpAddr, Ipv4Addr};
+use core::str::FromStr;
+use futures_util::TryStreamExt;
+use ipnet::IpNet;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// A client that is never actually connected, for inspecting generated configs
+fn client() -> Arc<Docker> {
+    Arc::new(Docker::connect_with_http("http://127.0.0.1:2375", 4, API_DEFAULT_VERSION).unwrap())
+}
+
+#[test]
+fn test_labels() {
+    let container = Container::new(client(), "alpine")
+        .labels([("com.myorg.stack", "foo"), ("com.myorg.tier", "backend")]);
+
+    let config = container.container_config();
+    let labels = config.labels.unwrap();
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels["com.myorg.stack"], "foo");
+    assert_eq!(labels["com.myorg.tier"], "backend");
+}
+
+#[test]
+fn test_entrypoint_and_working_dir() {
+    let container = Container::new(client(), "alpine")
+        .entrypoint(["sh", "-c"])
+        .cmd(["echo $PWD"])
+        .working_dir("/src");
+
+    let config = container.container_config();
+    assert_eq!(config.entrypoint.unwrap(), ["sh", "-c"]);
+    assert_eq!(config.cmd.unwrap(), ["echo $PWD"]);
+    assert_eq!(config.working_dir.as_deref(), Some("/src"));
+
+    // Neither is set unless asked for, so the image's defaults apply
+    let config = Container::new(client(), "alpine").container_config();
+    assert!(config.entrypoint.is_none());
+    assert!(config.working_dir.is_none());
+
+    let container = Container::new(client(), "alpine")
+        .working_dir("/src")
+        .config_override(bollard::container::Config {
+            working_dir: Some(String::from("/app")),
+            ..Default::default()
+        });
+    assert_eq!(container.override_conflicts().len(), 1);
+    assert_eq!(
+        container.container_config().working_dir.as_deref(),
+        Some("/app")
+    );
+}
+
+#[test]
+fn test_labels_survive_unrelated_override() {
+    let container = Container::new(client(), "alpine")
+        .labels([("com.myorg.stack", "foo")])
+        .config_override(bollard::container::Config {
+            user: Some(String::from("nobody")),
+            ..Default::default()
+        });
+
+    let config = container.container_config();
+    assert_eq!(config.user.as_deref(), Some("nobody"));
+    assert_eq!(
+        config.labels,
+        Some(HashMap::from([(
+            String::from("com.myorg.stack"),
+            String::from("foo")
+        )]))
+    );
+}
+
+#[test]
+fn test_labels_override_wins() {
+    let container = Container::new(client(), "alpine")
+        .labels([("com.myorg.stack", "foo")])
+        .config_override(bollard::container::Config {
+            labels: Some(HashMap::from([(
+                String::from("com.myorg.stack"),
+                String::from("bar"),
+            )])),
+            ..Default::default()
+        });
+
+    let config = container.container_config();
+    assert_eq!(config.labels.unwrap()["com.myorg.stack"], "bar");
+}
+
+#[test]
+fn test_list_filter_options() {
+    let filter = ListFilter::new()
+        .label("com.myorg.stack=foo")
+        .label("com.myorg.tier")
+        .status(ContainerStatus::Exited);
+
+    let options = filter.to_options();
+    assert!(options.all, "a status filter should imply `all`");
+    assert_eq!(
+        options.filters["label"],
+        vec!["com.myorg.stack=foo", "com.myorg.tier"]
+    );
+    assert_eq!(options.filters["status"], vec!["exited"]);
+}
+
+#[test]
+fn test_unknown_status() {
+    let status = ContainerStatus::from_str("hibernating").unwrap();
+    assert_eq!(
+        status,
+        ContainerStatus::Unknown(String::from("hibernating"))
+    );
+    assert!(!status.is_active());
+    assert!(!status.is_usable());
+
+    let filter = ListFilter::new().status(status);
+    assert_eq!(filter.to_options().filters["status"], vec!["hibernating"]);
+}
+
+#[test]
+fn test_log_query() {
+    let since = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_500);
+    let options = LogsOptions::<String>::from(LogQuery::new().tail(10).since(since));
+    assert_eq!(
+        options,
+        LogsOptions {
+            stdout: true,
+            stderr: true,
+            since: 1_700_000_000,
+            tail: String::from("10"),
+            ..Default::default()
+        }
+    );
+
+    // Unset, everything is returned
+    let options = LogsOptions::<String>::from(LogQuery::new().stdout(false));
+    assert_eq!(options.tail, "all");
+    assert_eq!((options.stdout, options.stderr), (false, true));
+    assert_eq!((options.since, options.until), (0, 0));
+}
+
+#[test]
+fn test_list_filter_name() {
+    let filter = ListFilter::new().name(Regex::new(r"^foo-\d+$").unwrap());
+
+    assert!(filter.matches_name(Some(&[String::from("/foo-1")])));
+    assert!(!filter.matches_name(Some(&[String::from("/foo-bar")])));
+    assert!(!filter.matches_name(None));
+
+    // No name filter matches everything
+    assert!(ListFilter::new().matches_name(None));
+}
+
+#[test]
+fn test_restart_policy_with_auto_remove_rejected() {
+    let container = Container::new(client(), "alpine")
+        .restart_policy(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::ON_FAILURE),
+            maximum_retry_count: Some(3),
+        })
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                auto_remove: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+    let err = container.validate().unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidConfig(msg) if msg.contains("restart policy") && msg.contains("auto_remove")),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn test_auto_remove() {
+    let container = Container::new(client(), "alpine").auto_remove(true);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.auto_remove, Some(true));
+    assert!(container.validate().is_ok());
+
+    let err = Container::new(client(), "alpine")
+        .auto_remove(true)
+        .restart_policy(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::ALWAYS),
+            maximum_retry_count: None,
+        })
+        .validate()
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidConfig(msg) if msg.contains("auto_remove")),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn test_no_restart_policy_with_auto_remove_allowed() {
+    let container = Container::new(client(), "alpine")
+        .restart_policy(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::NO),
+            maximum_retry_count: None,
+        })
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                auto_remove: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+    assert!(container.validate().is_ok());
+}
+
+#[tokio::test]
+async fn test_progress_stream() {
+    use futures_util::StreamExt;
+    use std::sync::Mutex;
+
+    // Just over 3 chunks
+    let len = 3 * super::UPLOAD_PROGRESS_CHUNK_SIZE + 10;
+    let archive = bytes::Bytes::from(vec![7u8; len]);
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let chunks = super::progress_stream(archive.clone(), {
+        let reports = Arc::clone(&reports);
+        move |sent| reports.lock().unwrap().push(sent)
+    })
+    .collect::<Vec<_>>()
+    .await;
+
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks.concat(), archive);
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 4);
+    assert!(reports.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(reports.last().copied(), Some(len as u64));
+}
+
+#[tokio::test]
+async fn test_progress_stream_small_archive_is_one_chunk() {
+    use futures_util::StreamExt;
+
+    let chunks = super::progress_stream(bytes::Bytes::from_static(b"small"), |_| {})
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(chunks.len(), 1);
+}
+
+#[test]
+fn test_static_ip_on_primary_network() {
+    let container = Container::new(client(), "alpine")
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                network_mode: Some(String::from("topology")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .with_ipv4("topology", Ipv4Addr::new(172, 28, 5, 10))
+        .with_ipv6("topology", "fd00::10".parse().unwrap())
+        .with_ipv4("other", Ipv4Addr::new(10, 0, 0, 2));
+
+    let config = container.container_config();
+    let endpoints = config.networking_config.unwrap().endpoints_config;
+
+    // Only the primary network is configured at creation
+    assert_eq!(endpoints.len(), 1);
+    let ipam = endpoints["topology"].ipam_config.clone().unwrap();
+    assert_eq!(ipam.ipv4_address.as_deref(), Some("172.28.5.10"));
+    assert_eq!(ipam.ipv6_address.as_deref(), Some("fd00::10"));
+}
+
+#[test]
+fn test_address_in_subnets() {
+    let subnets = ["172.28.0.0/16", "fd00::/64"].map(|subnet| subnet.parse::<IpNet>().unwrap());
+
+    assert!(check_address_in_subnets("net", "172.28.5.10".parse().unwrap(), &subnets).is_ok());
+    assert!(check_address_in_subnets("net", "fd00::10".parse().unwrap(), &subnets).is_ok());
+
+    let err = check_address_in_subnets("net", "10.0.0.1".parse().unwrap(), &subnets).unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig(msg) if msg.contains("10.0.0.1")));
+    assert!(check_address_in_subnets("net", "fd01::1".parse().unwrap(), &subnets).is_err());
+
+    // Unknown subnets are left for the daemon to decide
+    let v4_only = ["172.28.0.0/16".parse::<IpNet>().unwrap()];
+    assert!(check_address_in_subnets("net", "fd01::1".parse().unwrap(), &v4_only).is_ok());
+    assert!(check_address_in_subnets("net", "10.0.0.1".parse().unwrap(), &[]).is_ok());
+}
+
+#[tokio::test]
+async fn test_health_log_not_created() {
+    let container = Container::new(client(), "alpine");
+    assert!(matches!(
+        container.health_log().await,
+        Err(Error::ContainerNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_health_log_removed() {
+    let daemon = FakeDaemon::with_handler(|_| {
+        Some(Response::status(
+            404,
+            r#"{"message":"No such container: abc"}"#,
+        ))
+    });
+    let mut container = Container::new(daemon.client(), "alpine");
+    container.id = Some(String::from("abc"));
+    assert!(matches!(
+        container.health_log().await,
+        Err(Error::ContainerNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_until_healthy_errors() {
+    // Removed while waiting
+    let daemon = FakeDaemon::with_handler(|_| {
+        Some(Response::status(
+            404,
+            r#"{"message":"No such container: abc"}"#,
+        ))
+    });
+    let mut container = Container::new(daemon.client(), "alpine");
+    container.id = Some(String::from("abc"));
+    let err = container
+        .wait_until_healthy(Duration::from_secs(5))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ContainerNotFound), "{err:?}");
+
+    // The timeout is reported, even if the health log can't be fetched afterwards
+    let daemon = FakeDaemon::with_handler(|_| {
+        Some(
+            Response::ok(r#"{"Id":"abc","State":{"Health":{"Status":"starting"}}}"#)
+                .delay(Duration::from_secs(2)),
+        )
+    });
+    let mut container = Container::new(daemon.client_with_timeout(1), "alpine");
+    container.id = Some(String::from("abc"));
+    let err = container
+        .wait_until_healthy(Duration::from_millis(200))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::HealthTimeout {
+                last_failure: None,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_read_only_with_writable() {
+    let container = Container::new(client(), "alpine")
+        .read_only_with_writable(&["/tmp", "/run"])
+        .tmpfs([("/run", "rw,size=1m")]);
+
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.readonly_rootfs, Some(true));
+    assert_eq!(
+        host_config.tmpfs,
+        Some(HashMap::from([
+            (String::from("/tmp"), String::from(DEFAULT_TMPFS_OPTIONS)),
+            (String::from("/run"), String::from("rw,size=1m")),
+        ]))
+    );
+}
+
+#[test]
+fn test_tmpfs() {
+    let container = Container::new(client(), "alpine")
+        .tmpfs([("/scratch", "rw,size=1g")])
+        .tmpfs([("/tmp", "size=64m"), ("/run", "")]);
+
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(
+        host_config.tmpfs,
+        Some(HashMap::from([
+            (String::from("/scratch"), String::from("rw,size=1g")),
+            (String::from("/tmp"), String::from("size=64m")),
+            (String::from("/run"), String::new()),
+        ]))
+    );
+}
+
+#[test]
+fn test_publish_port() {
+    use crate::config::Protocol;
+    use bollard::models::PortBinding;
+
+    let container = Container::new(client(), "alpine")
+        .publish_port(8080, 80, Protocol::Tcp)
+        .publish_port_on(Ipv4Addr::LOCALHOST.into(), 8080, 8081, Protocol::Tcp)
+        .publish_port(53, 5353, Protocol::Udp)
+        .publish_all_ports(true);
+
+    let config = container.container_config();
+    let host_config = config.host_config.unwrap();
+    let bindings = host_config.port_bindings.unwrap();
+    assert_eq!(bindings.len(), 2);
+    assert_eq!(
+        bindings["8080/tcp"],
+        Some(vec![
+            PortBinding {
+                host_ip: None,
+                host_port: Some(String::from("80")),
+            },
+            PortBinding {
+                host_ip: Some(String::from("127.0.0.1")),
+                host_port: Some(String::from("8081")),
+            },
+        ])
+    );
+    assert_eq!(
+        bindings["53/udp"],
+        Some(vec![PortBinding {
+            host_ip: None,
+            host_port: Some(String::from("5353")),
+        }])
+    );
+    assert_eq!(host_config.publish_all_ports, Some(true));
+
+    let mut exposed = config
+        .exposed_ports
+        .unwrap()
+        .into_keys()
+        .collect::<Vec<_>>();
+    exposed.sort();
+    assert_eq!(exposed, ["53/udp", "8080/tcp"]);
+}
+
+#[test]
+fn test_port_spec() {
+    use crate::config::Protocol;
+    use bollard::models::PortBinding;
+
+    let binding = |host_ip: Option<&str>, host_port: &str| PortBinding {
+        host_ip: host_ip.map(String::from),
+        host_port: Some(String::from(host_port)),
+    };
+    let bindings = |spec: &str| {
+        Container::new(client(), "alpine")
+            .port(spec)
+            .unwrap()
+            .container_config()
+            .host_config
+            .unwrap()
+            .port_bindings
+            .unwrap()
+    };
+
+    assert_eq!(bindings("80")["80/tcp"], Some(vec![binding(None, "")]));
+    assert_eq!(
+        bindings("8080:80")["80/tcp"],
+        Some(vec![binding(None, "8080")])
+    );
+    assert_eq!(
+        bindings("127.0.0.1:8080:80/udp")["80/udp"],
+        Some(vec![binding(Some("127.0.0.1"), "8080")])
+    );
+    assert_eq!(
+        bindings("127.0.0.1::80")["80/tcp"],
+        Some(vec![binding(Some("127.0.0.1"), "")])
+    );
+    assert_eq!(
+        bindings("[::1]:8080:80/tcp")["80/tcp"],
+        Some(vec![binding(Some("::1"), "8080")])
+    );
+
+    // Specs accumulate alongside the typed helpers
+    let container = Container::new(client(), "alpine")
+        .port("8080:80")
+        .unwrap()
+        .publish_port(80, 8081, Protocol::Tcp);
+    let bindings = container
+        .container_config()
+        .host_config
+        .unwrap()
+        .port_bindings
+        .unwrap();
+    assert_eq!(
+        bindings["80/tcp"],
+        Some(vec![binding(None, "8080"), binding(None, "8081")])
+    );
+
+    for spec in [
+        "",
+        "http",
+        "0",
+        "70000",
+        "8080:80/sctp",
+        "8000-8010:80",
+        "localhost:8080:80",
+        "1.2.3.4:5:6:7",
+        "[::1:8080:80",
+        "127.0.0.1:80",
+    ] {
+        let err = Container::new(client(), "alpine").port(spec).unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig(msg) if msg.contains("invalid port")),
+            "unexpected error for `{spec}`: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn test_ulimits() {
+    use bollard::models::ResourcesUlimits;
+
+    let ulimit = |name: &str, soft, hard| ResourcesUlimits {
+        name: Some(String::from(name)),
+        soft: Some(soft),
+        hard: Some(hard),
+    };
+
+    let container = Container::new(client(), "alpine")
+        .ulimit("nofile", 1024, 1024)
+        .ulimit("nproc", 4096, 8192)
+        .ulimit("nofile", 65536, 65536);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(
+        host_config.ulimits.unwrap(),
+        [ulimit("nproc", 4096, 8192), ulimit("nofile", 65536, 65536)]
+    );
+    assert!(container.validate().is_ok());
+
+    let err = Container::new(client(), "alpine")
+        .ulimit("nofile", 2048, 1024)
+        .validate()
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidConfig(msg) if msg.contains("nofile")),
+        "unexpected error: {err:?}"
+    );
+
+    // -1 is unlimited
+    assert!(
+        Container::new(client(), "alpine")
+            .ulimit("memlock", 1024, -1)
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        Container::new(client(), "alpine")
+            .ulimit("memlock", -1, 1024)
+            .validate()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_sysctls() {
+    let container = Container::new(client(), "alpine")
+        .sysctl("net.ipv4.ip_forward", "1")
+        .sysctl("net.core.somaxconn", "128")
+        .sysctl("net.core.somaxconn", "1024");
+
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(
+        host_config.sysctls,
+        Some(HashMap::from([
+            (String::from("net.ipv4.ip_forward"), String::from("1")),
+            (String::from("net.core.somaxconn"), String::from("1024")),
+        ]))
+    );
+}
+
+#[test]
+fn test_log_config() {
+    let container = Container::new(client(), "alpine")
+        .log_driver("json-file")
+        .log_options([("max-size", "10m"), ("max-file", "3")]);
+    let log_config = container
+        .container_config()
+        .host_config
+        .unwrap()
+        .log_config
+        .unwrap();
+    assert_eq!(log_config.typ.as_deref(), Some("json-file"));
+    assert_eq!(
+        log_config.config,
+        Some(HashMap::from([
+            (String::from("max-size"), String::from("10m")),
+            (String::from("max-file"), String::from("3")),
+        ]))
+    );
+
+    // The daemon's default driver is kept if only options are given
+    let log_config = Container::new(client(), "alpine")
+        .log_options([("max-size", "10m")])
+        .container_config()
+        .host_config
+        .unwrap()
+        .log_config
+        .unwrap();
+    assert_eq!(log_config.typ, None);
+
+    let host_config = Container::new(client(), "alpine")
+        .container_config()
+        .host_config
+        .unwrap();
+    assert!(host_config.log_config.is_none());
+}
+
+#[test]
+fn test_is_missing_image() {
+    let missing = bollard::errors::Error::DockerResponseServerError {
+        status_code: 404,
+        message: String::from("No such image: alpine:does-not-exist"),
+    };
+    assert!(is_missing_image(&missing));
+
+    let missing_network = bollard::errors::Error::DockerResponseServerError {
+        status_code: 404,
+        message: String::from("network foo not found"),
+    };
+    assert!(!is_missing_image(&missing_network));
+}
+
+#[test]
+fn test_single_stream_options() {
+    let options = bollard::container::LogsOptions {
+        stdout: true,
+        stderr: true,
+        tail: String::from("10"),
+        ..Default::default()
+    };
+
+    let stdout = super::single_stream_options(Some(options.clone()), true);
+    assert!(stdout.stdout && !stdout.stderr);
+    assert_eq!(stdout.tail, "10");
+
+    let stderr = super::single_stream_options(Some(options), false);
+    assert!(!stderr.stdout && stderr.stderr);
+    assert_eq!(stderr.tail, "10");
+
+    let default = super::single_stream_options(None, false);
+    assert!(!default.stdout && default.stderr);
+}
+
+#[test]
+fn test_healthcheck() {
+    let container = Container::new(client(), "alpine").healthcheck(
+        vec![String::from("CMD"), String::from("true")],
+        Duration::from_secs(2),
+        Duration::from_millis(500),
+        3,
+        Duration::from_secs(10),
+    );
+
+    let healthcheck = container.container_config().healthcheck.unwrap();
+    assert_eq!(
+        healthcheck.test,
+        Some(vec![String::from("CMD"), String::from("true")])
+    );
+    assert_eq!(healthcheck.interval, Some(2_000_000_000));
+    assert_eq!(healthcheck.timeout, Some(500_000_000));
+    assert_eq!(healthcheck.retries, Some(3));
+    assert_eq!(healthcheck.start_period, Some(10_000_000_000));
+}
+
+#[test]
+fn test_health_status_from_inspect() {
+    use bollard::models::{ContainerInspectResponse, ContainerState, Health, HealthStatusEnum};
+
+    let inspect = |status: Option<HealthStatusEnum>| ContainerInspectResponse {
+        state: Some(ContainerState {
+            health: Some(Health {
+                status,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    for (status, expected) in [
+        (Some(HealthStatusEnum::STARTING), HealthStatus::Starting),
+        (Some(HealthStatusEnum::HEALTHY), HealthStatus::Healthy),
+        (Some(HealthStatusEnum::UNHEALTHY), HealthStatus::Unhealthy),
+        (Some(HealthStatusEnum::NONE), HealthStatus::None),
+        (Some(HealthStatusEnum::EMPTY), HealthStatus::None),
+        (None, HealthStatus::None),
+    ] {
+        assert_eq!(HealthStatus::from_inspect(&inspect(status)), expected);
+    }
+
+    // No healthcheck at all
+    let no_health = ContainerInspectResponse {
+        state: Some(ContainerState::default()),
+        ..Default::default()
+    };
+    assert_eq!(HealthStatus::from_inspect(&no_health), HealthStatus::None);
+    assert_eq!(
+        HealthStatus::from_inspect(&ContainerInspectResponse::default()),
+        HealthStatus::None
+    );
+}
+
+#[test]
+fn test_log_line_matcher() {
+    let stdout = |message: &'static [u8]| LogOutput::StdOut {
+        message: message.into(),
+    };
+    let stderr = |message: &'static [u8]| LogOutput::StdErr {
+        message: message.into(),
+    };
+
+    let pattern = Regex::new(r"worker \d+ ready").unwrap();
+    let mut matcher = super::LogLineMatcher::new(&pattern);
+
+    // Lines may be split across chunks, or share one
+    matcher.push(stdout(b"starting\nworker 1 re"));
+    matcher.push(stdout(b"ady\r\nworker 2 ready\nworker 3 fai"));
+    matcher.push(stdout(b"led\nworker 4 ready"));
+    assert_eq!(matcher.matches, ["worker 1 ready", "worker 2 ready"]);
+
+    // Partial lines of each stream are kept apart
+    matcher.push(stderr(b"worker 5 re"));
+    matcher.push(stdout(b"\n"));
+    matcher.push(stderr(b"ady\nworker 6 ready"));
+    assert_eq!(
+        matcher.matches,
+        [
+            "worker 1 ready",
+            "worker 2 ready",
+            "worker 4 ready",
+            "worker 5 ready"
+        ]
+    );
+
+    matcher.finish();
+    assert_eq!(
+        matcher.matches,
+        [
+            "worker 1 ready",
+            "worker 2 ready",
+            "worker 4 ready",
+            "worker 5 ready",
+            "worker 6 ready"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_demux_output() {
+    // "é" split across chunks
+    let chunks = [
+        LogOutput::StdOut {
+            message: b"caf\xC3"[..].into(),
+        },
+        LogOutput::StdErr {
+            message: b"oops"[..].into(),
+        },
+        LogOutput::StdOut {
+            message: b"\xA9\n"[..].into(),
+        },
+    ];
+    let (stdout, stderr) = demux_output(futures_util::stream::iter(chunks.map(Ok)))
+        .await
+        .unwrap();
+    assert_eq!(stdout, "café\n");
+    assert_eq!(stderr, "oops");
+}
+
+#[test]
+fn test_override_conflicts() {
+    let container = Container::new(client(), "alpine")
+        .cmd(["echo", "hello"])
+        .env(["FOO=BAR"])
+        .restart_policy(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::ALWAYS),
+            maximum_retry_count: None,
+        })
+        .config_override(bollard::container::Config {
+            // Same as the builder, so not a conflict
+            cmd: Some(vec![String::from("echo"), String::from("hello")]),
+            env: Some(vec![String::from("FOO=BAZ")]),
+            host_config: Some(HostConfig {
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::NO),
+                    maximum_retry_count: None,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+    let conflicts = container.override_conflicts();
+    assert_eq!(conflicts.len(), 2, "{conflicts:?}");
+    assert!(conflicts[0].starts_with("`env`") && conflicts[0].contains("FOO=BAZ"));
+    assert!(conflicts[1].starts_with("`restart_policy`"));
+    assert!(conflicts[1].contains("ALWAYS") && conflicts[1].contains("NO"));
+
+    // The override still wins
+    let restart_policy = container
+        .container_config()
+        .host_config
+        .unwrap()
+        .restart_policy
+        .unwrap();
+    assert_eq!(restart_policy.name, Some(RestartPolicyNameEnum::NO));
+}
+
+#[test]
+fn test_no_override_conflicts() {
+    let container = Container::new(client(), "alpine")
+        .cmd(["echo", "hello"])
+        .config_override(bollard::container::Config {
+            env: Some(vec![String::from("FOO=BAZ")]),
+            ..Default::default()
+        });
+
+    assert!(container.override_conflicts().is_empty());
+}
+
+#[test]
+fn test_network_and_aliases() {
+    let container = Container::new(client(), "alpine")
+        .network("backend")
+        .network_aliases(["db", "postgres"])
+        .extra_hosts(["host.docker.internal:host-gateway"]);
+
+    let config = container.container_config();
+    let host_config = config.host_config.unwrap();
+    assert_eq!(host_config.network_mode.as_deref(), Some("backend"));
+    assert_eq!(
+        host_config.extra_hosts,
+        Some(vec![String::from("host.docker.internal:host-gateway")])
+    );
+
+    let endpoints = config.networking_config.unwrap().endpoints_config;
+    assert_eq!(
+        endpoints["backend"].aliases,
+        Some(vec![String::from("db"), String::from("postgres")])
+    );
+    assert!(endpoints["backend"].ipam_config.is_none());
+}
+
+#[test]
+fn test_tar_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("app.conf");
+    std::fs::write(&file, "port = 8080").unwrap();
+    std::fs::create_dir(dir.path().join("conf.d")).unwrap();
+    std::fs::write(dir.path().join("conf.d").join("extra.conf"), "").unwrap();
+
+    let entries = |archive: Vec<u8>| -> Vec<String> {
+        tar::Archive::new(archive.as_slice())
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect()
+    };
+
+    // Entries are named relative to the path itself, not its parents
+    assert_eq!(entries(tar_path(&file).unwrap()), ["app.conf"]);
+    assert!(
+        entries(tar_path(&dir.path().join("conf.d")).unwrap())
+            .contains(&String::from("conf.d/extra.conf"))
+    );
+    assert!(tar_path(&dir.path().join("missing")).is_err());
+}
+
+#[tokio::test]
+async fn test_reader_stream() {
+    use futures_util::StreamExt;
+
+    let error = Arc::new(Mutex::new(None));
+    let data = vec![7u8; 600 * 1024];
+    let chunks: Vec<_> = reader_stream(std::io::Cursor::new(data.clone()), error.clone())
+        .collect()
+        .await;
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.concat(), data);
+    assert!(error.lock().unwrap().is_none());
+}
+
+#[test]
+fn test_parse_labels() {
+    let labels = parse_labels(
+        r#"
+# Routing
+traefik.enable=true
+traefik.http.routers.web.rule = "Host(`example.com`)"
+com.myorg.note='a = b'
+com.myorg.empty=
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(labels.len(), 4);
+    assert_eq!(labels["traefik.enable"], "true");
+    assert_eq!(
+        labels["traefik.http.routers.web.rule"],
+        "Host(`example.com`)"
+    );
+    assert_eq!(labels["com.myorg.note"], "a = b");
+    assert_eq!(labels["com.myorg.empty"], "");
+
+    assert!(matches!(
+        parse_labels("traefik.enable"),
+        Err(Error::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        parse_labels("=true"),
+        Err(Error::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_parse_yaml_labels() {
+    let labels = parse_yaml_labels(
+        "traefik.enable: true\ntraefik.http.services.web.loadbalancer.server.port: 8080\ncom.myorg.stack: foo\n",
+    )
+    .unwrap();
+
+    assert_eq!(labels["traefik.enable"], "true");
+    assert_eq!(
+        labels["traefik.http.services.web.loadbalancer.server.port"],
+        "8080"
+    );
+    assert_eq!(labels["com.myorg.stack"], "foo");
+
+    assert!(matches!(
+        parse_yaml_labels("nested:\n  key: value\n"),
+        Err(Error::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_labels_from_file_merges() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.labels");
+    std::fs::write(&path, "com.myorg.tier=frontend\ncom.myorg.port=80\n").unwrap();
+
+    let container = Container::new(client(), "alpine")
+        .labels([("com.myorg.stack", "foo"), ("com.myorg.tier", "backend")])
+        .labels_from_file(&path)
+        .unwrap();
+
+    let labels = container.container_config().labels.unwrap();
+    assert_eq!(labels.len(), 3);
+    assert_eq!(labels["com.myorg.stack"], "foo");
+    assert_eq!(labels["com.myorg.tier"], "frontend");
+
+    let err = Container::new(client(), "alpine")
+        .labels_from_file(dir.path().join("missing.labels"))
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::Io(_)), "{err:?}");
+}
+
+#[test]
+fn test_gpus() {
+    let container = Container::new(client(), "alpine").gpus(GpuRequest::all());
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(
+        host_config.device_requests,
+        Some(vec![DeviceRequest {
+            driver: Some(String::new()),
+            count: Some(-1),
+            device_ids: None,
+            capabilities: Some(vec![vec![String::from("gpu")]]),
+            options: Some(HashMap::new()),
+        }])
+    );
+
+    let container = Container::new(client(), "alpine").gpus(GpuRequest::count(2));
+    let request = &container
+        .container_config()
+        .host_config
+        .unwrap()
+        .device_requests
+        .unwrap()[0];
+    assert_eq!(request.count, Some(2));
+    assert_eq!(request.device_ids, None);
+
+    let container = Container::new(client(), "alpine")
+        .gpus(GpuRequest::device_ids(["0", "GPU-abc"]).capabilities([["compute", "utility"]]));
+    let request = &container
+        .container_config()
+        .host_config
+        .unwrap()
+        .device_requests
+        .unwrap()[0];
+    assert_eq!(request.count, None);
+    assert_eq!(
+        request.device_ids,
+        Some(vec![String::from("0"), String::from("GPU-abc")])
+    );
+    assert_eq!(
+        request.capabilities,
+        Some(vec![vec![String::from("compute"), String::from("utility")]])
+    );
+
+    // No device requests unless asked for
+    let container = Container::new(client(), "alpine");
+    assert!(
+        container
+            .container_config()
+            .host_config
+            .unwrap()
+            .device_requests
+            .is_none()
+    );
+}
+
+#[test]
+fn test_memory_swappiness() {
+    let container = Container::new(client(), "alpine").memory_swappiness(10);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.memory_swappiness, Some(10));
+    assert!(container.validate().is_ok());
+
+    for swappiness in [-1, 101] {
+        let err = Container::new(client(), "alpine")
+            .memory_swappiness(swappiness)
+            .validate()
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig(msg) if msg.contains("swappiness")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    // Overridden values are validated too
+    let container = Container::new(client(), "alpine")
+        .memory_swappiness(10)
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                memory_swappiness: Some(200),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    assert_eq!(container.override_conflicts().len(), 1);
+    assert!(container.validate().is_err());
+}
+
+#[test]
+fn test_resource_limits() {
+    let container = Container::new(client(), "alpine")
+        .memory("512M")
+        .unwrap()
+        .memory_swap("-1")
+        .unwrap()
+        .cpus(1.5)
+        .cpu_shares(512);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.memory, Some(512 * 1024 * 1024));
+    assert_eq!(host_config.memory_swap, Some(-1));
+    assert_eq!(host_config.nano_cpus, Some(1_500_000_000));
+    assert_eq!(host_config.cpu_shares, Some(512));
+    assert!(container.validate().is_ok());
+
+    let Err(err) = Container::new(client(), "alpine").memory("lots") else {
+        panic!("an invalid memory string should be rejected");
+    };
+    assert!(matches!(err, Error::InvalidResourceLimit(_)), "{err:?}");
+
+    let err = Container::new(client(), "alpine")
+        .memory("1G")
+        .unwrap()
+        .memory_swap("512M")
+        .unwrap()
+        .validate()
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidConfig(msg) if msg.contains("swap")),
+        "unexpected error: {err:?}"
+    );
+
+    assert!(
+        Container::new(client(), "alpine")
+            .cpus(-1.0)
+            .validate()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_shm_size() {
+    let container = Container::new(client(), "alpine").shm_size("256M").unwrap();
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.shm_size, Some(256 * 1024 * 1024));
+
+    let Err(err) = Container::new(client(), "alpine").shm_size("big") else {
+        panic!("an invalid shm size should be rejected");
+    };
+    assert!(matches!(err, Error::InvalidResourceLimit(_)), "{err:?}");
+}
+
+#[test]
+fn test_namespace_modes() {
+    let container = Container::new(client(), "alpine")
+        .ipc_mode("host")
+        .pid_mode("container:other");
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.ipc_mode.as_deref(), Some("host"));
+    assert_eq!(host_config.pid_mode.as_deref(), Some("container:other"));
+}
+
+#[test]
+fn test_apply_requirements() {
+    let requirements = SystemRequirements {
+        cpu_limit: Some(2.0),
+        memory_limit: Some(String::from("1G")),
+        memory_reservation: Some(String::from("512M")),
+        cpuset_cpus: Some(String::from("0-1")),
+        ..Default::default()
+    };
+
+    let container = Container::new(client(), "alpine")
+        .cpu_shares(512)
+        .apply_requirements(&requirements);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.memory, Some(1024 * 1024 * 1024));
+    assert_eq!(host_config.memory_reservation, Some(512 * 1024 * 1024));
+    assert_eq!(host_config.nano_cpus, Some(2_000_000_000));
+    assert_eq!(host_config.cpuset_cpus.as_deref(), Some("0-1"));
+    // Left unset by the requirements
+    assert_eq!(host_config.cpu_shares, Some(512));
+    assert_eq!(host_config.memory_swap, None);
+}
+
+#[test]
+fn test_blkio_device_weight() {
+    let container = Container::new(client(), "alpine")
+        .blkio_device_weight("/dev/sda", 100)
+        .blkio_device_weight("/dev/sdb", 900);
+    let host_config = container.container_config().host_config.unwrap();
+    let devices = host_config.blkio_weight_device.unwrap();
+    assert_eq!(
+        devices,
+        vec![
+            ResourcesBlkioWeightDevice {
+                path: Some(String::from("/dev/sda")),
+                weight: Some(100),
+            },
+            ResourcesBlkioWeightDevice {
+                path: Some(String::from("/dev/sdb")),
+                weight: Some(900),
+            },
+        ]
+    );
+    assert!(container.validate().is_ok());
+
+    for (device, weight) in [("/dev/sda", 5), ("/dev/sda", 1001), ("sda", 500), ("", 500)] {
+        let err = Container::new(client(), "alpine")
+            .blkio_device_weight(device, weight)
+            .validate()
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig(msg) if msg.contains("blkio")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    // Overrides replace the whole list
+    let container = Container::new(client(), "alpine")
+        .blkio_device_weight("/dev/sda", 100)
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                blkio_weight_device: Some(vec![ResourcesBlkioWeightDevice {
+                    path: Some(String::from("/dev/nvme0n1")),
+                    weight: Some(500),
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    assert_eq!(container.override_conflicts().len(), 1);
+    let host_config = container.container_config().host_config.unwrap();
+    let devices = host_config.blkio_weight_device.unwrap();
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].path.as_deref(), Some("/dev/nvme0n1"));
+}
+
+#[test]
+fn test_device_mapping_parsing() {
+    let mapping = "/dev/fuse".parse::<DeviceMapping>().unwrap();
+    assert_eq!(mapping, DeviceMapping::new("/dev/fuse"));
+
+    let mapping = "/dev/sdc:/dev/xvdc".parse::<DeviceMapping>().unwrap();
+    assert_eq!(mapping.path_on_host, "/dev/sdc");
+    assert_eq!(mapping.path_in_container, "/dev/xvdc");
+    assert_eq!(mapping.cgroup_permissions, "rwm");
+
+    let mapping = "/dev/sdc:/dev/xvdc:r".parse::<DeviceMapping>().unwrap();
+    assert_eq!(mapping.path_in_container, "/dev/xvdc");
+    assert_eq!(mapping.cgroup_permissions, "r");
+
+    // A second part that looks like permissions keeps the host path
+    let mapping = "/dev/net/tun:rw".parse::<DeviceMapping>().unwrap();
+    assert_eq!(mapping.path_in_container, "/dev/net/tun");
+    assert_eq!(mapping.cgroup_permissions, "rw");
+
+    for device in [
+        "",
+        "fuse",
+        "/dev/sdc:xvdc",
+        "/dev/sdc:/dev/xvdc:rx",
+        "/dev/sdc:/dev/xvdc:r:w",
+    ] {
+        let err = device.parse::<DeviceMapping>().unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig(msg) if msg.contains("device")),
+            "unexpected error for `{device}`: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn test_devices() {
+    let container = Container::new(client(), "alpine")
+        .devices([DeviceMapping::new("/dev/fuse")])
+        .device_str("/dev/sdc:/dev/xvdc:r")
+        .unwrap();
+    let devices = container
+        .container_config()
+        .host_config
+        .unwrap()
+        .devices
+        .unwrap();
+    assert_eq!(
+        devices,
+        vec![
+            bollard::models::DeviceMapping {
+                path_on_host: Some(String::from("/dev/fuse")),
+                path_in_container: Some(String::from("/dev/fuse")),
+                cgroup_permissions: Some(String::from("rwm")),
+            },
+            bollard::models::DeviceMapping {
+                path_on_host: Some(String::from("/dev/sdc")),
+                path_in_container: Some(String::from("/dev/xvdc")),
+                cgroup_permissions: Some(String::from("r")),
+            },
+        ]
+    );
+
+    // `devices` replaces everything set before it
+    let container = Container::new(client(), "alpine")
+        .device_str("/dev/fuse")
+        .unwrap()
+        .devices([DeviceMapping::new("/dev/net/tun")]);
+    let devices = container
+        .container_config()
+        .host_config
+        .unwrap()
+        .devices
+        .unwrap();
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].path_on_host.as_deref(), Some("/dev/net/tun"));
+}
+
+#[test]
+fn test_capabilities() {
+    let container = Container::new(client(), "alpine")
+        .cap_add(["net_admin", "CAP_NET_RAW", "NET_ADMIN"])
+        .cap_drop(["all"]);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(
+        host_config.cap_add.unwrap(),
+        ["CAP_NET_ADMIN", "CAP_NET_RAW"]
+    );
+    assert_eq!(host_config.cap_drop.unwrap(), ["ALL"]);
+
+    // Overridden capabilities are merged in
+    let container = Container::new(client(), "alpine")
+        .cap_add(["NET_ADMIN"])
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                cap_add: Some(vec![
+                    String::from("CAP_NET_ADMIN"),
+                    String::from("sys_time"),
+                ]),
+                cap_drop: Some(vec![String::from("MKNOD")]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(
+        host_config.cap_add.unwrap(),
+        ["CAP_NET_ADMIN", "CAP_SYS_TIME"]
+    );
+    assert_eq!(host_config.cap_drop.unwrap(), ["CAP_MKNOD"]);
+    assert!(container.override_conflicts().is_empty());
+}
+
+#[test]
+fn test_privileged_and_read_only_rootfs() {
+    let container = Container::new(client(), "alpine")
+        .privileged(true)
+        .read_only_rootfs(true);
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.privileged, Some(true));
+    assert_eq!(host_config.readonly_rootfs, Some(true));
+
+    // An unrelated override leaves both alone
+    let container = Container::new(client(), "alpine")
+        .privileged(true)
+        .read_only_rootfs(true)
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                shm_size: Some(64 * 1024 * 1024),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    let host_config = container.container_config().host_config.unwrap();
+    assert_eq!(host_config.privileged, Some(true));
+    assert_eq!(host_config.readonly_rootfs, Some(true));
+    assert!(container.override_conflicts().is_empty());
+
+    // But a conflicting one is reported
+    let container = Container::new(client(), "alpine")
+        .privileged(true)
+        .config_override(bollard::container::Config {
+            host_config: Some(HostConfig {
+                privileged: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    assert_eq!(container.override_conflicts().len(), 1);
+}
+
+#[test]
+fn test_container_event_from_message() {
+    use bollard::models::{EventActor, EventMessage};
+
+    let event = ContainerEvent::from(EventMessage {
+        action: Some(String::from("die")),
+        actor: Some(EventActor {
+            id: Some(String::from("a1b2c3")),
+            attributes: Some(HashMap::from([
+                (String::from("exitCode"), String::from("3")),
+                (String::from("image"), String::from("alpine")),
+            ])),
+        }),
+        time: Some(1_700_000_000),
+        time_nano: Some(1_700_000_000_123_456_789),
+        ..Default::default()
+    });
+    assert_eq!(event.action, "die");
+    assert_eq!(event.time_nano, Some(1_700_000_000_123_456_789));
+    assert_eq!(event.exit_code(), Some(3));
+    assert_eq!(event.attributes["image"], "alpine");
+
+    // Older daemons only report whole seconds
+    let event = ContainerEvent::from(EventMessage {
+        action: Some(String::from("start")),
+        time: Some(1_700_000_000),
+        ..Default::default()
+    });
+    assert_eq!(event.time_nano, Some(1_700_000_000_000_000_000));
+    assert_eq!(event.exit_code(), None);
+}
+
+#[tokio::test]
+async fn test_events_since_subscription() {
+    let daemon = FakeDaemon::serve(r#"{"Action":"die","Actor":{"Attributes":{"exitCode":"3"}}}"#);
+    let client = daemon.client();
+    let mut container = Container::new(client, "alpine");
+    container.id = Some(String::from("abc"));
+
+    let now = || {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    };
+    let before = now();
+    let mut events = container.events();
+    let after = now();
+    // Only polling sends the request, which must still cover everything since the call
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let event = events.try_next().await.unwrap().unwrap();
+    assert_eq!(event.exit_code(), Some(3));
+
+    let lines = daemon.requests();
+    let since = lines[0]
+        .split(['?', '&', ' '])
+        .find_map(|param| param.strip_prefix("since="))
+        .expect("no `since` in the request");
+    let (secs, nanos) = since.split_once('.').unwrap();
+    assert!((before..=after).contains(&secs.parse().unwrap()), "{since}");
+    assert_eq!(nanos.len(), 9);
+}
+
+#[test]
+fn test_resource_update_options() {
+    let options = ResourceUpdate {
+        memory: Some(String::from("512M")),
+        memory_swap: Some(String::from("1g")),
+        cpu_shares: Some(512),
+        cpuset_cpus: Some(String::from("0,1")),
+        nano_cpus: Some(500_000_000),
+    }
+    .to_options()
+    .unwrap();
+    assert_eq!(options.memory, Some(512 * 1024 * 1024));
+    assert_eq!(options.memory_swap, Some(1024 * 1024 * 1024));
+    assert_eq!(options.cpu_shares, Some(512));
+    assert_eq!(options.cpuset_cpus.as_deref(), Some("0,1"));
+    assert_eq!(options.nano_cpus, Some(500_000_000));
+
+    // Unset limits are left alone
+    let options = ResourceUpdate {
+        memory_swap: Some(String::from("-1")),
+        ..Default::default()
+    }
+    .to_options()
+    .unwrap();
+    assert_eq!(options.memory, None);
+    assert_eq!(options.memory_swap, Some(-1));
+
+    let err = ResourceUpdate {
+        memory: Some(String::from("12X")),
+        ..Default::default()
+    }
+    .to_options()
+    .unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidResourceLimit(msg) if msg.contains("unit")),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn test_empty_image_rejected() {
+    for image in ["", "  "] {
+        let err = Container::new(client(), image).validate().unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig(msg) if msg.contains("image")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    // An override can still supply the image
+    let container = Container::new(client(), "").config_override(bollard::container::Config {
+        image: Some(String::from("alpine")),
+        ..Default::default()
+    });
+    assert!(container.validate().is_ok());
+}
+
+#[tokio::test]
+async fn test_create_rejects_empty_image() {
+    // Rejected before the (unreachable) daemon is contacted
+    let mut container = Container::new(client(), "");
+    let err = container.create().await.unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig(_)), "{err:?}");
+    assert!(container.id().is_none());
+}
+
+#[tokio::test]
+async fn test_resolve_host() {
+    let addr = resolve_host("localhost", IpFamily::V4).await.unwrap();
+    assert_eq!(addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+    let addr = resolve_host("127.0.0.1", IpFamily::Any).await.unwrap();
+    assert!(addr.is_loopback());
+
+    // An IPv4 literal has no IPv6 address
+    let err = resolve_host("127.0.0.1", IpFamily::V6).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::HostResolution { host, family: IpFamily::V6, .. } if host == "127.0.0.1"),
+        "{err:?}"
+    );
+
+    let err = resolve_host("does-not-exist.invalid", IpFamily::Any)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::HostResolution { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_snapshot_overrides_config_image() {
+    let daemon = FakeDaemon::serve(r#"{"Id":"def","Warnings":[]}"#);
+    let mut container =
+        Container::new(daemon.client(), "alpine").config_override(bollard::container::Config {
+            image: Some(String::from("busybox")),
+            ..Default::default()
+        });
+    container.id = Some(String::from("abc"));
+
+    let image = container.snapshot_and_restart(true).await.unwrap();
+    assert_eq!(container.container_config().image, Some(image));
+}
+
+#[tokio::test]
+async fn test_client() {
+    let daemon = FakeDaemon::serve("OK");
+    let client = daemon.client();
+    let container = Container::new(client, "alpine");
+    container.client().ping().await.unwrap();
+    assert_eq!(daemon.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_status_removed() {
+    let client = FakeDaemon::serve("[]").client();
+    let mut container = Container::new(client, "alpine");
+    container.id = Some(String::from("abc"));
+    assert_eq!(container.status().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_inspect_cache() {
+    const INSPECT: &str = r#"{"Id":"abc","Config":{"Image":"alpine"},"State":{"Status":"running","Health":{"Status":"healthy"}}}"#;
+
+    // Without a TTL, every call inspects
+    let daemon = FakeDaemon::serve(INSPECT);
+    let client = daemon.client();
+    let container = Container::from_id(client, "abc").await.unwrap();
+    let base = daemon.request_count();
+    container.health_status().await.unwrap();
+    container.health_status().await.unwrap();
+    assert_eq!(daemon.request_count(), base + 2);
+
+    // Rapid calls share one inspect
+    let daemon = FakeDaemon::serve(INSPECT);
+    let client = daemon.client();
+    let mut container = Container::from_id(client, "abc")
+        .await
+        .unwrap()
+        .inspect_cache_ttl(Duration::from_secs(60));
+    let base = daemon.request_count();
+    assert_eq!(
+        container.status().await.unwrap(),
+        Some(ContainerStatus::Running)
+    );
+    assert_eq!(
+        container.health_status().await.unwrap(),
+        HealthStatus::Healthy
+    );
+    let (a, b) = tokio::join!(container.health_status(), container.health_log());
+    a.unwrap();
+    b.unwrap();
+    assert_eq!(daemon.request_count(), base + 1);
+
+    // Bypassed once cleared
+    container.clear_inspect_cache().await;
+    container.health_status().await.unwrap();
+    assert_eq!(daemon.request_count(), base + 2);
+
+    // Changing the container's state also clears it
+    container.pause().await.unwrap();
+    let base = daemon.request_count();
+    container.health_status().await.unwrap();
+    assert_eq!(daemon.request_count(), base + 1);
+
+    // Results expire after the TTL
+    let daemon = FakeDaemon::serve(INSPECT);
+    let client = daemon.client();
+    let container = Container::from_id(client, "abc")
+        .await
+        .unwrap()
+        .inspect_cache_ttl(Duration::from_millis(10));
+    let base = daemon.request_count();
+    container.health_status().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    container.health_status().await.unwrap();
+    assert_eq!(daemon.request_count(), base + 2);
+}
+
+#[tokio::test]
+async fn test_provision() {
+    use futures_util::StreamExt;
+
+    // Serves as both the pull progress and the create response
+    const BODY: &str = r#"{"Id":"abc","Warnings":[],"status":"Pulling fs layer"}"#;
+
+    let client = FakeDaemon::serve(BODY).client();
+    let mut container = Container::new(client, "alpine").pull_policy(PullPolicy::Always, false);
+    let events = container.provision().collect::<Vec<_>>().await;
+    assert!(
+        matches!(
+            &events[..],
+            [ProvisionEvent::Pull(progress), ProvisionEvent::Created(id)]
+                if progress.status == "Pulling fs layer" && id == "abc"
+        ),
+        "{events:?}"
+    );
+    assert_eq!(container.id(), Some("abc"));
+
+    // Nothing to pull
+    let client = FakeDaemon::serve(BODY).client();
+    let mut container = Container::new(client, "alpine");
+    let events = container.provision().collect::<Vec<_>>().await;
+    assert!(
+        matches!(&events[..], [ProvisionEvent::Created(id)] if id == "abc"),
+        "{events:?}"
+    );
+
+    // Invalid configs fail before anything is pulled
+    let daemon = FakeDaemon::serve(BODY);
+    let client = daemon.client();
+    let mut container = Container::new(client, "alpine")
+        .pull_policy(PullPolicy::Always, false)
+        .memory_swappiness(200);
+    let events = container.provision().collect::<Vec<_>>().await;
+    assert!(
+        matches!(
+            &events[..],
+            [ProvisionEvent::Failed(Error::InvalidConfig(_))]
+        ),
+        "{events:?}"
+    );
+    assert_eq!(daemon.request_count(), 0);
+    assert_eq!(container.id(), None);
+}
+
+#[test]
+fn test_duration_secs() {
+    assert_eq!(duration_secs(Duration::ZERO), 0);
+    assert_eq!(duration_secs(Duration::from_secs(30)), 30);
+    // A sub-second grace period must not become an immediate kill
+    assert_eq!(duration_secs(Duration::from_millis(500)), 1);
+    assert_eq!(duration_secs(Duration::from_millis(30_500)), 31);
+    assert_eq!(duration_secs(Duration::MAX), i64::MAX);
+}
+
+#[tokio::test]
+async fn test_stop_timeout() {
+    const INSPECT: &str = r#"{"Id":"abc","Config":{"Image":"alpine"}}"#;
+
+    let container = Container::new(client(), "alpine")
+        .stop_signal("SIGINT")
+        .stop_timeout(Duration::from_millis(30_500));
+    let config = container.container_config();
+    assert_eq!(config.stop_signal.as_deref(), Some("SIGINT"));
+    assert_eq!(config.stop_timeout, Some(31));
+
+    let daemon = FakeDaemon::serve(INSPECT);
+    let client = daemon.client();
+    let mut container = Container::from_id(client, "abc")
+        .await
+        .unwrap()
+        .stop_timeout(Duration::from_secs(30));
+    container.stop().await.unwrap();
+    let stop = daemon.requests().pop().unwrap();
+    assert!(
+        stop.starts_with("POST /containers/abc/stop?t=30 "),
+        "{stop}"
+    );
+
+    // Without a timeout, the daemon's default is used
+    let daemon = FakeDaemon::serve(INSPECT);
+    let client = daemon.client();
+    let mut container = Container::from_id(client, "abc").await.unwrap();
+    container.stop().await.unwrap();
+    let stop = daemon.requests().pop().unwrap();
+    assert!(stop.starts_with("POST /containers/abc/stop "), "{stop}");
+}
+
+#[tokio::test]
+async fn test_restart_timeout() {
+    const INSPECT: &str = r#"{"Id":"abc","Config":{"Image":"alpine"}}"#;
+
+    let daemon = FakeDaemon::serve(INSPECT);
+    let client = daemon.client();
+    let mut container = Container::from_id(client, "abc")
+        .await
+        .unwrap()
+        .stop_timeout(Duration::from_secs(30));
+
+    // Sub-second timeouts are rounded up, rather than killing the container immediately
+    container
+        .restart(Some(Duration::from_millis(500)))
+        .await
+        .unwrap();
+    let restart = daemon.requests().pop().unwrap();
+    assert!(
+        restart.starts_with("POST /containers/abc/restart?t=1 "),
+        "{restart}"
+    );
+
+    // Without a timeout, the container's stop timeout is used
+    container.restart(None).await.unwrap();
+    let restart = daemon.requests().pop().unwrap();
+    assert!(
+        restart.starts_with("POST /containers/abc/restart?t=30 "),
+        "{restart}"
+    );
+
+    // Falling back to the daemon's default
+    let mut container = Container::from_id(daemon.client(), "abc").await.unwrap();
+    container.restart(None).await.unwrap();
+    let restart = daemon.requests().pop().unwrap();
+    assert!(
+        restart.starts_with("POST /containers/abc/restart?t=10 "),
+        "{restart}"
+    );
+}
+
+#[tokio::test]
+async fn test_operation_timeout() {
+    const INSPECT: &str = r#"{"Id":"abc","Config":{"Image":"alpine"}}"#;
+
+    // Stopping takes longer than the client-wide timeout
+    let client = FakeDaemon::with_handler(|line| {
+        let delay = if line.starts_with("POST") {
+            Duration::from_millis(1500)
+        } else {
+            Duration::ZERO
+        };
+        Some(Response::ok(INSPECT).delay(delay))
+    })
+    .client_with_timeout(1);
+    let mut container = Container::from_id(client, "abc").await.unwrap();
+    let err = container.stop().await.unwrap_err();
+    assert!(
+        matches!(err, bollard::errors::Error::RequestTimeoutError),
+        "{err:?}"
+    );
+
+    container
+        .with_operation_timeout(Duration::from_secs(5))
+        .stop()
+        .await
+        .unwrap();
+
+    // Only the next operation is extended
+    let err = container.stop().await.unwrap_err();
+    assert!(
+        matches!(err, bollard::errors::Error::RequestTimeoutError),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_wait_any_empty() {
+    let err = wait_any(client(), &[]).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_resize_tty_not_created() {
+    let container = Container::new(client(), "alpine");
+    let err = container.resize_tty(80, 24).await.unwrap_err();
+    assert!(matches!(err, Error::ContainerNotFound), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_stop_graceful_not_created() {
+    let mut container = Container::new(client(), "alpine");
+    container
+        .stop_graceful(Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_is_running_not_created() {
+    let container = Container::new(client(), "alpine");
+    assert!(!container.is_running().await.unwrap());
+    assert!(!container.is_healthy().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_wait_for_condition() {
+    let daemon = FakeDaemon::serve(r#"{"StatusCode":0}"#);
+    let client = daemon.client();
+    let mut container = Container::new(client, "alpine");
+    container.id = Some(String::from("abc"));
+
+    assert_eq!(
+        container.wait_for(WaitCondition::NextExit).await.unwrap(),
+        0
+    );
+    let wait = daemon.requests().pop().unwrap();
+    assert!(
+        wait.starts_with("POST /containers/abc/wait?condition=next-exit "),
+        "{wait}"
+    );
+}
+
+#[tokio::test]
+async fn test_wait_behavior() {
+    let daemon = FakeDaemon::serve(r#"{"StatusCode":3}"#).client();
+    let mut container = Container::new(daemon.clone(), "alpine");
+    container.id = Some(String::from("abc"));
+    let err = container.wait().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::Bollard(bollard::errors::Error::DockerContainerWaitError { code: 3, .. })
+        ),
+        "{err:?}"
+    );
+
+    let mut container =
+        Container::new(daemon, "alpine").wait_behavior(WaitBehavior::ReturnExitCode);
+    container.id = Some(String::from("abc"));
+    assert_eq!(container.wait().await.unwrap(), 3);
+
+    let err = Container::new(client(), "alpine").wait().await.unwrap_err();
+    assert!(matches!(err, Error::ContainerNotFound), "{err:?}");
+}
+
+#[test]
+fn test_clone() {
+    let mut container = Container::new(client(), "alpine").cmd(["sleep", "300"]);
+    container.id = Some(String::from("abc"));
+
+    let clone = container.clone();
+    assert_eq!(clone.id(), Some("abc"));
+    assert_eq!(
+        clone.container_config().cmd,
+        container.container_config().cmd
+    );
+}
+
+#[test]
+fn test_error_classifiers() {
+    let not_found = Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+        status_code: 404,
+        message: String::from("No such container: abc"),
+    });
+    assert!(not_found.is_not_found());
+    assert!(matches!(
+        not_found.as_bollard(),
+        Some(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+
+    assert!(Error::ContainerNotFound.is_not_found());
+    assert!(Error::ImageNotFound(String::from("alpine")).is_not_found());
+    assert!(Error::ContainerNotFound.as_bollard().is_none());
+
+    let conflict = Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+        status_code: 409,
+        message: String::from("Conflict"),
+    });
+    assert!(!conflict.is_not_found());
+    assert!(conflict.as_bollard().is_some());
+    assert!(!Error::InvalidConfig(String::from("bad")).is_not_found());
+}
+
+#[test]
+fn test_error_conversion() {
+    use crate::DockerError;
+
+    // The bollard error survives the round trip
+    let err = Error::Bollard(bollard::errors::Error::DockerResponseServerError {
+        status_code: 404,
+        message: String::from("No such container: abc"),
+    });
+    let docker_err = DockerError::from(err);
+    assert!(matches!(
+        docker_err,
+        DockerError::BollardError(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+    assert!(Error::from(docker_err).is_not_found());
+
+    let err = Error::InvalidConfig(String::from("bad port"));
+    let message = err.to_string();
+    let docker_err = DockerError::from(err);
+    assert_eq!(docker_err.to_string(), message);
+    let err = Error::from(docker_err);
+    assert!(matches!(err, Error::InvalidConfig(_)), "{err:?}");
+    assert_eq!(err.to_string(), message);
+
+    let docker_err = DockerError::RequirementsNotMet(vec![DockerError::PortInUse {
+        port: 8080,
+        protocol: crate::config::Protocol::Tcp,
+    }]);
+    let message = docker_err.to_string();
+    let err = Error::from(docker_err);
+    assert_eq!(err.to_string(), message);
+    let docker_err = DockerError::from(err);
+    assert!(
+        matches!(docker_err, DockerError::RequirementsNotMet(_)),
+        "{docker_err:?}"
+    );
+    assert_eq!(docker_err.to_string(), message);
+}
+
+/// A daemon that drops the connection for the first `failures` requests, answering the rest
+fn flaky_daemon(failures: usize, body: &'static str) -> FakeDaemon {
+    let seen = AtomicUsize::new(0);
+    FakeDaemon::with_handler(move |_| {
+        (seen.fetch_add(1, Ordering::SeqCst) >= failures).then(|| Response::ok(body))
+    })
+}
+
+#[tokio::test]
+async fn test_retry_policy() {
+    const LIST: &str = r#"[{"Id":"abc","State":"running"}]"#;
+
+    // Fails twice, then succeeds on the third attempt
+    let daemon = flaky_daemon(2, LIST);
+    let client = daemon.client();
+    let mut container = Container::new(client, "alpine")
+        .retry_policy(RetryPolicy::new(3, Duration::from_millis(10)));
+    container.id = Some(String::from("abc"));
+    assert_eq!(
+        container.status().await.unwrap(),
+        Some(ContainerStatus::Running)
+    );
+    assert_eq!(daemon.request_count(), 3);
+
+    // Attempts are bounded
+    let daemon = flaky_daemon(2, LIST);
+    let client = daemon.client();
+    let mut container = Container::new(client, "alpine")
+        .retry_policy(RetryPolicy::new(2, Duration::from_millis(10)));
+    container.id = Some(String::from("abc"));
+    assert!(container.status().await.is_err());
+    assert_eq!(daemon.request_count(), 2);
+
+    // Without a policy, nothing is retried
+    let daemon = flaky_daemon(1, LIST);
+    let client = daemon.client();
+    let mut container = Container::new(client, "alpine");
+    container.id = Some(String::from("abc"));
+    assert!(container.status().await.is_err());
+    assert_eq!(daemon.request_count(), 1);
+}
+
+#[test]
+fn test_is_transient() {
+    assert!(is_transient(&bollard::errors::Error::RequestTimeoutError));
+    assert!(is_transient(&bollard::errors::Error::IOError {
+        err: std::io::Error::from(std::io::ErrorKind::ConnectionReset),
+    }));
+    assert!(!is_transient(&bollard::errors::Error::IOError {
+        err: std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+    }));
+    for status_code in [404, 409, 500] {
+        assert!(!is_transient(
+            &bollard::errors::Error::DockerResponseServerError {
+                status_code,
+                message: String::new(),
+            }
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_from_name() {
+    // Only exact names count, the daemon's filter is a substring match
+    let client = FakeDaemon::serve(r#"[{"Id":"abc","Names":["/web-1"]}]"#).client();
+    let err = Container::from_name(client, "web").await.unwrap_err();
+    assert!(matches!(err, Error::ContainerNotFound), "{err:?}");
+
+    let daemon =
+        FakeDaemon::serve(r#"[{"Id":"abc","Names":["/web"]},{"Id":"def","Names":["/web"]}]"#);
+    let client = daemon.client();
+    let err = Container::from_name(client, "/web").await.unwrap_err();
+    let Error::AmbiguousName { name, ids } = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(name, "web");
+    assert_eq!(ids, ["abc", "def"]);
+
+    let lines = daemon.requests();
+    assert!(
+        lines[0].starts_with("GET /containers/json?all=true"),
+        "{lines:?}"
+    );
+}
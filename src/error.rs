@@ -1,3 +1,4 @@
+use crate::config::Protocol;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -12,6 +13,15 @@ pub enum DockerError {
     #[error("Docker API error: {0}")]
     BollardError(#[from] bollard::errors::Error),
     #[cfg(feature = "deploy")]
+    #[error("Timed out after {0:?} connecting to the Docker daemon")]
+    ConnectionTimeout(std::time::Duration),
+    #[cfg(feature = "deploy")]
+    #[error("Failed to connect to the Docker daemon at {host}: {reason}")]
+    ConnectionFailed { host: String, reason: String },
+    #[cfg(feature = "deploy")]
+    #[error("Timed out after {0:?} tearing down compose services")]
+    TeardownTimeout(std::time::Duration),
+    #[cfg(feature = "deploy")]
     #[error("Invalid IPAM configuration")]
     InvalidIpamConfig,
     #[cfg(feature = "deploy")]
@@ -20,8 +30,46 @@ pub enum DockerError {
     #[cfg(feature = "deploy")]
     #[error("Network creation failed: {0}")]
     NetworkCreationError(String),
+    #[cfg(feature = "deploy")]
+    #[error(transparent)]
+    Container(Box<crate::container::Error>),
     #[error("Invalid resource limit: {0}")]
     InvalidResourceLimit(String),
     #[error("Validation error: {0}")]
     ValidationError(String),
+    #[error("Insufficient memory: {available_gb} GB available, {required_gb} GB required")]
+    InsufficientMemory { available_gb: u64, required_gb: u64 },
+    #[error("Insufficient free memory: {free_gb} GB free, {required_gb} GB required")]
+    InsufficientFreeMemory { free_gb: u64, required_gb: u64 },
+    #[error("Insufficient disk space: {available_gb} GB available, {required_gb} GB required")]
+    InsufficientDisk { available_gb: u64, required_gb: u64 },
+    #[error(
+        "Insufficient bandwidth: {measured_mbps:.1} Mbps measured, {required_mbps} Mbps required"
+    )]
+    InsufficientBandwidth {
+        measured_mbps: f64,
+        required_mbps: u64,
+    },
+    #[error("Port {port}/{protocol} is already in use")]
+    PortInUse { port: u16, protocol: Protocol },
+    #[error(
+        "System requirements not met: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    RequirementsNotMet(Vec<DockerError>),
+}
+
+#[cfg(feature = "deploy")]
+impl From<crate::container::Error> for DockerError {
+    fn from(err: crate::container::Error) -> Self {
+        use crate::container::Error;
+
+        match err {
+            Error::Bollard(e) => DockerError::BollardError(e),
+            Error::Io(e) => DockerError::FileError(e),
+            Error::InvalidResourceLimit(msg) => DockerError::InvalidResourceLimit(msg),
+            Error::Docker(e) => *e,
+            e => DockerError::Container(Box::new(e)),
+        }
+    }
 }
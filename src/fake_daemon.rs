@@ -0,0 +1,176 @@
+//! A fake Docker daemon for unit tests, speaking just enough HTTP/1.1 for bollard
+//!
+//! It runs on plain threads, so it works from both sync and async tests.
+
+use bollard::{API_DEFAULT_VERSION, Docker};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Handler = dyn Fn(&str) -> Option<Response> + Send + Sync;
+
+/// A response from a [`FakeDaemon`]
+pub(crate) struct Response {
+    status: u16,
+    body: Vec<u8>,
+    delay: Duration,
+}
+
+impl Response {
+    /// A `200 OK` with `body`
+    pub(crate) fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::status(200, body)
+    }
+
+    /// A response with the given status code and `body`
+    pub(crate) fn status(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Wait `delay` before answering
+    pub(crate) fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            201 => "Created",
+            204 => "No Content",
+            304 => "Not Modified",
+            404 => "Not Found",
+            409 => "Conflict",
+            500 => "Internal Server Error",
+            _ => "Unknown",
+        };
+
+        let mut response = format!("HTTP/1.1 {} {reason}\r\n", self.status).into_bytes();
+        // These can't have a body
+        if matches!(self.status, 204 | 304) {
+            response.extend_from_slice(b"\r\n");
+            return response;
+        }
+
+        response.extend_from_slice(
+            format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                self.body.len()
+            )
+            .as_bytes(),
+        );
+        response.extend_from_slice(&self.body);
+        response
+    }
+}
+
+/// A fake daemon listening on a local port, recording the request lines it receives (e.g.
+/// `POST /containers/abc/stop?t=30 HTTP/1.1`)
+pub(crate) struct FakeDaemon {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl FakeDaemon {
+    /// Answer every request with a `200 OK` and `body`
+    pub(crate) fn serve(body: &'static str) -> Self {
+        Self::with_handler(move |_| Some(Response::ok(body)))
+    }
+
+    /// Answer each request with `handler`, given its request line
+    ///
+    /// If `handler` returns `None`, the connection is closed without answering.
+    pub(crate) fn with_handler(
+        handler: impl Fn(&str) -> Option<Response> + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let handler: Arc<Handler> = Arc::new(handler);
+        let log = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = handler.clone();
+                let log = log.clone();
+                // The client may hang up at any point
+                std::thread::spawn(move || {
+                    let _ = serve_connection(stream, &*handler, &log);
+                });
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    /// The daemon's URL, e.g. `http://127.0.0.1:1234`
+    pub(crate) fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A client connected to the daemon, with a timeout of 5s
+    pub(crate) fn client(&self) -> Arc<Docker> {
+        self.client_with_timeout(5)
+    }
+
+    /// A client connected to the daemon, with a timeout of `timeout` seconds
+    pub(crate) fn client_with_timeout(&self, timeout: u64) -> Arc<Docker> {
+        Arc::new(Docker::connect_with_http(&self.url(), timeout, API_DEFAULT_VERSION).unwrap())
+    }
+
+    /// The request lines received so far, oldest first
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The number of requests received so far
+    pub(crate) fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+fn serve_connection(
+    mut stream: TcpStream,
+    handler: &Handler,
+    log: &Mutex<Vec<String>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 {
+                return Ok(());
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        std::io::copy(&mut (&mut reader).take(length), &mut std::io::sink())?;
+
+        let line = line.trim_end();
+        log.lock().unwrap().push(line.to_string());
+        let Some(response) = handler(line) else {
+            return Ok(());
+        };
+
+        std::thread::sleep(response.delay);
+        stream.write_all(&response.to_bytes())?;
+    }
+}
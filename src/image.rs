@@ -0,0 +1,461 @@
+//! Utilities for building and managing Docker images
+
+use bollard::Docker;
+use bollard::auth::DockerCredentials;
+use bollard::image::{BuildImageOptions, PushImageOptions, RemoveImageOptions, TagImageOptions};
+use bollard::models::{BuildInfo, ImageInspect, PushImageInfo};
+use futures::SinkExt;
+use futures::channel::mpsc;
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Image build failed: {0}")]
+    BuildFailed(String),
+    #[error("Image push failed: {0}")]
+    PushFailed(String),
+    #[error("Image `{image}` is in use: {reason}")]
+    ImageInUse { image: String, reason: String },
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    Bollard(#[from] bollard::errors::Error),
+}
+
+/// A progress event from [`Image::build`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildProgress {
+    /// A line of build output, if any
+    pub line: Option<String>,
+    /// The ID of the built image, only set once the build has succeeded
+    pub image_id: Option<String>,
+}
+
+impl BuildProgress {
+    /// Whether this is the final event of a successful build
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.image_id.is_some()
+    }
+}
+
+impl TryFrom<BuildInfo> for BuildProgress {
+    type Error = Error;
+
+    fn try_from(info: BuildInfo) -> Result<Self, Error> {
+        if let Some(error) = info.error {
+            let detail = info.error_detail.and_then(|detail| detail.message);
+            return Err(Error::BuildFailed(detail.unwrap_or(error)));
+        }
+
+        Ok(Self {
+            // Pulls of the base image only report a status
+            line: info
+                .stream
+                .or(info.status)
+                .map(|line| line.trim_end_matches('\n').to_string()),
+            image_id: info.aux.and_then(|aux| aux.id),
+        })
+    }
+}
+
+/// A progress event from [`Image::push`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushProgress {
+    /// The status text (e.g. `Pushing`, `Pushed`)
+    pub status: String,
+    /// How far along the current layer upload is, from `0.0` to `100.0`, if known
+    pub percentage: Option<f64>,
+}
+
+impl TryFrom<PushImageInfo> for PushProgress {
+    type Error = Error;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn try_from(info: PushImageInfo) -> Result<Self, Error> {
+        if let Some(error) = info.error {
+            return Err(Error::PushFailed(error));
+        }
+
+        let percentage =
+            info.progress_detail
+                .and_then(|detail| match (detail.current, detail.total) {
+                    (Some(current), Some(total)) if total > 0 => {
+                        Some((current as f64 / total as f64 * 100.0).clamp(0.0, 100.0))
+                    }
+                    _ => None,
+                });
+
+        Ok(Self {
+            status: info.status.unwrap_or_default(),
+            percentage,
+        })
+    }
+}
+
+/// A Docker image
+#[derive(Debug)]
+pub struct Image {
+    name: String,
+    client: Arc<Docker>,
+}
+
+impl Image {
+    /// Reference an existing image by name or ID
+    ///
+    /// NOTE: This doesn't check that the image exists
+    pub fn new(client: Arc<Docker>, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            client,
+        }
+    }
+
+    /// The name (or ID) of the image
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the image's details from the daemon (equivalent to `docker image inspect`)
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to inspect the image (e.g. it doesn't exist)
+    pub async fn inspect(&self) -> Result<ImageInspect, Error> {
+        Ok(self.client.inspect_image(&self.name).await?)
+    }
+
+    /// Add a tag to the image (equivalent to `docker tag`), returning the newly tagged image
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to tag the image (e.g. it doesn't exist, or `repo` is invalid)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::image::Image;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::image::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let image = Image::new(connection.client(), "myorg/app:latest");
+    /// let release = image.tag("registry.example.com/myorg/app", "v1.2.0").await?;
+    /// assert_eq!(release.name(), "registry.example.com/myorg/app:v1.2.0");
+    /// # Ok(()) }
+    /// ```
+    pub async fn tag(&self, repo: &str, tag: &str) -> Result<Image, Error> {
+        self.client
+            .tag_image(&self.name, Some(TagImageOptions { repo, tag }))
+            .await?;
+
+        Ok(Image::new(self.client.clone(), format!("{repo}:{tag}")))
+    }
+
+    /// Push the image to its registry (equivalent to `docker push`)
+    ///
+    /// If the image name has no tag, `latest` is pushed. `credentials` are only needed for
+    /// registries that require authentication. The push is complete when the stream ends.
+    ///
+    /// # Errors
+    ///
+    /// Each item will be an error if the push fails (e.g. the credentials are rejected), see
+    /// [`Error::PushFailed`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::bollard::auth::DockerCredentials;
+    /// use docktopus::image::Image;
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::image::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let image = Image::new(connection.client(), "registry.example.com/myorg/app:v1.2.0");
+    ///
+    /// let credentials = DockerCredentials {
+    ///     username: Some(String::from("ci")),
+    ///     password: std::env::var("REGISTRY_PASSWORD").ok(),
+    ///     ..Default::default()
+    /// };
+    /// let mut push = image.push(Some(credentials));
+    /// while let Some(progress) = push.next().await {
+    ///     println!("{}", progress?.status);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn push(
+        &self,
+        credentials: Option<DockerCredentials>,
+    ) -> impl Stream<Item = Result<PushProgress, Error>> + use<> {
+        let (repo, tag) = split_tag(&self.name);
+        self.client
+            .push_image(
+                repo,
+                Some(PushImageOptions {
+                    tag: tag.to_string(),
+                }),
+                credentials,
+            )
+            .map_err(Error::from)
+            .and_then(|info| futures_util::future::ready(PushProgress::try_from(info)))
+    }
+
+    /// Remove the image (equivalent to `docker image rm`)
+    ///
+    /// If the image has other tags, only this tag is removed. `force` removes the image even if
+    /// it has other tags or is used by stopped containers, `no_prune` keeps untagged parent
+    /// images.
+    ///
+    /// # Errors
+    ///
+    /// * A container is using the image, see [`Error::ImageInUse`]
+    /// * Docker fails to remove the image (e.g. it doesn't exist)
+    pub async fn remove(self, force: bool, no_prune: bool) -> Result<(), Error> {
+        let options = RemoveImageOptions {
+            force,
+            noprune: no_prune,
+        };
+        match self
+            .client
+            .remove_image(&self.name, Some(options), None)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 409,
+                message,
+            }) if message.contains("being used") || message.contains("is using") => {
+                Err(Error::ImageInUse {
+                    image: self.name,
+                    reason: message,
+                })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Build an image from a Dockerfile (equivalent to `docker build`)
+    ///
+    /// Everything under `context_dir` is sent to the daemon as the build context. `dockerfile` is
+    /// the path of the Dockerfile, relative to `context_dir`. The build runs in the background,
+    /// dropping the stream cancels it.
+    ///
+    /// The build has succeeded once an event satisfies [`BuildProgress::is_complete`]. A few
+    /// lines of output (e.g. `Successfully tagged ...`) may still follow it.
+    ///
+    /// # Errors
+    ///
+    /// Each item will be an error if:
+    ///
+    /// * The build context can't be read
+    /// * A build step fails, see [`Error::BuildFailed`]
+    /// * Docker fails to start the build
+    ///
+    /// No items follow an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::image::Image;
+    /// use futures::StreamExt;
+    /// use std::collections::HashMap;
+    /// use std::path::Path;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::image::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let build_args = HashMap::from([(String::from("RUST_VERSION"), String::from("1.85"))]);
+    ///
+    /// let mut build = Image::build(
+    ///     connection.client(),
+    ///     Path::new("."),
+    ///     "Dockerfile",
+    ///     "myorg/app:latest",
+    ///     build_args,
+    /// );
+    /// while let Some(progress) = build.next().await {
+    ///     let progress = progress?;
+    ///     if let Some(line) = progress.line {
+    ///         println!("{line}");
+    ///     }
+    ///     if let Some(id) = progress.image_id {
+    ///         println!("Built {id}");
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn build(
+        client: Arc<Docker>,
+        context_dir: &Path,
+        dockerfile: &str,
+        tag: &str,
+        build_args: HashMap<String, String>,
+    ) -> impl Stream<Item = Result<BuildProgress, Error>> + Send + Unpin + use<> {
+        let options = BuildImageOptions {
+            dockerfile: dockerfile.to_string(),
+            t: tag.to_string(),
+            buildargs: build_args,
+            // Don't leave intermediate containers behind, like `docker build`
+            rm: true,
+            ..Default::default()
+        };
+        let context_dir = context_dir.to_path_buf();
+
+        // The build stream borrows the client, so it's driven from a task that owns it
+        let (mut tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            let context =
+                match tokio::task::spawn_blocking(move || tar_path_as(&context_dir, ".")).await {
+                    Ok(Ok(context)) => context,
+                    Ok(Err(e)) => {
+                        let _ = tx.send(Err(e.into())).await;
+                        return;
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(std::io::Error::other(e).into())).await;
+                        return;
+                    }
+                };
+
+            let mut build = client.build_image(options, None, Some(context.into()));
+            while let Some(info) = build.next().await {
+                let progress = info.map_err(Error::from).and_then(BuildProgress::try_from);
+                let failed = progress.is_err();
+                if tx.send(progress).await.is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        rx
+    }
+}
+
+/// Split an image name into its repository and tag, defaulting to `latest`
+fn split_tag(name: &str) -> (&str, &str) {
+    // A `:` before the last `/` is a registry port, not a tag
+    match name.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') => (repo, tag),
+        _ => (name, "latest"),
+    }
+}
+
+/// Archive a file, or a directory and everything in it, placing it at `name` in the archive
+///
+/// Use a `name` of `.` to archive the contents of a directory, e.g. for a build context.
+/// Symlinks are followed.
+pub(crate) fn tar_path_as(path: &Path, name: impl AsRef<Path>) -> std::io::Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    if path.is_dir() {
+        builder.append_dir_all(name, path)?;
+    } else {
+        builder.append_path_with_name(path, name)?;
+    }
+    builder.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::{ErrorDetail, ImageId};
+
+    #[test]
+    fn test_build_progress() {
+        let progress = BuildProgress::try_from(BuildInfo {
+            stream: Some(String::from("Step 1/2 : FROM alpine\n")),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(progress.line.as_deref(), Some("Step 1/2 : FROM alpine"));
+        assert!(!progress.is_complete());
+
+        let progress = BuildProgress::try_from(BuildInfo {
+            aux: Some(ImageId {
+                id: Some(String::from("sha256:abc")),
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(progress.image_id.as_deref(), Some("sha256:abc"));
+        assert!(progress.is_complete());
+
+        let err = BuildProgress::try_from(BuildInfo {
+            error: Some(String::from("failed")),
+            error_detail: Some(ErrorDetail {
+                code: Some(1),
+                message: Some(String::from("The command '/bin/sh -c false' returned 1")),
+            }),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(
+            matches!(&err, Error::BuildFailed(msg) if msg.contains("returned 1")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn test_split_tag() {
+        assert_eq!(split_tag("alpine"), ("alpine", "latest"));
+        assert_eq!(split_tag("alpine:3.20"), ("alpine", "3.20"));
+        assert_eq!(
+            split_tag("localhost:5000/myorg/app"),
+            ("localhost:5000/myorg/app", "latest")
+        );
+        assert_eq!(
+            split_tag("localhost:5000/myorg/app:v1"),
+            ("localhost:5000/myorg/app", "v1")
+        );
+    }
+
+    #[test]
+    fn test_push_progress() {
+        let progress = PushProgress::try_from(PushImageInfo {
+            status: Some(String::from("Pushing")),
+            progress_detail: Some(bollard::models::ProgressDetail {
+                current: Some(50),
+                total: Some(200),
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(progress.status, "Pushing");
+        assert_eq!(progress.percentage, Some(25.0));
+
+        let err = PushProgress::try_from(PushImageInfo {
+            error: Some(String::from(
+                "denied: requested access to the resource is denied",
+            )),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(matches!(err, Error::PushFailed(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_tar_path_as() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Dockerfile"), "FROM alpine\n").unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src").join("main.rs"), "fn main() {}\n").unwrap();
+
+        let archive = tar_path_as(dir.path(), ".").unwrap();
+        let mut paths = tar::Archive::new(archive.as_slice())
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .filter(|path| !path.ends_with('/'))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, ["Dockerfile", "src", "src/main.rs"]);
+    }
+}
@@ -10,6 +10,8 @@ pub mod config;
 pub mod error;
 pub mod parser;
 
+#[cfg(all(test, feature = "deploy"))]
+mod fake_daemon;
 #[cfg(test)]
 mod test_fixtures;
 
@@ -18,6 +20,16 @@ pub mod builder;
 #[cfg(feature = "deploy")]
 pub use builder::DockerBuilder;
 #[cfg(feature = "deploy")]
+pub mod compose;
+#[cfg(feature = "deploy")]
 pub mod container;
 #[cfg(feature = "deploy")]
+pub mod image;
+#[cfg(feature = "deploy")]
+pub mod network;
+#[cfg(feature = "deploy")]
+pub mod stack;
+#[cfg(feature = "deploy")]
+pub mod volume;
+#[cfg(feature = "deploy")]
 pub use bollard;
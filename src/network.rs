@@ -0,0 +1,316 @@
+//! Utilities for creating and managing Docker networks
+
+use bollard::Docker;
+use bollard::models::{Ipam, IpamConfig, NetworkCreateResponse};
+use bollard::network::{
+    ConnectNetworkOptions, CreateNetworkOptions, DisconnectNetworkOptions, InspectNetworkOptions,
+};
+use core::net::IpAddr;
+use ipnet::IpNet;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Invalid network configuration: {0}")]
+    InvalidConfig(String),
+    #[error("{0}")]
+    Bollard(#[from] bollard::errors::Error),
+}
+
+/// Options for [`Network::create`]
+///
+/// By default, a non-internal `bridge` network is created, with a subnet chosen by Docker.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::network::NetworkOptions;
+///
+/// let options = NetworkOptions::new()
+///     .driver("bridge")
+///     .labels([("env", "test")])
+///     .internal(true)
+///     .subnet("10.5.0.0/16".parse().unwrap())
+///     .gateway("10.5.0.1".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NetworkOptions {
+    driver: Option<String>,
+    labels: HashMap<String, String>,
+    internal: bool,
+    subnet: Option<IpNet>,
+    gateway: Option<IpAddr>,
+}
+
+impl NetworkOptions {
+    /// Create a new set of options, using Docker's defaults
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the network driver (equivalent to `--driver`), defaults to `bridge`
+    #[must_use]
+    pub fn driver(mut self, driver: impl Into<String>) -> Self {
+        self.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the labels for the network
+    ///
+    /// NOTE: This will override any existing labels
+    #[must_use]
+    pub fn labels(
+        mut self,
+        labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.labels = labels
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+
+    /// Restrict external access to the network (equivalent to `--internal`)
+    #[must_use]
+    pub fn internal(mut self, internal: bool) -> Self {
+        self.internal = internal;
+        self
+    }
+
+    /// Set the network's subnet (equivalent to `--subnet`)
+    #[must_use]
+    pub fn subnet(mut self, subnet: IpNet) -> Self {
+        self.subnet = Some(subnet);
+        self
+    }
+
+    /// Set the network's gateway (equivalent to `--gateway`)
+    ///
+    /// This requires a [`NetworkOptions::subnet`] containing the address.
+    #[must_use]
+    pub fn gateway(mut self, gateway: IpAddr) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    fn to_create_options(&self, name: &str) -> Result<CreateNetworkOptions<String>, Error> {
+        let ipam = match (self.subnet, self.gateway) {
+            (None, None) => Ipam::default(),
+            (None, Some(gateway)) => {
+                return Err(Error::InvalidConfig(format!(
+                    "gateway `{gateway}` requires a subnet"
+                )));
+            }
+            (Some(subnet), gateway) => {
+                if let Some(gateway) = gateway {
+                    if !subnet.contains(&gateway) {
+                        return Err(Error::InvalidConfig(format!(
+                            "gateway `{gateway}` is outside of subnet `{subnet}`"
+                        )));
+                    }
+                }
+
+                Ipam {
+                    config: Some(vec![IpamConfig {
+                        subnet: Some(subnet.to_string()),
+                        gateway: gateway.map(|gateway| gateway.to_string()),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }
+            }
+        };
+
+        Ok(CreateNetworkOptions {
+            name: name.to_string(),
+            driver: self
+                .driver
+                .clone()
+                .unwrap_or_else(|| String::from("bridge")),
+            internal: self.internal,
+            ipam,
+            labels: self.labels.clone(),
+            ..Default::default()
+        })
+    }
+}
+
+/// A Docker network
+///
+/// Unlike [`Container`](crate::container::Container), a `Network` always exists on the daemon,
+/// from [`Network::create`] until [`Network::remove`].
+#[derive(Debug)]
+pub struct Network {
+    id: String,
+    name: String,
+    client: Arc<Docker>,
+}
+
+impl Network {
+    /// Create a new network (equivalent to `docker network create`)
+    ///
+    /// # Errors
+    ///
+    /// * The options are invalid, see [`Error::InvalidConfig`]
+    /// * Docker fails to create the network (e.g. the name is taken, or the subnet overlaps)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use docktopus::network::{Network, NetworkOptions};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let network = Network::create(
+    ///     connection.client(),
+    ///     "backend",
+    ///     NetworkOptions::new().subnet("10.5.0.0/16".parse()?),
+    /// )
+    /// .await?;
+    ///
+    /// let mut container = Container::new(connection.client(), "alpine:latest");
+    /// container.create().await?;
+    /// network.connect(container.id().unwrap()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create(
+        client: Arc<Docker>,
+        name: impl Into<String>,
+        options: NetworkOptions,
+    ) -> Result<Self, Error> {
+        let name = name.into();
+        let NetworkCreateResponse { id, warning } = client
+            .create_network(options.to_create_options(&name)?)
+            .await?;
+        if !warning.is_empty() {
+            log::warn!("{}", warning);
+        }
+
+        Ok(Self { id, name, client })
+    }
+
+    /// The ID of the network
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name of the network
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Connect a container to the network (equivalent to `docker network connect`)
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to connect the container (e.g. it doesn't exist, or is already connected)
+    pub async fn connect(&self, container_id: &str) -> Result<(), Error> {
+        self.client
+            .connect_network(
+                &self.id,
+                ConnectNetworkOptions {
+                    container: container_id,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Disconnect a container from the network (equivalent to `docker network disconnect`)
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to disconnect the container (e.g. it isn't connected)
+    pub async fn disconnect(&self, container_id: &str) -> Result<(), Error> {
+        self.client
+            .disconnect_network(
+                &self.id,
+                DisconnectNetworkOptions {
+                    container: container_id,
+                    force: false,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Inspect the network (equivalent to `docker network inspect`)
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to inspect the network (e.g. it was removed out-of-band)
+    pub async fn inspect(&self) -> Result<bollard::models::Network, Error> {
+        let network = self
+            .client
+            .inspect_network(&self.id, None::<InspectNetworkOptions<String>>)
+            .await?;
+        Ok(network)
+    }
+
+    /// Remove the network (equivalent to `docker network rm`)
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to remove the network (e.g. containers are still connected to it)
+    pub async fn remove(self) -> Result<(), Error> {
+        self.client.remove_network(&self.id).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_options() {
+        let options = NetworkOptions::new()
+            .labels([("test", "true")])
+            .internal(true)
+            .subnet("10.5.0.0/16".parse().unwrap())
+            .gateway("10.5.0.1".parse().unwrap())
+            .to_create_options("backend")
+            .unwrap();
+
+        assert_eq!(options.name, "backend");
+        assert_eq!(options.driver, "bridge");
+        assert!(options.internal);
+        assert_eq!(options.labels.get("test").map(String::as_str), Some("true"));
+
+        let config = &options.ipam.config.unwrap()[0];
+        assert_eq!(config.subnet.as_deref(), Some("10.5.0.0/16"));
+        assert_eq!(config.gateway.as_deref(), Some("10.5.0.1"));
+
+        // No IPAM config unless a subnet is given
+        let options = NetworkOptions::new()
+            .driver("overlay")
+            .to_create_options("backend")
+            .unwrap();
+        assert_eq!(options.driver, "overlay");
+        assert!(options.ipam.config.is_none());
+    }
+
+    #[test]
+    fn test_invalid_gateway() {
+        let err = NetworkOptions::new()
+            .subnet("10.5.0.0/16".parse().unwrap())
+            .gateway("10.6.0.1".parse().unwrap())
+            .to_create_options("backend")
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig(_)), "{err:?}");
+
+        let err = NetworkOptions::new()
+            .gateway("10.6.0.1".parse().unwrap())
+            .to_create_options("backend")
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig(_)), "{err:?}");
+    }
+}
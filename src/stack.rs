@@ -0,0 +1,375 @@
+//! Utilities for managing groups of related containers
+
+use crate::container::{self, Container};
+use std::collections::{HashMap, HashSet};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Dependency cycle between containers: {}", .0.join(" -> "))]
+    DependencyCycle(Vec<String>),
+    #[error("`{name}` depends on `{dependency}`, which is not in the stack")]
+    UnknownDependency { name: String, dependency: String },
+    #[error("Container `{name}` failed: {source}")]
+    Container {
+        name: String,
+        #[source]
+        source: Box<container::Error>,
+    },
+    #[error(
+        "Failed to tear down the stack: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    Teardown(Vec<Error>),
+}
+
+/// A group of named [`Container`]s, started and torn down together (like a Compose project)
+///
+/// Containers are started in dependency order by [`Stack::up`], and torn down in the reverse
+/// order by [`Stack::down`]. Containers without dependencies between them are started in the
+/// order they were added.
+///
+/// NOTE: The names here only identify containers within the stack, use [`Container::with_name`]
+///       to name the containers themselves.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use docktopus::DockerBuilder;
+/// use docktopus::container::Container;
+/// use docktopus::stack::Stack;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let connection = DockerBuilder::new().await?;
+/// let mut stack = Stack::new()
+///     .container("api", Container::new(connection.client(), "myorg/api"))
+///     .container("db", Container::new(connection.client(), "postgres:16"))
+///     .depends_on("api", "db");
+///
+/// // Starts `db`, then `api`
+/// stack.up().await?;
+///
+/// // Removes `api`, then `db`
+/// stack.down().await?;
+/// # Ok(()) }
+/// ```
+#[derive(Default)]
+pub struct Stack {
+    containers: Vec<(String, Container)>,
+    depends_on: HashMap<String, Vec<String>>,
+}
+
+impl Stack {
+    /// Create an empty stack
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a container named `name`
+    ///
+    /// NOTE: This replaces any container already added with the same name
+    #[must_use]
+    pub fn container(mut self, name: impl Into<String>, container: Container) -> Self {
+        let name = name.into();
+        match self.containers.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, existing)) => *existing = container,
+            None => self.containers.push((name, container)),
+        }
+        self
+    }
+
+    /// Require `dependency` to be started before `name` (and torn down after it)
+    ///
+    /// This can be called multiple times, in which case all dependencies must be started first.
+    #[must_use]
+    pub fn depends_on(mut self, name: impl Into<String>, dependency: impl Into<String>) -> Self {
+        self.depends_on
+            .entry(name.into())
+            .or_default()
+            .push(dependency.into());
+        self
+    }
+
+    /// Get the container named `name`
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Container> {
+        self.containers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, container)| container)
+    }
+
+    /// Get the container named `name`, mutably
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Container> {
+        self.containers
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, container)| container)
+    }
+
+    /// The order [`Stack::up`] starts the containers in
+    ///
+    /// # Errors
+    ///
+    /// * The dependencies form a cycle, see [`Error::DependencyCycle`]
+    /// * A container depends on one that isn't in the stack, see [`Error::UnknownDependency`]
+    pub fn startup_order(&self) -> Result<Vec<String>, Error> {
+        fn visit<'a>(
+            name: &'a str,
+            depends_on: &'a HashMap<String, Vec<String>>,
+            path: &mut Vec<&'a str>,
+            visited: &mut HashSet<&'a str>,
+            order: &mut Vec<String>,
+        ) -> Result<(), Error> {
+            if let Some(start) = path.iter().position(|n| *n == name) {
+                let mut cycle = path[start..]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                cycle.push(name.to_string());
+                return Err(Error::DependencyCycle(cycle));
+            }
+            if !visited.insert(name) {
+                return Ok(());
+            }
+
+            path.push(name);
+            for dependency in depends_on.get(name).into_iter().flatten() {
+                visit(dependency, depends_on, path, visited, order)?;
+            }
+            path.pop();
+
+            order.push(name.to_string());
+            Ok(())
+        }
+
+        for (name, dependencies) in &self.depends_on {
+            if let Some(dependency) = dependencies.iter().find(|d| self.get(d).is_none()) {
+                return Err(Error::UnknownDependency {
+                    name: name.clone(),
+                    dependency: dependency.clone(),
+                });
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.containers.len());
+        let mut visited = HashSet::new();
+        for (name, _) in &self.containers {
+            visit(
+                name,
+                &self.depends_on,
+                &mut Vec::new(),
+                &mut visited,
+                &mut order,
+            )?;
+        }
+
+        Ok(order)
+    }
+
+    /// Start every container, each only once its dependencies have been started
+    ///
+    /// Containers that haven't been created yet are created first, see [`Container::start`].
+    ///
+    /// NOTE: If a container fails to start, those already started are left running. Use
+    ///       [`Stack::down`] to clean them up.
+    ///
+    /// # Errors
+    ///
+    /// * The dependencies are invalid, see [`Stack::startup_order`]
+    /// * Any container fails to start, see [`Error::Container`]
+    pub async fn up(&mut self) -> Result<(), Error> {
+        for name in self.startup_order()? {
+            let Some(container) = self.get_mut(&name) else {
+                continue;
+            };
+
+            log::debug!("Starting `{name}`");
+            if let Err(e) = container.start(false).await {
+                return Err(Error::Container {
+                    name,
+                    source: Box::new(e),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop and remove every container, in the reverse of the order they were started
+    ///
+    /// Containers that were never started are skipped, and kept in the stack. Those that have
+    /// already been removed are taken out of the stack. A container that fails to be torn down
+    /// doesn't stop the others from being torn down, and is kept in the stack so this can be
+    /// retried.
+    ///
+    /// # Errors
+    ///
+    /// * The dependencies are invalid, see [`Stack::startup_order`]
+    /// * Any container fails to be stopped or removed, see [`Error::Teardown`]
+    pub async fn down(&mut self) -> Result<(), Error> {
+        let mut order = self.startup_order()?;
+        order.reverse();
+
+        let mut errors = Vec::new();
+        for name in order {
+            let Some(index) = self.containers.iter().position(|(n, _)| *n == name) else {
+                continue;
+            };
+            let container = &mut self.containers[index].1;
+            // Kept in the stack, so a later `up` still starts it
+            if container.id().is_none() {
+                continue;
+            }
+
+            log::debug!("Tearing down `{name}`");
+            let result = match container.stop().await {
+                Ok(()) => container.clone().remove(None).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(())
+                | Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => {
+                    self.containers.remove(index);
+                }
+                Err(e) => {
+                    log::warn!("Failed to tear down `{name}`: {e}");
+                    errors.push(Error::Container {
+                        name,
+                        source: Box::new(e.into()),
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Teardown(errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_daemon::{FakeDaemon, Response};
+    use bollard::{API_DEFAULT_VERSION, Docker};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_up_order() {
+        let daemon = FakeDaemon::serve(r#"{"Id":"abc","Warnings":[]}"#);
+        let client = daemon.client();
+        let container = |name: &str| Container::new(client.clone(), "alpine").with_name(name);
+
+        let mut stack = Stack::new()
+            .container("c", container("c"))
+            .container("b", container("b"))
+            .container("a", container("a"))
+            .depends_on("b", "a");
+        assert_eq!(stack.startup_order().unwrap(), ["c", "a", "b"]);
+
+        stack.up().await.unwrap();
+        let created = daemon
+            .requests()
+            .iter()
+            .filter_map(|line| {
+                let (_, query) = line.split_once("/containers/create?name=")?;
+                query.split_whitespace().next().map(ToString::to_string)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(created, ["c", "a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_down_continues_past_failures() {
+        // Each container's id is its name, and `b` can't be stopped
+        let daemon = FakeDaemon::with_handler(|line| {
+            Some(
+                if let Some((_, query)) = line.split_once("/containers/create?name=") {
+                    let id = query.split_whitespace().next().unwrap_or_default();
+                    Response::status(201, format!(r#"{{"Id":"{id}","Warnings":[]}}"#))
+                } else if line.contains("/containers/b/stop") {
+                    Response::status(500, r#"{"message":"cannot stop b"}"#)
+                } else {
+                    Response::status(204, "")
+                },
+            )
+        });
+        let client = daemon.client();
+        let container = |name: &str| Container::new(client.clone(), "alpine").with_name(name);
+
+        let mut stack = Stack::new()
+            .container("a", container("a"))
+            .container("b", container("b"))
+            .container("c", container("c"))
+            .depends_on("b", "a")
+            .depends_on("c", "b");
+        stack.up().await.unwrap();
+
+        let err = stack.down().await.unwrap_err();
+        assert!(
+            matches!(&err, Error::Teardown(errors) if matches!(errors.as_slice(), [Error::Container { name, .. }] if name == "b")),
+            "unexpected error: {err:?}"
+        );
+
+        // `a` is still torn down, despite its dependent failing
+        let requests = daemon.requests();
+        assert!(
+            requests
+                .iter()
+                .any(|r| r.starts_with("DELETE /containers/a"))
+        );
+        assert!(
+            requests
+                .iter()
+                .any(|r| r.starts_with("DELETE /containers/c"))
+        );
+        assert!(stack.get("a").is_none() && stack.get("c").is_none());
+        assert!(stack.get("b").is_some());
+
+        // A container that was never started is left alone
+        let mut stack = stack.container("d", container("d"));
+        let _ = stack.down().await;
+        assert!(stack.get("d").is_some());
+        assert!(
+            !daemon
+                .requests()
+                .iter()
+                .any(|r| r.contains("/containers/d"))
+        );
+    }
+
+    #[test]
+    fn test_dependency_cycle() {
+        let client = Arc::new(
+            Docker::connect_with_http("http://127.0.0.1:2375", 4, API_DEFAULT_VERSION).unwrap(),
+        );
+        let container = || Container::new(client.clone(), "alpine");
+
+        let stack = Stack::new()
+            .container("a", container())
+            .container("b", container())
+            .container("c", container())
+            .depends_on("a", "b")
+            .depends_on("b", "c")
+            .depends_on("c", "a");
+        let err = stack.startup_order().unwrap_err();
+        assert!(
+            matches!(&err, Error::DependencyCycle(cycle) if cycle == &["a", "b", "c", "a"]),
+            "unexpected error: {err:?}"
+        );
+
+        let stack = Stack::new()
+            .container("a", container())
+            .depends_on("a", "missing");
+        assert!(matches!(
+            stack.startup_order(),
+            Err(Error::UnknownDependency { .. })
+        ));
+    }
+}
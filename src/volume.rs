@@ -0,0 +1,330 @@
+//! Utilities for creating and managing named Docker volumes
+
+use bollard::Docker;
+use bollard::volume::{CreateVolumeOptions, ListVolumesOptions, RemoveVolumeOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Volume `{0}` is in use by a container")]
+    VolumeInUse(String),
+    #[error("{0}")]
+    Bollard(#[from] bollard::errors::Error),
+}
+
+/// Filters for [`Volume::list`]
+///
+/// By default, all volumes are listed.
+///
+/// # Examples
+///
+/// ```rust
+/// use docktopus::volume::ListFilter;
+///
+/// // All unused volumes in the `foo` stack
+/// let filter = ListFilter::new()
+///     .label("com.myorg.stack=foo")
+///     .dangling(true);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ListFilter {
+    labels: Vec<String>,
+    name: Option<String>,
+    dangling: Option<bool>,
+}
+
+impl ListFilter {
+    /// Create an empty filter, matching all volumes
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match volumes with a label, either `key` or `key=value`
+    ///
+    /// This can be called multiple times, in which case all labels must match.
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Only match volumes whose name contains `name`
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only match volumes that are (or aren't) unused by any container
+    #[must_use]
+    pub fn dangling(mut self, dangling: bool) -> Self {
+        self.dangling = Some(dangling);
+        self
+    }
+
+    fn to_options(&self) -> ListVolumesOptions<String> {
+        let mut filters = HashMap::new();
+        if !self.labels.is_empty() {
+            filters.insert(String::from("label"), self.labels.clone());
+        }
+        if let Some(name) = &self.name {
+            filters.insert(String::from("name"), vec![name.clone()]);
+        }
+        if let Some(dangling) = self.dangling {
+            filters.insert(String::from("dangling"), vec![dangling.to_string()]);
+        }
+
+        ListVolumesOptions { filters }
+    }
+}
+
+/// A named Docker volume
+///
+/// Once created, a volume can be mounted into a [`Container`](crate::container::Container) with
+/// [`Container::binds`](crate::container::Container::binds), e.g. `"my-volume:/data"`.
+#[derive(Debug)]
+pub struct Volume {
+    name: String,
+    client: Arc<Docker>,
+    options: VolumeOptions,
+}
+
+#[derive(Debug, Default, Clone)]
+struct VolumeOptions {
+    driver: Option<String>,
+    labels: HashMap<String, String>,
+    driver_opts: HashMap<String, String>,
+}
+
+impl Volume {
+    /// Create a new `Volume`
+    ///
+    /// NOTE: The volume isn't created until [`Volume::create`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::container::Container;
+    /// use docktopus::volume::Volume;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let connection = DockerBuilder::new().await?;
+    /// let mut volume = Volume::new(connection.client(), "app-data");
+    /// volume.create().await?;
+    ///
+    /// let mut container =
+    ///     Container::new(connection.client(), "alpine:latest").binds(["app-data:/data"]);
+    /// container.start(false).await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn new<T>(client: Arc<Docker>, name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            client,
+            options: VolumeOptions::default(),
+        }
+    }
+
+    /// List existing volumes matching `filter`
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to list the volumes
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::volume::{ListFilter, Volume};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// // Clean up any unused volumes from previous runs
+    /// let filter = ListFilter::new().label("com.myorg.stack=foo").dangling(true);
+    /// for volume in Volume::list(connection.client(), filter).await? {
+    ///     volume.remove(false).await?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn list(client: Arc<Docker>, filter: ListFilter) -> Result<Vec<Self>, Error> {
+        let response = client.list_volumes(Some(filter.to_options())).await?;
+        for warning in response.warnings.unwrap_or_default() {
+            log::warn!("{}", warning);
+        }
+
+        Ok(response
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|volume| Self {
+                name: volume.name,
+                client: client.clone(),
+                options: VolumeOptions {
+                    driver: Some(volume.driver),
+                    labels: volume.labels,
+                    driver_opts: volume.options,
+                },
+            })
+            .collect())
+    }
+
+    /// The name of the volume
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the volume driver (equivalent to `--driver`), defaults to `local`
+    #[must_use]
+    pub fn driver(mut self, driver: impl Into<String>) -> Self {
+        self.options.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the labels for the volume
+    ///
+    /// NOTE: This will override any existing labels
+    #[must_use]
+    pub fn labels(
+        mut self,
+        labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.options.labels = labels
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+
+    /// Set driver-specific options (equivalent to `--opt`)
+    ///
+    /// NOTE: This will override any existing options
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use docktopus::DockerBuilder;
+    /// use docktopus::volume::Volume;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), docktopus::volume::Error> {
+    /// let connection = DockerBuilder::new().await?;
+    ///
+    /// // A volume backed by tmpfs
+    /// let mut volume = Volume::new(connection.client(), "scratch").driver_opts([
+    ///     ("type", "tmpfs"),
+    ///     ("device", "tmpfs"),
+    ///     ("o", "size=100m"),
+    /// ]);
+    /// volume.create().await?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn driver_opts(
+        mut self,
+        opts: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.options.driver_opts = opts
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+
+    /// Create the volume (equivalent to `docker volume create`)
+    ///
+    /// NOTE: Like `docker volume create`, this succeeds if a volume with the same name and driver
+    ///       already exists.
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to create the volume
+    pub async fn create(&mut self) -> Result<(), Error> {
+        log::debug!("Creating volume `{}`", self.name);
+
+        self.client
+            .create_volume(CreateVolumeOptions {
+                name: self.name.clone(),
+                driver: self
+                    .options
+                    .driver
+                    .clone()
+                    .unwrap_or_else(|| String::from("local")),
+                driver_opts: self.options.driver_opts.clone(),
+                labels: self.options.labels.clone(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Inspect the volume (equivalent to `docker volume inspect`)
+    ///
+    /// # Errors
+    ///
+    /// Docker fails to inspect the volume (e.g. it hasn't been created)
+    pub async fn inspect(&self) -> Result<bollard::models::Volume, Error> {
+        let volume = self.client.inspect_volume(&self.name).await?;
+        Ok(volume)
+    }
+
+    /// Remove the volume (equivalent to `docker volume rm`)
+    ///
+    /// `force` only affects volumes that don't exist, which are then not an error. Docker never
+    /// removes a volume that a container is using, even if `force` is set.
+    ///
+    /// # Errors
+    ///
+    /// * A container is using the volume, see [`Error::VolumeInUse`]
+    /// * Docker fails to remove the volume
+    pub async fn remove(self, force: bool) -> Result<(), Error> {
+        self.client
+            .remove_volume(&self.name, Some(RemoveVolumeOptions { force }))
+            .await
+            .map_err(|e| match e {
+                bollard::errors::Error::DockerResponseServerError {
+                    status_code: 409, ..
+                } => Error::VolumeInUse(self.name.clone()),
+                e => Error::Bollard(e),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_filter() {
+        let options = ListFilter::new().to_options();
+        assert!(options.filters.is_empty());
+
+        let options = ListFilter::new()
+            .label("stack=foo")
+            .label("tier")
+            .name("data")
+            .dangling(true)
+            .to_options();
+        assert_eq!(
+            options.filters.get("label"),
+            Some(&vec![String::from("stack=foo"), String::from("tier")])
+        );
+        assert_eq!(
+            options.filters.get("name"),
+            Some(&vec![String::from("data")])
+        );
+        assert_eq!(
+            options.filters.get("dangling"),
+            Some(&vec![String::from("true")])
+        );
+    }
+}
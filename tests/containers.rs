@@ -3,12 +3,20 @@ mod common;
 use bollard::container::{
     Config, CreateContainerOptions, InspectContainerOptions, ListContainersOptions,
 };
+use bollard::models::{RestartPolicy, RestartPolicyNameEnum};
 use color_eyre::Result;
-use common::with_docker_cleanup;
+use common::{is_docker_running, with_docker_cleanup};
 use docktopus::DockerBuilder;
-use futures_util::TryStreamExt;
+use docktopus::container::{
+    AttachOptions, Container, ContainerGuard, ContainerStatus, Error, IpFamily, ListFilter,
+    LogStream, PullPolicy, Readiness, ResourceUpdate, RestartEvent, WaitBehavior, WaitCondition,
+    wait_any,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use regex::Regex;
 use std::collections::HashMap;
 use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use uuid::Uuid;
 
 #[tokio::test]
@@ -212,3 +220,1927 @@ async fn test_container_management() -> Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn test_container_pause_unpause() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "30"])
+                .labels([("test_id", test_id.as_str())]);
+
+            // Pausing a container that isn't running is rejected
+            container.create().await?;
+            let err = container.pause().await.unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    Error::InvalidStateTransition {
+                        from: ContainerStatus::Created,
+                        to: ContainerStatus::Paused,
+                    }
+                ),
+                "unexpected error: {err:?}"
+            );
+
+            container.start(false).await?;
+            container.pause().await?;
+            assert_eq!(container.status().await?, Some(ContainerStatus::Paused));
+
+            container.unpause().await?;
+            assert_eq!(container.status().await?, Some(ContainerStatus::Running));
+
+            // Unpausing a container that isn't paused is rejected
+            let err = container.unpause().await.unwrap_err();
+            assert!(
+                matches!(err, Error::NotPaused(ContainerStatus::Running)),
+                "unexpected error: {err:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_run_with_provenance() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "echo out; echo err >&2; exit 3"])
+                .labels([("test_id", test_id.as_str())]);
+
+            let (output, provenance) = container.run_with_provenance().await?;
+            assert_eq!(output.exit_code, 3);
+            assert_eq!(output.stdout, "out\n");
+            assert_eq!(output.stderr, "err\n");
+
+            assert_eq!(provenance.image, "alpine:latest");
+            assert!(provenance.image_id.is_some());
+            assert!(
+                provenance
+                    .image_digest
+                    .as_deref()
+                    .is_some_and(|digest| digest.contains("@sha256:"))
+            );
+            assert!(provenance.daemon_version.is_some());
+            assert!(provenance.platform.is_some());
+            assert!(provenance.started_at.is_some());
+            assert!(provenance.finished_at.is_some());
+
+            // The record must be storable
+            let json = serde_json::to_string(&provenance)?;
+            assert!(json.contains("image_digest"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_restart() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "30"])
+                .labels([("test_id", test_id.as_str())]);
+
+            // Not started yet, this is a no-op
+            container.restart(None).await?;
+            assert!(container.id().is_none());
+
+            container.start(false).await?;
+            let id = container.id().map(ToString::to_string);
+
+            container.restart(Some(Duration::from_secs(1))).await?;
+            assert_eq!(container.id().map(ToString::to_string), id);
+            assert_eq!(container.status().await?, Some(ContainerStatus::Running));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_run_and_capture_instant_exit() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            // Exits immediately, any output collected after `wait` would be racy
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["echo", "Hello!"])
+                .labels([("test_id", test_id.as_str())]);
+
+            let output = container
+                .run_and_capture(Some(Duration::from_secs(30)))
+                .await?;
+            assert_eq!(output.exit_code, 0);
+            assert_eq!(output.stdout, "Hello!\n");
+            assert!(output.stderr.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_kill() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+
+            container.start(false).await?;
+            assert_eq!(container.status().await?, Some(ContainerStatus::Running));
+
+            container.kill(Some("SIGKILL")).await?;
+
+            // The container was killed, so it will report a non-zero exit
+            let _ = container.wait().await;
+            assert_eq!(container.status().await?, Some(ContainerStatus::Exited));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_status_after_removal() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            // Remove it out from under the handle
+            builder
+                .remove_container(
+                    container.id().unwrap(),
+                    Some(bollard::container::RemoveContainerOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+
+            assert_eq!(container.status().await?, None);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_exists() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            assert!(!container.exists().await?);
+
+            container.create().await?;
+            assert!(container.exists().await?);
+
+            builder
+                .remove_container(
+                    container.id().unwrap(),
+                    Some(bollard::container::RemoveContainerOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+            assert!(!container.exists().await?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_is_running_and_healthy() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut running = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            running.start(false).await?;
+            assert!(running.is_running().await?);
+            // No healthcheck, so running is enough
+            assert!(running.is_healthy().await?);
+
+            let mut exited = Container::new(builder.client(), "alpine:latest")
+                .cmd(["true"])
+                .labels([("test_id", test_id.as_str())]);
+            exited.start(true).await?;
+            assert!(!exited.is_running().await?);
+            assert!(!exited.is_healthy().await?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_clone() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            container.create().await?;
+
+            let monitor = container.clone();
+            assert_eq!(monitor.id(), container.id());
+            assert_eq!(monitor.status().await?, Some(ContainerStatus::Created));
+
+            // Both refer to the same daemon container
+            container.start(false).await?;
+            assert_eq!(monitor.status().await?, Some(ContainerStatus::Running));
+            assert_eq!(monitor.status().await?, container.status().await?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_graceful_force_remove() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            // `sleep` as PID 1 ignores SIGTERM, so stopping it takes the full grace period
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())])
+                .config_override(Config {
+                    stop_timeout: Some(2),
+                    ..Default::default()
+                })
+                .graceful_force(true);
+
+            container.start(false).await?;
+
+            let start = std::time::Instant::now();
+            container
+                .remove(Some(bollard::container::RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+            assert!(
+                start.elapsed() >= Duration::from_secs(2),
+                "removal should have waited for the grace period"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_list() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            for role in ["a", "b"] {
+                let mut container = Container::new(builder.client(), "alpine:latest")
+                    .cmd(["sleep", "30"])
+                    .labels([("test_id", test_id.as_str()), ("role", role)]);
+                container.start(false).await?;
+            }
+
+            let filter = ListFilter::new()
+                .label(format!("test_id={test_id}"))
+                .label("role=a");
+            let containers = Container::list(builder.client(), filter).await?;
+            assert_eq!(containers.len(), 1);
+            assert_eq!(
+                containers[0].status().await?,
+                Some(ContainerStatus::Running)
+            );
+
+            let filter = ListFilter::new().label(format!("test_id={test_id}"));
+            let containers = Container::list(builder.client(), filter).await?;
+            assert_eq!(containers.len(), 2);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_wait_exit_codes() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut success = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "exit 0"])
+                .labels([("test_id", test_id.as_str())]);
+            success.start(false).await?;
+            assert_eq!(success.wait().await?, 0);
+
+            let mut failure = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "exit 3"])
+                .labels([("test_id", test_id.as_str())])
+                .wait_behavior(WaitBehavior::ReturnExitCode);
+            failure.start(false).await?;
+            assert_eq!(failure.wait().await?, 3);
+
+            // The default behavior still reports non-zero exits as errors
+            let failure = failure.wait_behavior(WaitBehavior::ErrorOnNonZero);
+            let err = failure.wait().await.unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    Error::Bollard(bollard::errors::Error::DockerContainerWaitError {
+                        code: 3,
+                        ..
+                    })
+                ),
+                "unexpected error: {err:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_upload_download_progress() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            // A multi-megabyte payload, so the upload is sent in several chunks
+            let payload = vec![0xAB_u8; 4 * 1024 * 1024];
+            let mut tar = tar::Builder::new(Vec::new());
+            let mut header = tar::Header::new_gnu();
+            header.set_size(payload.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, "payload.bin", payload.as_slice())?;
+            let archive = tar.into_inner()?;
+            let archive_len = archive.len() as u64;
+
+            let (tx, rx) = std::sync::mpsc::channel();
+            container
+                .upload_with_progress("/tmp", archive, move |sent| {
+                    let _ = tx.send(sent);
+                })
+                .await?;
+
+            let uploaded = rx.try_iter().collect::<Vec<_>>();
+            assert!(uploaded.len() > 1, "expected several progress reports");
+            assert!(uploaded.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(uploaded.last().copied(), Some(archive_len));
+
+            let mut downloaded = Vec::new();
+            let archive = container
+                .download_with_progress("/tmp/payload.bin", |received| downloaded.push(received))
+                .await?;
+            assert!(!downloaded.is_empty());
+            assert_eq!(downloaded.last().copied(), Some(archive.len() as u64));
+
+            let mut tar = tar::Archive::new(archive.as_slice());
+            let mut entry = tar
+                .entries()?
+                .next()
+                .expect("archive should have an entry")?;
+            let mut contents = Vec::new();
+            std::io::Read::read_to_end(&mut entry, &mut contents)?;
+            assert_eq!(contents, payload);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_static_ips() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let primary = format!("test-network-{}", test_id);
+            let secondary = format!("test-network-{}-2", test_id);
+            for (name, subnet) in [(&primary, "172.29.0.0/16"), (&secondary, "172.30.0.0/16")] {
+                builder
+                    .client()
+                    .create_network(bollard::network::CreateNetworkOptions {
+                        name: name.clone(),
+                        driver: String::from("bridge"),
+                        labels: HashMap::from([(String::from("test_id"), test_id.clone())]),
+                        ipam: bollard::models::Ipam {
+                            config: Some(vec![bollard::models::IpamConfig {
+                                subnet: Some(String::from(subnet)),
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .await?;
+            }
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())])
+                .config_override(bollard::container::Config {
+                    host_config: Some(bollard::models::HostConfig {
+                        network_mode: Some(primary.clone()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .with_ipv4(primary.clone(), "172.29.5.10".parse()?)
+                .with_ipv4(secondary.clone(), "172.30.5.20".parse()?);
+
+            container.start(false).await?;
+            container.connect(&secondary).await?;
+
+            let inspect = builder
+                .client()
+                .inspect_container(container.id().unwrap(), None)
+                .await?;
+            let networks = inspect.network_settings.unwrap().networks.unwrap();
+            assert_eq!(
+                networks[&primary].ip_address.as_deref(),
+                Some("172.29.5.10")
+            );
+            assert_eq!(
+                networks[&secondary].ip_address.as_deref(),
+                Some("172.30.5.20")
+            );
+
+            // Addresses outside of the subnet are rejected before reaching the daemon
+            let mut outside = Container::new(builder.client(), "alpine:latest")
+                .labels([("test_id", test_id.as_str())])
+                .config_override(bollard::container::Config {
+                    host_config: Some(bollard::models::HostConfig {
+                        network_mode: Some(primary.clone()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .with_ipv4(primary.clone(), "10.0.0.5".parse()?);
+            assert!(matches!(
+                outside.create().await,
+                Err(Error::InvalidConfig(_))
+            ));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_health_log() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            // No healthcheck, so no log
+            let mut plain = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            plain.start(false).await?;
+            assert!(plain.health_log().await?.is_empty());
+
+            let mut failing = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())])
+                .config_override(bollard::container::Config {
+                    healthcheck: Some(bollard::models::HealthConfig {
+                        test: Some(vec![
+                            String::from("CMD-SHELL"),
+                            String::from("echo database unreachable; exit 1"),
+                        ]),
+                        interval: Some(500_000_000),
+                        timeout: Some(1_000_000_000),
+                        retries: Some(1),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                });
+            failing.start(false).await?;
+
+            let err = failing
+                .wait_until_healthy(Duration::from_secs(3))
+                .await
+                .unwrap_err();
+            let Error::HealthTimeout {
+                last_failure: Some(failure),
+                ..
+            } = err
+            else {
+                panic!("unexpected error: {err:?}");
+            };
+            assert_eq!(failure.exit_code, Some(1));
+            assert!(failure.output.contains("database unreachable"));
+
+            let log = failing.health_log().await?;
+            assert!(!log.is_empty());
+            assert!(log.iter().all(|result| result.exit_code == Some(1)));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_read_only_with_writable() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd([
+                    "sh",
+                    "-c",
+                    "touch /tmp/ok && echo writable; touch /etc/nope || echo read-only",
+                ])
+                .labels([("test_id", test_id.as_str())])
+                .read_only_with_writable(&["/tmp"]);
+
+            let output = container.run_and_capture(None).await?;
+            assert!(output.stdout.contains("writable"), "{output:?}");
+            assert!(output.stdout.contains("read-only"), "{output:?}");
+            assert!(
+                output.stderr.contains("Read-only file system"),
+                "{output:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_rename_conflict() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let taken = format!("test-service-{}", test_id);
+            let mut existing = Container::new(builder.client(), "alpine:latest")
+                .with_name(taken.clone())
+                .labels([("test_id", test_id.as_str())]);
+            existing.create().await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .labels([("test_id", test_id.as_str())]);
+            container.create().await?;
+
+            let err = container.rename(&taken).await.unwrap_err();
+            let Error::Conflict {
+                name,
+                existing: existing_id,
+            } = err
+            else {
+                panic!("unexpected error: {err:?}");
+            };
+            assert_eq!(name, taken);
+            assert_eq!(Some(existing_id.as_str()), existing.id());
+
+            container.rename_force(&taken).await?;
+            assert_eq!(container.name(), Some(taken.as_str()));
+
+            // The conflicting container was removed to free up the name
+            let existing_id = existing.id().unwrap();
+            assert!(
+                builder
+                    .client()
+                    .inspect_container(existing_id, None)
+                    .await
+                    .is_err()
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_pull_image_with_progress() -> Result<()> {
+    with_docker_cleanup(|_test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            let events = builder
+                .pull_image_with_progress("hello-world:latest", None)
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            let last = events.last().expect("pull should report progress");
+            assert!(last.is_complete(), "unexpected final event: {last:?}");
+            assert!(
+                events
+                    .iter()
+                    .filter_map(|event| event.percentage)
+                    .all(|percentage| (0.0..=100.0).contains(&percentage))
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_pull_on_missing() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+
+            // Make sure the image really is missing, it may be left over from another test
+            let _ = builder
+                .client()
+                .remove_image(
+                    "hello-world:linux",
+                    Some(bollard::image::RemoveImageOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                    None,
+                )
+                .await;
+
+            let mut container = Container::new(builder.client(), "hello-world:linux")
+                .labels([("test_id", test_id.as_str())])
+                .pull_on_missing(true);
+            container.start(true).await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_pull_policy_always() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            // Shadow `alpine:3.18` with a different cached image, only a pull will restore it
+            builder
+                .client()
+                .tag_image(
+                    "alpine:latest",
+                    Some(bollard::image::TagImageOptions {
+                        repo: "alpine",
+                        tag: "3.18",
+                    }),
+                )
+                .await?;
+
+            let mut container = Container::new(builder.client(), "alpine:3.18")
+                .cmd(["cat", "/etc/alpine-release"])
+                .labels([("test_id", test_id.as_str())])
+                .pull_policy(PullPolicy::Always, true);
+            let output = container.run_and_capture(None).await?;
+            assert!(output.stdout.starts_with("3.18"), "{output:?}");
+
+            let latest = builder.client().inspect_image("alpine:latest").await?;
+            let pinned = builder.client().inspect_image("alpine:3.18").await?;
+            assert_ne!(latest.id, pinned.id);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_container_guard_cleans_up_on_panic() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            let client = builder.client();
+            let body = tokio::spawn(async move {
+                let mut container = ContainerGuard::new(
+                    Container::new(client, "alpine:latest")
+                        .cmd(["sleep", "300"])
+                        .labels([("test_id", test_id.as_str())]),
+                );
+                container.start(false).await.unwrap();
+                tx.send(container.id().unwrap().to_string()).unwrap();
+
+                panic!("test body failed");
+            });
+
+            let id = rx.await?;
+            assert!(body.await.unwrap_err().is_panic());
+
+            let inspect = builder.client().inspect_container(&id, None).await;
+            assert!(
+                matches!(
+                    inspect,
+                    Err(bollard::errors::Error::DockerResponseServerError {
+                        status_code: 404,
+                        ..
+                    })
+                ),
+                "container should have been removed: {inspect:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_image_not_found() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            let image = format!("alpine:docktopus-missing-{}", test_id);
+
+            let mut container = Container::new(builder.client(), image.clone())
+                .labels([("test_id", test_id.as_str())]);
+            let err = container.create().await.unwrap_err();
+            assert!(
+                matches!(&err, Error::ImageNotFound(missing) if *missing == image),
+                "unexpected error: {err:?}"
+            );
+
+            // The tag can't be pulled either
+            let mut container = Container::new(builder.client(), image.clone())
+                .labels([("test_id", test_id.as_str())])
+                .pull_on_missing(true);
+            let err = container.create().await.unwrap_err();
+            assert!(
+                matches!(&err, Error::ImageNotFound(missing) if *missing == image),
+                "unexpected error: {err:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_collect_stdout_stderr() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "echo to-stdout; echo to-stderr >&2"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(true).await?;
+
+            assert_eq!(container.collect_stdout(None).await?, "to-stdout\n");
+            assert_eq!(container.collect_stderr(None).await?, "to-stderr\n");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_logs_to_string() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["echo", "Hello!"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(true).await?;
+
+            assert_eq!(container.logs_to_string(None).await?, "Hello!\n");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_attach() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["cat"])
+                .labels([("test_id", test_id.as_str())])
+                .config_override(Config {
+                    open_stdin: Some(true),
+                    ..Default::default()
+                });
+            container.start(false).await?;
+
+            let mut attached = container.attach(AttachOptions::new()).await?;
+            assert!(!attached.tty);
+            attached.input.write_all(b"Hello!\n").await?;
+
+            let mut echoed = Vec::new();
+            while !echoed.ends_with(b"\n") {
+                let out = tokio::time::timeout(Duration::from_secs(10), attached.output.next())
+                    .await?
+                    .expect("the container should echo the input")?;
+                let bollard::container::LogOutput::StdOut { message } = out else {
+                    panic!("unexpected output: {out:?}");
+                };
+                echoed.extend_from_slice(&message);
+            }
+            assert_eq!(echoed, b"Hello!\n");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_resize_tty() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "infinity"])
+                .labels([("test_id", test_id.as_str())])
+                .config_override(Config {
+                    tty: Some(true),
+                    open_stdin: Some(true),
+                    ..Default::default()
+                });
+            container.start(false).await?;
+
+            container.resize_tty(120, 40).await?;
+
+            // The main process' stdin is the container's TTY
+            let size = container
+                .exec_output(["sh", "-c", "stty size < /proc/1/fd/0"])
+                .await?;
+            assert_eq!(size.trim(), "40 120");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_wait_for_removed() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())])
+                .wait_behavior(WaitBehavior::ReturnExitCode);
+            container.start(false).await?;
+            let id = container.id().unwrap().to_string();
+
+            let (exit_code, removed) = tokio::join!(
+                tokio::time::timeout(
+                    Duration::from_secs(30),
+                    container.wait_for(WaitCondition::Removed)
+                ),
+                async {
+                    // Give the wait a chance to be registered first
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    builder
+                        .remove_container(
+                            &id,
+                            Some(bollard::container::RemoveContainerOptions {
+                                force: true,
+                                ..Default::default()
+                            }),
+                        )
+                        .await
+                }
+            );
+            removed?;
+            // Killed by the force-remove
+            assert_eq!(exit_code??, 137);
+            assert!(!container.exists().await?);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_wait_any() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut slow = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "30"])
+                .labels([("test_id", test_id.as_str())]);
+            slow.start(false).await?;
+
+            let mut fast = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "sleep 1; exit 3"])
+                .labels([("test_id", test_id.as_str())]);
+            fast.start(false).await?;
+
+            let ids = [slow.id().unwrap(), fast.id().unwrap()].map(String::from);
+            let (id, exit_code) =
+                tokio::time::timeout(Duration::from_secs(20), wait_any(builder.client(), &ids))
+                    .await??;
+            assert_eq!(id, ids[1]);
+            assert_eq!(exit_code, 3);
+
+            // The other container is left alone
+            assert_eq!(slow.status().await?, Some(ContainerStatus::Running));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_healthcheck() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())])
+                .healthcheck(
+                    vec![String::from("CMD"), String::from("true")],
+                    Duration::from_millis(200),
+                    Duration::from_secs(1),
+                    1,
+                    Duration::ZERO,
+                );
+            container.start(false).await?;
+
+            let start = std::time::Instant::now();
+            container
+                .wait_until_healthy(Duration::from_secs(10))
+                .await?;
+            assert!(start.elapsed() < Duration::from_secs(5));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_wait_for_log_count() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd([
+                    "sh",
+                    "-c",
+                    "for i in 1 2 3 4; do echo worker $i ready; sleep 0.2; done; sleep 300",
+                ])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let pattern = Regex::new(r"worker \d ready")?;
+            let lines = container
+                .wait_for_log_count(&pattern, 3, Duration::from_secs(10))
+                .await?;
+            assert_eq!(
+                lines,
+                ["worker 1 ready", "worker 2 ready", "worker 3 ready"]
+            );
+
+            let err = container
+                .wait_for_log_count(&pattern, 5, Duration::from_secs(2))
+                .await
+                .unwrap_err();
+            let Error::LogTimeout { found, .. } = err else {
+                panic!("unexpected error: {err:?}");
+            };
+            assert_eq!(found.len(), 4);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_network_aliases() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let network = format!("test-network-{}", test_id);
+            builder
+                .create_network_with_retry(
+                    &network,
+                    3,
+                    Duration::from_secs(2),
+                    Some(HashMap::from([(String::from("test_id"), test_id.clone())])),
+                )
+                .await?;
+
+            let mut server = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())])
+                .network(network.clone())
+                .network_aliases(["db"]);
+            server.start(false).await?;
+
+            // `extra_hosts` still applies alongside the network
+            let mut client = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "ping -c 1 -W 2 db && getent hosts static-host"])
+                .labels([("test_id", test_id.as_str())])
+                .network(network.clone())
+                .network_aliases(["app"])
+                .extra_hosts(["static-host:10.1.2.3"]);
+            let output = client.run_and_capture(None).await?;
+            assert_eq!(output.exit_code, 0, "{output:?}");
+            assert!(output.stdout.contains("10.1.2.3"), "{output:?}");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_add_host_resolved() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["getent", "hosts", "resolved-host"])
+                .labels([("test_id", test_id.as_str())])
+                .extra_hosts(["static-host:10.1.2.3"])
+                .add_host_resolved("resolved-host", "localhost", IpFamily::V4);
+            let output = container.run_and_capture(None).await?;
+            assert_eq!(output.exit_code, 0, "{output:?}");
+            assert!(output.stdout.contains("127.0.0.1"), "{output:?}");
+
+            // Resolution failures are caught before the container is created
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .labels([("test_id", test_id.as_str())])
+                .add_host_resolved("missing", "does-not-exist.invalid", IpFamily::Any);
+            let err = container.create().await.unwrap_err();
+            assert!(matches!(err, Error::HostResolution { .. }), "{err:?}");
+            assert!(container.id().is_none());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_exec_output() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let os_release = container.exec_output(["cat", "/etc/os-release"]).await?;
+            assert!(os_release.contains("ID=alpine"), "{os_release}");
+
+            // stdout and stderr are merged
+            let output = container
+                .exec_output(["sh", "-c", "echo out; echo err >&2"])
+                .await?;
+            assert!(output.contains("out") && output.contains("err"), "{output}");
+
+            let err = container
+                .exec_output(["sh", "-c", "echo oops >&2; exit 3"])
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&err, Error::ExecFailed { exit_code: 3, output, .. } if output.contains("oops")),
+                "{err:?}"
+            );
+
+            container
+                .remove(Some(bollard::container::RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_restart_events() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "exit 3"])
+                .labels([("test_id", test_id.as_str())])
+                .restart_policy(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                    maximum_retry_count: Some(2),
+                });
+            container.create().await?;
+
+            let restarts = container.restart_events();
+            container.start(false).await?;
+
+            // Let the policy run its course before polling, restarts from before the stream is
+            // first polled aren't lost
+            tokio::time::timeout(Duration::from_secs(30), async {
+                loop {
+                    let inspect = builder
+                        .client()
+                        .inspect_container(container.id().unwrap(), None)
+                        .await?;
+                    let state = inspect.state.unwrap_or_default();
+                    let settled = state.running == Some(false) && state.restarting == Some(false);
+                    if settled && inspect.restart_count == Some(2) {
+                        return Ok::<_, bollard::errors::Error>(());
+                    }
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            })
+            .await??;
+
+            // The stream ends once the policy is exhausted
+            let restarts: Vec<RestartEvent> =
+                tokio::time::timeout(Duration::from_secs(30), restarts.try_collect()).await??;
+            assert_eq!(
+                restarts,
+                vec![
+                    RestartEvent {
+                        restart_count: 1,
+                        exit_code: Some(3),
+                    },
+                    RestartEvent {
+                        restart_count: 2,
+                        exit_code: Some(3),
+                    },
+                ]
+            );
+
+            container
+                .remove(Some(bollard::container::RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_logs_until_ready() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd([
+                    "sh",
+                    "-c",
+                    "echo starting; echo warming up >&2; sleep 1; echo ready; sleep 1; echo after; sleep 300",
+                ])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let mut lines = Vec::new();
+            container
+                .logs_until_ready(
+                    &Readiness::LogMatches(Regex::new("^ready$")?),
+                    Duration::from_secs(30),
+                    |line| lines.push(line.to_string()),
+                )
+                .await?;
+            assert_eq!(lines, ["starting", "warming up", "ready"]);
+
+            // A pattern that never appears times out
+            let err = container
+                .logs_until_ready(
+                    &Readiness::LogMatches(Regex::new("never")?),
+                    Duration::from_secs(3),
+                    |_| {},
+                )
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::ReadyTimeout(_)), "{err:?}");
+
+            container
+                .remove(Some(bollard::container::RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_snapshot_and_restart() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd([
+                    "sh",
+                    "-c",
+                    "if [ -f /warm ]; then echo restored; else echo fresh; fi; touch /warm; sleep 300",
+                ])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+            container
+                .wait_for_log(&Regex::new("fresh")?, Duration::from_secs(10))
+                .await?;
+
+            let old_id = container.id().unwrap().to_string();
+            let image = container.snapshot_and_restart(false).await?;
+            assert_ne!(container.id(), Some(old_id.as_str()));
+
+            // The file written at runtime survived the restart
+            container
+                .wait_for_log(&Regex::new("restored")?, Duration::from_secs(10))
+                .await?;
+
+            container
+                .remove(Some(bollard::container::RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+            assert!(builder.client().inspect_image(&image).await.is_err());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_copy_to_and_from() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let dir = tempfile::tempdir()?;
+            let local = dir.path().join("app.conf");
+            std::fs::write(&local, "port = 8080\n")?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+
+            // Nothing to copy to or from yet
+            assert!(matches!(
+                container.copy_path_to("/etc", &local).await,
+                Err(Error::ContainerNotFound)
+            ));
+            assert!(matches!(
+                container.copy_from("/etc").try_next().await,
+                Err(Error::ContainerNotFound)
+            ));
+
+            container.start(false).await?;
+            container.copy_path_to("/etc", &local).await?;
+
+            let id = container.id().unwrap();
+            let contents = builder
+                .exec_in_container(id, vec!["cat", "/etc/app.conf"], None)
+                .await?;
+            assert_eq!(contents.trim(), "port = 8080");
+
+            // And back out, through the streaming API
+            let archive = container
+                .copy_from("/etc/app.conf")
+                .try_fold(Vec::new(), |mut archive, chunk| async move {
+                    archive.extend_from_slice(&chunk);
+                    Ok(archive)
+                })
+                .await?;
+            let mut entries = tar::Archive::new(archive.as_slice());
+            let mut entry = entries.entries()?.next().unwrap()?;
+            let mut copied = String::new();
+            std::io::Read::read_to_string(&mut entry, &mut copied)?;
+            assert_eq!(copied, "port = 8080\n");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_labels_from_file() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let dir = tempfile::tempdir()?;
+            let path = dir.path().join("traefik.yaml");
+            std::fs::write(
+                &path,
+                "traefik.enable: true\ntraefik.http.routers.web.rule: \"Host(`example.com`)\"\n",
+            )?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .labels([("test_id", test_id.as_str())])
+                .labels_from_file(&path)?;
+            container.create().await?;
+
+            let inspect = builder
+                .client()
+                .inspect_container(container.id().unwrap(), None::<InspectContainerOptions>)
+                .await?;
+            let labels = inspect.config.unwrap().labels.unwrap();
+            assert_eq!(labels["test_id"], test_id);
+            assert_eq!(labels["traefik.enable"], "true");
+            assert_eq!(
+                labels["traefik.http.routers.web.rule"],
+                "Host(`example.com`)"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_events() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "exit 3"])
+                .labels([("test_id", test_id.as_str())])
+                .wait_behavior(WaitBehavior::ReturnExitCode);
+            assert!(matches!(
+                container.events().try_next().await,
+                Err(Error::ContainerNotFound)
+            ));
+
+            container.create().await?;
+            let mut events = container.events();
+            container.start(false).await?;
+
+            // Events from before the stream is first polled aren't lost
+            assert_eq!(container.wait().await?, 3);
+
+            let die = tokio::time::timeout(Duration::from_secs(30), async {
+                while let Some(event) = events.try_next().await? {
+                    if event.action == "die" {
+                        return Ok(Some(event));
+                    }
+                }
+                Ok::<_, Error>(None)
+            })
+            .await??
+            .expect("event stream ended before the container died");
+            assert_eq!(die.exit_code(), Some(3));
+            assert_eq!(die.attributes.get("test_id"), Some(&test_id));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_update() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            container
+                .update(ResourceUpdate {
+                    memory: Some(String::from("128M")),
+                    memory_swap: Some(String::from("256M")),
+                    cpu_shares: Some(256),
+                    ..Default::default()
+                })
+                .await?;
+
+            let host_config = builder
+                .client()
+                .inspect_container(container.id().unwrap(), None::<InspectContainerOptions>)
+                .await?
+                .host_config
+                .unwrap();
+            assert_eq!(host_config.memory, Some(128 * 1024 * 1024));
+            assert_eq!(host_config.memory_swap, Some(256 * 1024 * 1024));
+            assert_eq!(host_config.cpu_shares, Some(256));
+
+            let err = container
+                .update(ResourceUpdate {
+                    memory: Some(String::from("lots")),
+                    ..Default::default()
+                })
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::InvalidResourceLimit(_)), "{err:?}");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_logs_reader() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "seq 1 1000; echo oops >&2"])
+                .labels([("test_id", test_id.as_str())]);
+            assert!(matches!(
+                container.logs_reader(None, LogStream::Stdout),
+                Err(Error::ContainerNotFound)
+            ));
+            container.start(true).await?;
+
+            let mut stdout = Vec::new();
+            container
+                .logs_reader(None, LogStream::Stdout)?
+                .read_to_end(&mut stdout)
+                .await?;
+            assert_eq!(
+                String::from_utf8(stdout)?,
+                container.collect_stdout(None).await?
+            );
+
+            let mut stderr = Vec::new();
+            container
+                .logs_reader(None, LogStream::Stderr)?
+                .read_to_end(&mut stderr)
+                .await?;
+            assert_eq!(stderr, b"oops\n");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_wait_with_logs() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let cmd = ["sh", "-c", "seq 1 50; sleep 0.1; echo boom >&2; exit 4"];
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(cmd)
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let err = container.wait_with_logs(3).await.unwrap_err();
+            let Error::NonZeroExit { exit_code, tail } = &err else {
+                panic!("unexpected error: {err:?}");
+            };
+            assert_eq!(*exit_code, 4);
+            assert_eq!(tail, &["49", "50", "boom"]);
+            assert!(err.to_string().contains("boom"));
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(cmd)
+                .labels([("test_id", test_id.as_str())])
+                .wait_behavior(WaitBehavior::ReturnExitCode);
+            container.start(false).await?;
+
+            let (exit_code, tail) = container.wait_with_logs(1).await?;
+            assert_eq!(exit_code, 4);
+            assert_eq!(tail, ["boom"]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_from_name() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let name = format!("test-from-name-{}", test_id);
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .with_name(name.clone())
+                .labels([("test_id", test_id.as_str())]);
+            container.create().await?;
+
+            // A container whose name merely contains ours must not make the lookup ambiguous
+            let mut other = Container::new(builder.client(), "alpine:latest")
+                .with_name(format!("{name}-other"))
+                .labels([("test_id", test_id.as_str())]);
+            other.create().await?;
+
+            let found = Container::from_name(builder.client(), &name).await?;
+            assert_eq!(found.id(), container.id());
+            assert_eq!(found.name(), Some(name.as_str()));
+
+            let found = Container::from_name(builder.client(), format!("/{name}")).await?;
+            assert_eq!(found.id(), container.id());
+
+            let err = Container::from_name(builder.client(), format!("{name}-missing"))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::ContainerNotFound), "{err:?}");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_container_stop_graceful() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let timeout = Duration::from_secs(2);
+
+            // Ignores SIGTERM, so it has to be killed
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "trap '' TERM; sleep 300 & wait"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let start = std::time::Instant::now();
+            container.stop_graceful(timeout).await?;
+            assert!(start.elapsed() >= timeout);
+
+            let state = builder
+                .client()
+                .inspect_container(container.id().unwrap(), None)
+                .await?
+                .state
+                .unwrap();
+            assert_eq!(state.running, Some(false));
+            assert_eq!(state.exit_code, Some(137));
+
+            // Exits on SIGTERM, so it's never killed
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sh", "-c", "trap 'exit 0' TERM; sleep 300 & wait"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let start = std::time::Instant::now();
+            container.stop_graceful(Duration::from_secs(30)).await?;
+            assert!(start.elapsed() < Duration::from_secs(30));
+
+            let state = builder
+                .client()
+                .inspect_container(container.id().unwrap(), None)
+                .await?
+                .state
+                .unwrap();
+            assert_eq!(state.exit_code, Some(0));
+
+            // Already stopped, nothing to do
+            container.stop_graceful(timeout).await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
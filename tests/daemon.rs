@@ -0,0 +1,66 @@
+mod common;
+
+use color_eyre::Result;
+use common::{is_docker_running, with_docker_cleanup};
+use docktopus::DockerBuilder;
+use docktopus::builder::PruneTargets;
+use docktopus::container::Container;
+
+#[tokio::test]
+async fn test_ping_and_version() -> Result<()> {
+    with_docker_cleanup(|_| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.ping().await?;
+
+            let version = builder.version().await?;
+            assert!(!version.version.is_empty());
+            assert!(!version.api_version.is_empty());
+            assert!(!version.os.is_empty());
+            assert!(!version.arch.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_prune_containers() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["true"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(true).await?;
+            let id = container.id().unwrap().to_string();
+
+            // Only touch our own containers, other tests may be running
+            let report = builder
+                .prune(
+                    PruneTargets::new()
+                        .containers(true)
+                        .label(format!("test_id={test_id}")),
+                )
+                .await?;
+            assert_eq!(report.containers_deleted, [id]);
+            assert!(!container.exists().await?);
+
+            Ok(())
+        })
+    })
+    .await
+}
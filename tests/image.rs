@@ -0,0 +1,134 @@
+mod common;
+
+use color_eyre::Result;
+use common::{is_docker_running, with_docker_cleanup};
+use docktopus::DockerBuilder;
+use docktopus::container::Container;
+use docktopus::image::{Error, Image};
+use futures_util::TryStreamExt;
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_image_build() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+
+            let context = tempfile::tempdir()?;
+            std::fs::write(
+                context.path().join("Dockerfile"),
+                "FROM alpine:latest\nARG GREETING\nRUN echo \"$GREETING\" > /greeting\n",
+            )?;
+
+            let tag = format!("docktopus-build-test:{test_id}");
+            let progress: Vec<_> = Image::build(
+                builder.client(),
+                context.path(),
+                "Dockerfile",
+                &tag,
+                HashMap::from([(String::from("GREETING"), String::from("hello"))]),
+            )
+            .try_collect()
+            .await?;
+
+            let id = progress
+                .iter()
+                .find_map(|event| event.image_id.clone())
+                .expect("the build should report the image id");
+
+            let inspect = Image::new(builder.client(), &tag).inspect().await?;
+            assert_eq!(inspect.id, Some(id));
+
+            builder.client().remove_image(&tag, None, None).await?;
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_image_build_failure() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+
+            let context = tempfile::tempdir()?;
+            std::fs::write(
+                context.path().join("Dockerfile"),
+                "FROM alpine:latest\nRUN false\n",
+            )?;
+
+            let result: Result<Vec<_>, _> = Image::build(
+                builder.client(),
+                context.path(),
+                "Dockerfile",
+                &format!("docktopus-build-test:{test_id}"),
+                HashMap::new(),
+            )
+            .try_collect()
+            .await;
+            assert!(
+                matches!(result, Err(Error::BuildFailed(_))),
+                "unexpected result: {result:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_image_tag_and_remove() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("hello-world:latest", None).await?;
+
+            let image = Image::new(builder.client(), "hello-world:latest");
+            let repo = format!("docktopus-tag-test-{test_id}");
+            let tagged = image.tag(&repo, "v1").await?;
+            assert_eq!(tagged.name(), format!("{repo}:v1"));
+            assert_eq!(tagged.inspect().await?.id, image.inspect().await?.id);
+
+            // A container created from the tag keeps it from being removed
+            let mut container = Container::new(builder.client(), tagged.name())
+                .labels([("test_id", test_id.as_str())]);
+            container.create().await?;
+            let err = Image::new(builder.client(), tagged.name())
+                .remove(false, false)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, Error::ImageInUse { .. }),
+                "unexpected error: {err:?}"
+            );
+            container.remove(None).await?;
+
+            let name = tagged.name().to_string();
+            tagged.remove(false, false).await?;
+            assert!(Image::new(builder.client(), name).inspect().await.is_err());
+
+            // Only the tag was removed
+            image.inspect().await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
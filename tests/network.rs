@@ -0,0 +1,75 @@
+mod common;
+
+use bollard::container::RemoveContainerOptions;
+use color_eyre::Result;
+use common::{is_docker_running, with_docker_cleanup};
+use docktopus::DockerBuilder;
+use docktopus::container::Container;
+use docktopus::network::{Network, NetworkOptions};
+
+#[tokio::test]
+async fn test_network_round_trip() -> Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let network = Network::create(
+                builder.client(),
+                format!("test-network-{}", test_id),
+                NetworkOptions::new()
+                    .labels([("test_id", test_id.as_str())])
+                    .internal(true)
+                    .subnet("10.213.0.0/24".parse()?)
+                    .gateway("10.213.0.1".parse()?),
+            )
+            .await?;
+
+            let inspect = network.inspect().await?;
+            assert_eq!(inspect.name.as_deref(), Some(network.name()));
+            assert_eq!(inspect.internal, Some(true));
+            let ipam = inspect.ipam.and_then(|ipam| ipam.config).unwrap();
+            assert_eq!(ipam[0].subnet.as_deref(), Some("10.213.0.0/24"));
+            assert_eq!(ipam[0].gateway.as_deref(), Some("10.213.0.1"));
+
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+            let container_id = container.id().unwrap().to_string();
+
+            network.connect(&container_id).await?;
+            let containers = network.inspect().await?.containers.unwrap_or_default();
+            assert!(containers.contains_key(&container_id));
+
+            network.disconnect(&container_id).await?;
+            let containers = network.inspect().await?.containers.unwrap_or_default();
+            assert!(!containers.contains_key(&container_id));
+
+            container
+                .remove(Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+
+            let id = network.id().to_string();
+            network.remove().await?;
+            assert!(
+                builder
+                    .client()
+                    .inspect_network::<String>(&id, None)
+                    .await
+                    .is_err()
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -21,7 +21,7 @@ async fn test_resource_limits() -> Result<()> {
             let network_name = format!("test-network-{}", test_id);
 
             let mut network_labels = HashMap::new();
-            network_labels.insert("test_id".to_string(), test_id.to_string());
+            network_labels.insert("test_id".to_string(), test_id.clone());
 
             // Create network with retry mechanism
             builder
@@ -38,7 +38,7 @@ async fn test_resource_limits() -> Result<()> {
             // Create a service with resource limits
             let mut services = HashMap::new();
             let mut labels = HashMap::new();
-            labels.insert("test_id".to_string(), test_id.to_string());
+            labels.insert("test_id".to_string(), test_id.clone());
 
             services.insert(
                 service_name.clone(),
@@ -49,7 +49,6 @@ async fn test_resource_limits() -> Result<()> {
                         min_memory_gb: 1,
                         min_disk_gb: 1,
                         min_bandwidth_mbps: 100,
-                        required_ports: vec![],
                         data_directory: "/tmp".to_string(),
                         cpu_limit: Some(0.5),
                         memory_limit: Some("512M".to_string()),
@@ -57,6 +56,7 @@ async fn test_resource_limits() -> Result<()> {
                         memory_reservation: Some("256M".to_string()),
                         cpu_shares: Some(512),
                         cpuset_cpus: Some("0,1".to_string()),
+                        ..Default::default()
                     }),
                     networks: Some(vec![network_name.clone()]),
                     labels: Some(labels),
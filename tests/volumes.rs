@@ -1,7 +1,10 @@
 mod common;
 
-use common::with_docker_cleanup;
+use bollard::container::RemoveContainerOptions;
+use common::{is_docker_running, with_docker_cleanup};
 use docktopus::DockerBuilder;
+use docktopus::container::Container;
+use docktopus::volume::{Error, ListFilter, Volume};
 
 #[tokio::test]
 async fn test_volume_management() -> color_eyre::Result<()> {
@@ -25,3 +28,68 @@ async fn test_volume_management() -> color_eyre::Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn test_volume_lifecycle() -> color_eyre::Result<()> {
+    with_docker_cleanup(|test_id| {
+        Box::pin(async move {
+            if !is_docker_running() {
+                println!("Skipping test: Docker is not running");
+                return Ok(());
+            }
+
+            let builder = DockerBuilder::new().await?;
+            builder.pull_image("alpine:latest", None).await?;
+
+            let volume_name = format!("test-volume-{}", test_id);
+            let mut volume = Volume::new(builder.client(), volume_name.clone())
+                .labels([("test_id", test_id.as_str())])
+                .driver_opts([("type", "tmpfs"), ("device", "tmpfs")]);
+            volume.create().await?;
+
+            let inspect = volume.inspect().await?;
+            assert_eq!(inspect.name, volume_name);
+            assert_eq!(inspect.driver, "local");
+            assert_eq!(inspect.labels.get("test_id"), Some(&test_id));
+            assert_eq!(
+                inspect.options.get("type").map(String::as_str),
+                Some("tmpfs")
+            );
+
+            let filter = ListFilter::new().label(format!("test_id={test_id}"));
+            let volumes = Volume::list(builder.client(), filter.clone()).await?;
+            assert_eq!(volumes.len(), 1);
+            assert_eq!(volumes[0].name(), volume_name);
+
+            // A volume mounted into a container can't be removed
+            let mut container = Container::new(builder.client(), "alpine:latest")
+                .cmd(["sleep", "300"])
+                .binds([format!("{volume_name}:/data")])
+                .labels([("test_id", test_id.as_str())]);
+            container.start(false).await?;
+
+            let err = volume.remove(false).await.unwrap_err();
+            assert!(
+                matches!(&err, Error::VolumeInUse(name) if *name == volume_name),
+                "{err:?}"
+            );
+
+            container
+                .remove(Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }))
+                .await?;
+
+            let volume = Volume::list(builder.client(), filter.clone())
+                .await?
+                .pop()
+                .unwrap();
+            volume.remove(false).await?;
+            assert!(Volume::list(builder.client(), filter).await?.is_empty());
+
+            Ok(())
+        })
+    })
+    .await
+}